@@ -43,11 +43,19 @@ pub fn remaining_accounts_to_crank(
     Ok(remaining_accounts.into_iter().collect_vec())
 }
 
+/// Simulate a take order against the book.
+///
+/// With `exact_out` the output amount net of taker fees (`max_base_lots` for
+/// bids, `max_quote_lots_including_fees` for asks) needs to be fully delivered,
+/// as for `PlaceOrderType::ExactOut` orders, otherwise the result is flagged
+/// with `not_enough_liquidity`. Asks may overshoot it by less than a base lot.
+#[allow(clippy::too_many_arguments)]
 pub fn amounts_from_book(
     book: Orderbook,
     side: Side,
     max_base_lots: i64,
     max_quote_lots_including_fees: i64,
+    exact_out: bool,
     market: &Market,
     oracle_price: Option<I80F48>,
     now_ts: u64,
@@ -64,6 +72,7 @@ pub fn amounts_from_book(
             side,
             max_base_lots,
             max_quote_lots_including_fees,
+            exact_out,
             market,
             oracle_price_lots,
             now_ts,
@@ -87,6 +96,7 @@ pub fn iterate_book(
     side: Side,
     max_base_lots: i64,
     max_quote_lots_including_fees: i64,
+    exact_out: bool,
     market: &Market,
     oracle_price_lots: Option<i64>,
     now_ts: u64,
@@ -96,9 +106,20 @@ pub fn iterate_book(
     let mut number_of_processed_fill_events = 0;
     let mut number_of_dropped_expired_orders = 0;
 
-    let order_max_base_lots = max_base_lots;
+    // Exact-out orders receive their output net of taker fees
+    let fees_in_base = market.has_fees_in_base();
+    let order_max_base_lots = if exact_out && side == Side::Bid && fees_in_base {
+        market
+            .add_taker_fees_ceil(max_base_lots, market.base_lot_size)
+            .unwrap_or(max_base_lots)
+    } else {
+        max_base_lots
+    };
     let order_max_quote_lots = match side {
         Side::Bid => market.subtract_taker_fees(max_quote_lots_including_fees),
+        Side::Ask if exact_out && !fees_in_base => market
+            .add_taker_fees_ceil(max_quote_lots_including_fees, market.quote_lot_size)
+            .unwrap_or(max_quote_lots_including_fees),
         Side::Ask => max_quote_lots_including_fees,
     };
 
@@ -117,12 +138,16 @@ pub fn iterate_book(
             continue;
         }
 
-        if remaining_base_lots == 0 || remaining_quote_lots == 0 || limit == 0 {
+        if remaining_base_lots == 0 || remaining_quote_lots <= 0 || limit == 0 {
             break;
         }
 
         let best_opposing_price = best_opposing.price_lots;
-        let max_match_by_quote = remaining_quote_lots / best_opposing_price;
+        let max_match_by_quote = if exact_out && side == Side::Ask {
+            (remaining_quote_lots + best_opposing_price - 1) / best_opposing_price
+        } else {
+            remaining_quote_lots / best_opposing_price
+        };
         if max_match_by_quote == 0 {
             break;
         }
@@ -149,9 +174,27 @@ pub fn iterate_book(
     let total_base_lots_taken = order_max_base_lots - remaining_base_lots;
     let total_quote_lots_taken = order_max_quote_lots - remaining_quote_lots;
 
-    let not_enough_liquidity = match side {
-        Side::Ask => remaining_base_lots != 0,
-        Side::Bid => remaining_quote_lots != 0,
+    let not_enough_liquidity = match (side, exact_out) {
+        (Side::Ask, false) => remaining_base_lots != 0,
+        (Side::Bid, false) => remaining_quote_lots != 0,
+        (Side::Ask, true) => {
+            let taken_native = total_quote_lots_taken * market.quote_lot_size;
+            let fees_native = if fees_in_base {
+                0
+            } else {
+                market.taker_fees_ceil(taken_native)
+            };
+            taken_native - fees_native < max_quote_lots_including_fees * market.quote_lot_size
+        }
+        (Side::Bid, true) => {
+            let taken_native = total_base_lots_taken * market.base_lot_size;
+            let fees_native = if fees_in_base {
+                market.taker_fees_ceil(taken_native)
+            } else {
+                0
+            };
+            taken_native - fees_native < max_base_lots * market.base_lot_size
+        }
     };
 
     (
@@ -161,3 +204,81 @@ pub fn iterate_book(
         not_enough_liquidity,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_market, TestBook};
+
+    fn take(
+        book: &TestBook,
+        market: &Market,
+        side: Side,
+        max_base_lots: i64,
+        max_quote_lots_including_fees: i64,
+    ) -> (i64, i64, bool) {
+        let (base_lots, quote_lots, _, not_enough_liquidity) = iterate_book(
+            book.orderbook(),
+            side,
+            max_base_lots,
+            max_quote_lots_including_fees,
+            true,
+            market,
+            None,
+            0,
+            &mut vec![],
+        );
+        (base_lots, quote_lots, not_enough_liquidity)
+    }
+
+    #[test]
+    fn exact_out_ask_overshoots_net_of_fees() {
+        let mut market = test_market();
+        market.quote_lot_size = 10;
+        market.base_lot_size = 100;
+        market.taker_fee = 400;
+        let mut book = TestBook::default();
+        book.add_fixed(Side::Bid, 10_000, 1, Pubkey::new_unique());
+        book.add_fixed(Side::Bid, 9_000, 1, Pubkey::new_unique());
+
+        // 15000 quote lots after fees doesn't divide by either bid price, the second
+        // fill overshoots it
+        assert_eq!(
+            take(&book, &market, Side::Ask, 5, 15_000),
+            (2, 19_000, false)
+        );
+        assert_eq!(
+            take(&book, &market, Side::Ask, 1, 15_000),
+            (1, 10_000, true)
+        );
+
+        // The first bid covers exactly 10000 quote lots before fees, but not after
+        assert_eq!(
+            take(&book, &market, Side::Ask, 5, 10_000),
+            (2, 19_000, false)
+        );
+        assert_eq!(
+            take(&book, &market, Side::Ask, 5, 20_000),
+            (2, 19_000, true)
+        );
+    }
+
+    #[test]
+    fn exact_out_bid_net_of_base_fees() {
+        let mut market = test_market();
+        market.base_lot_size = 100;
+        market.taker_fee = 400;
+        market.fees_in_base = 1;
+        let mut book = TestBook::default();
+        book.add_fixed(Side::Ask, 100, 5, Pubkey::new_unique());
+
+        // Five base lots lose their taker fee, a sixth one is needed
+        assert_eq!(take(&book, &market, Side::Bid, 5, 1_000), (5, 500, true));
+        book.add_fixed(Side::Ask, 101, 5, Pubkey::new_unique());
+        assert_eq!(take(&book, &market, Side::Bid, 5, 1_000), (6, 601, false));
+
+        // Without fees in base the base is received in full
+        market.fees_in_base = 0;
+        assert_eq!(take(&book, &market, Side::Bid, 5, 1_000), (5, 500, false));
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+use openbook_v2::logs::FillLog;
+use openbook_v2::state::Market;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::event_decoder::{DecodedEvent, OpenBookEvent};
+use crate::report::{as_string, ui_base_lots};
+use crate::trade_backfill::{StoredFill, TradeStore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+    OneDay,
+}
+
+impl Resolution {
+    pub const ALL: [Resolution; 4] = [
+        Resolution::OneMinute,
+        Resolution::FiveMinutes,
+        Resolution::OneHour,
+        Resolution::OneDay,
+    ];
+
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Resolution::OneMinute => 60,
+            Resolution::FiveMinutes => 5 * 60,
+            Resolution::OneHour => 60 * 60,
+            Resolution::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Start of the candle containing `timestamp`
+    pub fn candle_start(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.seconds()
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Resolution::OneMinute => "1m",
+            Resolution::FiveMinutes => "5m",
+            Resolution::OneHour => "1h",
+            Resolution::OneDay => "1d",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Resolution::ALL
+            .into_iter()
+            .find(|r| r.to_string() == s)
+            .ok_or_else(|| anyhow::anyhow!("unknown resolution {}, expected 1m, 5m, 1h or 1d", s))
+    }
+}
+
+/// OHLCV bar in UI units, the volume in base and the quote volume at the fill prices
+#[derive(Clone, Debug, Serialize)]
+pub struct Candle {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub resolution: Resolution,
+    pub start_ts: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub quote_volume: f64,
+    pub vwap: f64,
+    pub trades: u64,
+    /// Sequence numbers of the fills setting the open and close
+    #[serde(skip)]
+    open_seq_num: u64,
+    #[serde(skip)]
+    close_seq_num: u64,
+}
+
+impl Candle {
+    fn new(
+        market: Pubkey,
+        resolution: Resolution,
+        start_ts: u64,
+        seq_num: u64,
+        price: f64,
+    ) -> Self {
+        Self {
+            market,
+            resolution,
+            start_ts,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+            quote_volume: 0.0,
+            vwap: price,
+            trades: 0,
+            open_seq_num: seq_num,
+            close_seq_num: seq_num,
+        }
+    }
+
+    fn add(&mut self, seq_num: u64, price: f64, volume: f64) {
+        if seq_num < self.open_seq_num {
+            self.open = price;
+            self.open_seq_num = seq_num;
+        }
+        if seq_num >= self.close_seq_num {
+            self.close = price;
+            self.close_seq_num = seq_num;
+        }
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.volume += volume;
+        self.quote_volume += price * volume;
+        if self.volume > 0.0 {
+            self.vwap = self.quote_volume / self.volume;
+        }
+        self.trades += 1;
+    }
+}
+
+/// Aggregates the fills of a market into candles of one resolution
+///
+/// Fills can be added in any order, the open and close follow the fill sequence
+/// numbers. The aggregator doesn't deduplicate, each fill must be added once.
+pub struct CandleAggregator {
+    pub market_address: Pubkey,
+    pub market: Market,
+    pub resolution: Resolution,
+    candles: BTreeMap<u64, Candle>,
+}
+
+impl CandleAggregator {
+    pub fn new(market_address: Pubkey, market: Market, resolution: Resolution) -> Self {
+        Self {
+            market_address,
+            market,
+            resolution,
+            candles: BTreeMap::new(),
+        }
+    }
+
+    /// Price of quote UI units per base UI unit
+    fn ui_price(&self, price_lots: i64) -> f64 {
+        let decimals = self.market.base_decimals as i32 - self.market.quote_decimals as i32;
+        self.market.lot_to_native_price(price_lots).to_num::<f64>() * 10f64.powi(decimals)
+    }
+
+    /// Adds a fill and returns the updated candle
+    pub fn add_fill(
+        &mut self,
+        seq_num: u64,
+        timestamp: u64,
+        price_lots: i64,
+        base_lots: i64,
+    ) -> &Candle {
+        let price = self.ui_price(price_lots);
+        let volume = ui_base_lots(&self.market, base_lots);
+        let start_ts = self.resolution.candle_start(timestamp);
+        let (market, resolution) = (self.market_address, self.resolution);
+
+        let candle = self
+            .candles
+            .entry(start_ts)
+            .or_insert_with(|| Candle::new(market, resolution, start_ts, seq_num, price));
+        candle.add(seq_num, price, volume);
+        candle
+    }
+
+    /// Adds a decoded fill, None if it belongs to another market
+    pub fn add_fill_log(&mut self, fill: &FillLog) -> Option<&Candle> {
+        (fill.market == self.market_address)
+            .then(|| self.add_fill(fill.seq_num, fill.timestamp, fill.price, fill.quantity))
+    }
+
+    pub fn add_stored_fill(&mut self, fill: &StoredFill) -> Option<&Candle> {
+        (fill.market == self.market_address)
+            .then(|| self.add_fill(fill.seq_num, fill.timestamp, fill.price, fill.quantity))
+    }
+
+    /// Adds the fill of a streamed event, None for other events
+    pub fn add_event(&mut self, event: &DecodedEvent) -> Option<&Candle> {
+        match &event.event {
+            OpenBookEvent::Fill(fill) => self.add_fill_log(fill),
+            _ => None,
+        }
+    }
+
+    pub fn candle(&self, start_ts: u64) -> Option<&Candle> {
+        self.candles.get(&start_ts)
+    }
+
+    /// Candles in time order, periods without fills have none
+    pub fn candles(&self) -> impl Iterator<Item = &Candle> {
+        self.candles.values()
+    }
+
+    /// Removes and returns the candles that ended before `now_ts`
+    pub fn take_closed(&mut self, now_ts: u64) -> Vec<Candle> {
+        let open = self
+            .candles
+            .split_off(&self.resolution.candle_start(now_ts));
+        std::mem::replace(&mut self.candles, open)
+            .into_values()
+            .collect()
+    }
+}
+
+/// Aggregates the stored fills of a market with `from_ts <= timestamp < to_ts`
+pub fn rebuild_candles(
+    store: &TradeStore,
+    market_address: Pubkey,
+    market: Market,
+    resolution: Resolution,
+    from_ts: u64,
+    to_ts: u64,
+) -> Result<Vec<Candle>> {
+    let mut aggregator = CandleAggregator::new(market_address, market, resolution);
+    for fill in store.fills(&market_address, from_ts, to_ts)? {
+        aggregator.add_stored_fill(&fill);
+    }
+    Ok(aggregator.candles().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{stored_fill, test_market};
+
+    #[test]
+    fn resolution_buckets() {
+        let timestamp = 2 * 86400 + 3 * 3600 + 7 * 60 + 42;
+        let starts: Vec<_> = Resolution::ALL
+            .iter()
+            .map(|r| r.candle_start(timestamp))
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                2 * 86400 + 3 * 3600 + 7 * 60,
+                2 * 86400 + 3 * 3600 + 5 * 60,
+                2 * 86400 + 3 * 3600,
+                2 * 86400,
+            ]
+        );
+        assert_eq!(Resolution::OneHour.candle_start(3600), 3600);
+
+        for resolution in Resolution::ALL {
+            assert_eq!(
+                resolution.to_string().parse::<Resolution>().unwrap(),
+                resolution
+            );
+        }
+        assert!("2m".parse::<Resolution>().is_err());
+    }
+
+    #[test]
+    fn out_of_order_fills() {
+        let market = Pubkey::new_unique();
+        let mut aggregator = CandleAggregator::new(market, test_market(), Resolution::OneMinute);
+
+        aggregator.add_fill(3, 65, 110, 1);
+        aggregator.add_fill(1, 61, 100, 2);
+        aggregator.add_fill(4, 119, 105, 4);
+        let candle = aggregator.add_fill(2, 62, 90, 3);
+
+        // Open and close follow the sequence numbers, not the order fills were added in
+        assert_eq!(candle.start_ts, 60);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.close),
+            (100.0, 110.0, 90.0, 105.0)
+        );
+        assert_eq!(candle.trades, 4);
+        assert_eq!(candle.volume, 10.0);
+        assert_eq!(candle.quote_volume, 110.0 + 200.0 + 270.0 + 420.0);
+        assert_eq!(candle.vwap, 100.0);
+        assert_eq!(aggregator.candles().count(), 1);
+    }
+
+    #[test]
+    fn take_closed_candles() {
+        let market = Pubkey::new_unique();
+        let mut aggregator = CandleAggregator::new(market, test_market(), Resolution::OneMinute);
+        aggregator.add_fill(1, 60, 100, 1);
+        aggregator.add_fill(2, 125, 101, 1);
+
+        // The candle containing now_ts is still open
+        let closed = aggregator.take_closed(125);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].start_ts, 60);
+        assert!(aggregator.candle(60).is_none());
+        assert!(aggregator.candle(120).is_some());
+
+        assert!(aggregator.take_closed(179).is_empty());
+        let closed = aggregator.take_closed(180);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].start_ts, 120);
+        assert_eq!(aggregator.candles().count(), 0);
+    }
+
+    #[test]
+    fn rebuild_from_store() {
+        let mut store = TradeStore::open_in_memory().unwrap();
+        let market = Pubkey::new_unique();
+        let other_market = Pubkey::new_unique();
+        let checkpoint = Default::default();
+        let fills = [
+            stored_fill(market, 2, 70, 102, 1),
+            stored_fill(market, 1, 10, 100, 1),
+            stored_fill(market, 3, 130, 103, 1),
+        ];
+        store.commit(&market, &fills, &checkpoint).unwrap();
+        let other = [stored_fill(other_market, 4, 20, 500, 1)];
+        store.commit(&other_market, &other, &checkpoint).unwrap();
+
+        let candles =
+            rebuild_candles(&store, market, test_market(), Resolution::OneMinute, 0, 130).unwrap();
+        let candles: Vec<_> = candles
+            .iter()
+            .map(|c| (c.start_ts, c.open, c.trades))
+            .collect();
+        assert_eq!(candles, vec![(0, 100.0, 1), (60, 102.0, 1)]);
+    }
+}
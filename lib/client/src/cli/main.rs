@@ -0,0 +1,813 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::Cluster;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
+use openbook_v2::state::{
+    EventHeap, Market, OracleConfigParams, PlaceOrderType, SelfTradeBehavior, Side,
+};
+use openbook_v2::PlaceOrderArgs;
+use openbook_v2_client::candles::{rebuild_candles, Resolution};
+use openbook_v2_client::chain_data::ChainData;
+use openbook_v2_client::event_heap_reader::fetch_event_heap_raw;
+use openbook_v2_client::get_event_log::fetch_raw_transaction_logs;
+use openbook_v2_client::l2_book::{fetch_book_accounts, L2Book};
+use openbook_v2_client::l3_book::L3Book;
+use openbook_v2_client::market_fetch::{fetch_all_markets, fetch_market};
+use openbook_v2_client::report::{
+    print_report, DepositReport, FillReport, MarketReport, MintReport, OutputFormat, VaultReport,
+};
+use openbook_v2_client::trade_backfill::{backfill_fills, TradeStore};
+use openbook_v2_client::trade_fetcher::fetch_fill_events;
+use openbook_v2_client::vault_balances::get_vault_balances;
+use openbook_v2_client::{
+    consume_events_accounts, keypair_from_cli, pubkey_from_cli, CachedAccountFetcher, Client,
+    OpenBookClient, RpcAccountFetcher, TransactionBuilderConfig,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use spl_associated_token_account::get_associated_token_address;
+
+#[derive(Parser, Debug, Clone)]
+#[clap(name = "openbook", about = "Read and trade on OpenBook v2 markets")]
+struct Cli {
+    #[clap(flatten)]
+    rpc: Rpc,
+
+    /// Format of the reports: table, json (one object per line) or csv
+    #[clap(
+        short,
+        long,
+        global = true,
+        default_value = "table",
+        value_parser = OutputFormat::from_str
+    )]
+    output: OutputFormat,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Args, Debug, Clone)]
+struct Rpc {
+    /// Cluster moniker (m, d, t, l) or RPC url
+    #[clap(short, long, global = true, env = "RPC_URL", default_value = "m")]
+    url: String,
+
+    /// Keypair signing write commands, as a file path or json bytes
+    #[clap(
+        short,
+        long,
+        global = true,
+        env = "KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+
+    /// Keypair paying the transaction fees, defaults to --keypair
+    #[clap(long, global = true)]
+    fee_payer: Option<String>,
+
+    /// Priority fee in micro lamports per compute unit
+    #[clap(long, global = true)]
+    prioritization_micro_lamports: Option<u64>,
+}
+
+impl Rpc {
+    fn cluster(&self) -> anyhow::Result<Cluster> {
+        Cluster::from_str(&self.url)
+    }
+
+    fn rpc_client(&self) -> anyhow::Result<RpcClient> {
+        Ok(RpcClient::new_with_commitment(
+            self.cluster()?.url().to_string(),
+            CommitmentConfig::processed(),
+        ))
+    }
+
+    fn owner(&self) -> Arc<Keypair> {
+        Arc::new(keypair_from_cli(&self.keypair))
+    }
+
+    fn client(&self) -> anyhow::Result<Client> {
+        let fee_payer = keypair_from_cli(self.fee_payer.as_ref().unwrap_or(&self.keypair));
+        Ok(Client::new(
+            self.cluster()?,
+            CommitmentConfig::confirmed(),
+            Arc::new(fee_payer),
+            None,
+            TransactionBuilderConfig {
+                prioritization_micro_lamports: self.prioritization_micro_lamports,
+            },
+        ))
+    }
+
+    /// Client for the commands that don't act on an open orders account
+    fn openbook_client(&self) -> anyhow::Result<OpenBookClient> {
+        let client = self.client()?;
+        let account_fetcher = Arc::new(CachedAccountFetcher::new(Arc::new(RpcAccountFetcher {
+            rpc: client.rpc_async(),
+        })));
+        OpenBookClient::new_detail(client, Pubkey::default(), self.owner(), account_fetcher)
+    }
+
+    /// Client acting on the open orders account of the owner on `market`
+    async fn account_client(
+        &self,
+        account: &AccountArgs,
+    ) -> anyhow::Result<(OpenBookClient, Pubkey, Market)> {
+        let client = self.client()?;
+        let owner = self.owner();
+        let market_address = pubkey_from_cli(&account.market);
+        let market: Market = client.rpc_anchor_account(&market_address).await?;
+
+        let open_orders_account = match account.open_orders_account.as_ref() {
+            Some(open_orders_account) => pubkey_from_cli(open_orders_account),
+            None => OpenBookClient::find_accounts(&client, &owner)
+                .await?
+                .into_iter()
+                .find(|(_, open_orders_account)| open_orders_account.market == market_address)
+                .map(|(address, _)| address)
+                .context("no open orders account of the owner on this market")?,
+        };
+
+        let openbook =
+            OpenBookClient::new_for_existing_account(client, open_orders_account, owner).await?;
+        Ok((openbook, market_address, market))
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+struct MarketArgs {
+    #[clap(long, env = "MARKET_PUBKEY")]
+    market: String,
+}
+
+#[derive(Args, Debug, Clone)]
+struct AccountArgs {
+    #[clap(long, env = "MARKET_PUBKEY")]
+    market: String,
+
+    /// Defaults to the first open orders account of the owner on the market
+    #[clap(long)]
+    open_orders_account: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct OrderArgs {
+    #[clap(long, value_parser = parse_side)]
+    side: Side,
+
+    #[clap(long)]
+    price_lots: i64,
+
+    #[clap(long)]
+    max_base_lots: i64,
+
+    #[clap(long, default_value_t = i64::MAX)]
+    max_quote_lots_including_fees: i64,
+
+    #[clap(long, default_value_t = 0)]
+    client_order_id: u64,
+
+    /// limit, ioc, post-only, market, post-only-slide, fok, exact-out or
+    /// post-only-cancel-replace
+    #[clap(long, value_parser = parse_order_type, default_value = "limit")]
+    order_type: PlaceOrderType,
+
+    /// Unix timestamp after which the order expires, 0 for none
+    #[clap(long, default_value_t = 0)]
+    expiry_timestamp: u64,
+
+    /// Maximum number of orders from the book to fill
+    #[clap(long, default_value_t = 10)]
+    limit: u8,
+
+    #[clap(long)]
+    reduce_only: bool,
+}
+
+impl OrderArgs {
+    fn to_place_order_args(&self) -> PlaceOrderArgs {
+        PlaceOrderArgs {
+            side: self.side,
+            price_lots: self.price_lots,
+            max_base_lots: self.max_base_lots,
+            max_quote_lots_including_fees: self.max_quote_lots_including_fees,
+            client_order_id: self.client_order_id,
+            order_type: self.order_type,
+            expiry_timestamp: self.expiry_timestamp,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            limit: self.limit,
+            reduce_only: self.reduce_only,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+struct CreateMarketArgs {
+    #[clap(long)]
+    name: String,
+
+    #[clap(long)]
+    base_mint: String,
+
+    #[clap(long)]
+    quote_mint: String,
+
+    #[clap(long)]
+    base_lot_size: i64,
+
+    #[clap(long)]
+    quote_lot_size: i64,
+
+    /// In units of 1e-6
+    #[clap(long, default_value_t = 0, allow_hyphen_values = true)]
+    maker_fee: i64,
+
+    /// In units of 1e-6
+    #[clap(long, default_value_t = 0)]
+    taker_fee: i64,
+
+    #[clap(long, default_value_t = 1)]
+    min_base_lots: i64,
+
+    #[clap(long, default_value_t = 1)]
+    min_quote_lots: i64,
+
+    #[clap(long, default_value_t = 1)]
+    tick_size_lots: i64,
+
+    #[clap(long, default_value_t = 0)]
+    time_expiry: i64,
+
+    #[clap(long, default_value_t = 0)]
+    auction_end_ts: i64,
+
+    #[clap(long)]
+    fees_in_base: bool,
+
+    #[clap(long)]
+    oracle_a: Option<String>,
+
+    #[clap(long)]
+    oracle_b: Option<String>,
+
+    /// Pool state of Raydium CPMM vault oracles, or the market whose bids and asks
+    /// are the oracles
+    #[clap(long)]
+    oracle_source: Option<String>,
+
+    #[clap(long, default_value_t = 0.1)]
+    conf_filter: f32,
+
+    #[clap(long)]
+    max_staleness_slots: Option<u32>,
+
+    #[clap(long)]
+    max_staleness_seconds: Option<u32>,
+
+    #[clap(long)]
+    max_twap_deviation: Option<f32>,
+
+    /// Defaults to the signing keypair
+    #[clap(long)]
+    collect_fee_admin: Option<String>,
+
+    #[clap(long)]
+    open_orders_admin: Option<String>,
+
+    #[clap(long)]
+    consume_events_admin: Option<String>,
+
+    #[clap(long)]
+    close_market_admin: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// List all markets with their fees, deposits and 24h trade stats
+    Markets,
+    /// List the base and quote vault addresses of all markets
+    Vaults,
+    /// List the base and quote vault balances of all markets
+    Deposits,
+    /// List the base and quote mints of all markets
+    Mints,
+    /// List the fill events in the event heap of a market
+    Trades(MarketArgs),
+    /// Store the fills of all the transactions of a market in a SQLite database,
+    /// resuming from the last run
+    Backfill {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        #[clap(long, default_value = "trades.sqlite")]
+        db: String,
+    },
+    /// Aggregate the fills stored by backfill into OHLCV candles
+    Candles {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        #[clap(long, default_value = "trades.sqlite")]
+        db: String,
+
+        /// 1m, 5m, 1h or 1d
+        #[clap(long, default_value = "1h", value_parser = Resolution::from_str)]
+        resolution: Resolution,
+
+        /// Unix timestamp of the first fill to include
+        #[clap(long, default_value_t = 0)]
+        from: u64,
+
+        /// Unix timestamp after the last fill to include, defaults to now
+        #[clap(long)]
+        to: Option<u64>,
+    },
+    /// List the price levels of the order book of a market, oracle pegged orders included
+    Book {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        /// Number of levels to list on each side
+        #[clap(long, default_value_t = 20)]
+        depth: usize,
+    },
+    /// List the orders on the book of a market with their owners and state
+    Orders {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        /// Only list the orders of this open orders account
+        #[clap(long)]
+        owner: Option<String>,
+    },
+    /// Print the raw event heap of a market
+    EventHeap(MarketArgs),
+    /// Print the logs of all the transactions of a market
+    Logs(MarketArgs),
+    /// Create a market, its order book accounts and vaults
+    CreateMarket(CreateMarketArgs),
+    /// Create the account keeping the 24h trade stats of a market
+    CreateMarketStats(MarketArgs),
+    /// Place an order, paying from the owner token accounts
+    PlaceOrder {
+        #[clap(flatten)]
+        account: AccountArgs,
+
+        #[clap(flatten)]
+        order: OrderArgs,
+    },
+    /// Cancel an order by id, or all orders
+    CancelOrder {
+        #[clap(flatten)]
+        account: AccountArgs,
+
+        #[clap(long, required_unless_present = "all")]
+        order_id: Option<u128>,
+
+        #[clap(long, conflicts_with = "order_id")]
+        all: bool,
+
+        /// Only cancel the orders on this side with --all
+        #[clap(long, value_parser = parse_side)]
+        side: Option<Side>,
+    },
+    /// Replace the order with --client-order-id
+    EditOrder {
+        #[clap(flatten)]
+        account: AccountArgs,
+
+        /// Base lots of the order being replaced, as last observed
+        #[clap(long)]
+        expected_cancel_size: i64,
+
+        #[clap(flatten)]
+        order: OrderArgs,
+    },
+    /// Deposit native amounts from the owner token accounts
+    Deposit {
+        #[clap(flatten)]
+        account: AccountArgs,
+
+        #[clap(long, default_value_t = 0)]
+        base_amount: u64,
+
+        #[clap(long, default_value_t = 0)]
+        quote_amount: u64,
+    },
+    /// Withdraw the free balances to the owner token accounts
+    Settle {
+        #[clap(flatten)]
+        account: AccountArgs,
+
+        #[clap(long)]
+        referrer_account: Option<String>,
+    },
+    /// Consume the events of a market
+    Crank {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        #[clap(long, default_value_t = 8)]
+        limit: usize,
+    },
+    /// Send the market fees to the collect fee admin
+    SweepFees {
+        #[clap(flatten)]
+        market: MarketArgs,
+
+        /// Defaults to the quote token account of the signer
+        #[clap(long)]
+        token_receiver_account: Option<String>,
+
+        /// Defaults to the base token account of the signer on markets with fees in base
+        #[clap(long)]
+        base_token_receiver_account: Option<String>,
+    },
+}
+
+fn parse_side(side: &str) -> Result<Side, String> {
+    match side {
+        "bid" | "buy" => Ok(Side::Bid),
+        "ask" | "sell" => Ok(Side::Ask),
+        _ => Err(format!("unknown side {}", side)),
+    }
+}
+
+fn parse_order_type(order_type: &str) -> Result<PlaceOrderType, String> {
+    match order_type {
+        "limit" => Ok(PlaceOrderType::Limit),
+        "ioc" => Ok(PlaceOrderType::ImmediateOrCancel),
+        "post-only" => Ok(PlaceOrderType::PostOnly),
+        "market" => Ok(PlaceOrderType::Market),
+        "post-only-slide" => Ok(PlaceOrderType::PostOnlySlide),
+        "fok" => Ok(PlaceOrderType::FillOrKill),
+        "exact-out" => Ok(PlaceOrderType::ExactOut),
+        "post-only-cancel-replace" => Ok(PlaceOrderType::PostOnlyCancelReplace),
+        _ => Err(format!("unknown order type {}", order_type)),
+    }
+}
+
+/// Owner token account and market vault an order on `side` pays from
+fn order_accounts(owner: &Pubkey, market: &Market, side: Side) -> (Pubkey, Pubkey) {
+    match side {
+        Side::Bid => (
+            get_associated_token_address(owner, &market.quote_mint),
+            market.market_quote_vault,
+        ),
+        Side::Ask => (
+            get_associated_token_address(owner, &market.base_mint),
+            market.market_base_vault,
+        ),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let default_filter = match cli.command {
+        Command::Logs(_) | Command::Backfill { .. } => "info",
+        _ => "warn",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter))
+        .init();
+
+    let rpc = cli.rpc;
+    let output = cli.output;
+    match cli.command {
+        Command::Markets => {
+            let markets = fetch_all_markets(&rpc.rpc_client()?).await?;
+            let now_ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let rows = markets
+                .iter()
+                .map(|market| MarketReport::new(market, now_ts))
+                .collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::Vaults => {
+            let markets = fetch_all_markets(&rpc.rpc_client()?).await?;
+            let rows = markets.iter().map(VaultReport::new).collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::Deposits => {
+            let client = rpc.rpc_client()?;
+            let markets = fetch_all_markets(&client).await?;
+            let mut rows = vec![];
+            for market in markets.iter() {
+                let (_, base_balance, quote_balance) =
+                    get_vault_balances(&client, market.market_data).await?;
+                rows.push(DepositReport::new(market, base_balance, quote_balance));
+            }
+            print_report(&rows, output)?;
+        }
+        Command::Mints => {
+            let markets = fetch_all_markets(&rpc.rpc_client()?).await?;
+            let rows = markets.iter().map(MintReport::new).collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::Trades(args) => {
+            let client = rpc.rpc_client()?;
+            let market = fetch_market(&client, pubkey_from_cli(&args.market)).await?;
+            let fill_events = fetch_fill_events(&client, market.market_pubkey).await?;
+            let rows = fill_events
+                .iter()
+                .map(|fill| FillReport::new(&market.market_data, fill))
+                .collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::Backfill { market, db } => {
+            let mut store = TradeStore::open(&db)?;
+            let inserted = backfill_fills(
+                &rpc.rpc_client()?,
+                &mut store,
+                pubkey_from_cli(&market.market),
+            )
+            .await?;
+            println!("Stored {} new fills in {}", inserted, db);
+        }
+        Command::Candles {
+            market,
+            db,
+            resolution,
+            from,
+            to,
+        } => {
+            let store = TradeStore::open(&db)?;
+            let market = fetch_market(&rpc.rpc_client()?, pubkey_from_cli(&market.market)).await?;
+            let to = match to {
+                Some(to) => to,
+                None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 1,
+            };
+            let candles = rebuild_candles(
+                &store,
+                market.market_pubkey,
+                market.market_data,
+                resolution,
+                from,
+                to,
+            )?;
+            print_report(&candles, output)?;
+        }
+        Command::Book { market, depth } => {
+            let market = pubkey_from_cli(&market.market);
+            let mut chain_data = ChainData::new();
+            fetch_book_accounts(&rpc.rpc_client()?, &mut chain_data, market).await?;
+            let now_ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let mut book = L2Book::from_chain_data(&chain_data, market, now_ts)?;
+            book.truncate(depth);
+            // Asks from the highest price down to the spread, then the bids
+            let rows = book
+                .asks
+                .iter()
+                .rev()
+                .chain(book.bids.iter())
+                .collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::Orders { market, owner } => {
+            let market = pubkey_from_cli(&market.market);
+            let mut chain_data = ChainData::new();
+            fetch_book_accounts(&rpc.rpc_client()?, &mut chain_data, market).await?;
+            let now_ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let book = L3Book::from_chain_data(&chain_data, market, now_ts)?;
+            let owner = owner.map(|owner| pubkey_from_cli(&owner));
+            let rows = book
+                .bids
+                .iter()
+                .chain(book.asks.iter())
+                .filter(|order| owner.map_or(true, |owner| order.owner == owner))
+                .collect::<Vec<_>>();
+            print_report(&rows, output)?;
+        }
+        Command::EventHeap(args) => {
+            fetch_event_heap_raw(&rpc.rpc_client()?, pubkey_from_cli(&args.market)).await?;
+        }
+        Command::Logs(args) => {
+            fetch_raw_transaction_logs(&rpc.rpc_client()?, pubkey_from_cli(&args.market)).await?;
+        }
+        Command::CreateMarket(args) => {
+            let openbook = rpc.openbook_client()?;
+            let market = Keypair::new();
+            let market_authority = Pubkey::find_program_address(
+                &[b"Market".as_ref(), market.pubkey().to_bytes().as_ref()],
+                &openbook_v2::id(),
+            )
+            .0;
+            let event_authority =
+                Pubkey::find_program_address(&[b"__event_authority"], &openbook_v2::id()).0;
+
+            let (bids, asks, event_heap, _) = openbook.create_market_book_accounts().await?;
+            let txsig = openbook
+                .create_market(
+                    &market,
+                    market_authority,
+                    bids.pubkey(),
+                    asks.pubkey(),
+                    event_heap.pubkey(),
+                    pubkey_from_cli(&args.base_mint),
+                    pubkey_from_cli(&args.quote_mint),
+                    args.oracle_a.as_deref().map(pubkey_from_cli),
+                    args.oracle_b.as_deref().map(pubkey_from_cli),
+                    args.oracle_source.as_deref().map(pubkey_from_cli),
+                    args.collect_fee_admin
+                        .as_deref()
+                        .map_or(openbook.owner(), pubkey_from_cli),
+                    args.open_orders_admin.as_deref().map(pubkey_from_cli),
+                    args.consume_events_admin.as_deref().map(pubkey_from_cli),
+                    args.close_market_admin.as_deref().map(pubkey_from_cli),
+                    event_authority,
+                    args.name,
+                    OracleConfigParams {
+                        conf_filter: args.conf_filter,
+                        max_staleness_slots: args.max_staleness_slots,
+                        max_staleness_seconds: args.max_staleness_seconds,
+                        max_twap_deviation: args.max_twap_deviation,
+                    },
+                    args.base_lot_size,
+                    args.quote_lot_size,
+                    args.maker_fee,
+                    args.taker_fee,
+                    args.time_expiry,
+                    args.min_base_lots,
+                    args.min_quote_lots,
+                    args.tick_size_lots,
+                    args.auction_end_ts,
+                    args.fees_in_base,
+                )
+                .await?;
+            println!("Created market {}: {}", market.pubkey(), txsig);
+        }
+        Command::CreateMarketStats(args) => {
+            let openbook = rpc.openbook_client()?;
+            let (market_stats, txsig) = openbook
+                .create_market_stats(pubkey_from_cli(&args.market))
+                .await?;
+            println!("Created market stats {}: {}", market_stats, txsig);
+        }
+        Command::PlaceOrder { account, order } => {
+            let (openbook, market_address, market) = rpc.account_client(&account).await?;
+            let (user_token_account, market_vault) =
+                order_accounts(&openbook.owner(), &market, order.side);
+            let txsig = openbook
+                .place_order(
+                    market,
+                    market_address,
+                    order.side,
+                    order.price_lots,
+                    order.max_base_lots,
+                    order.max_quote_lots_including_fees,
+                    order.client_order_id,
+                    order.order_type,
+                    order.expiry_timestamp,
+                    order.limit,
+                    user_token_account,
+                    market_vault,
+                    SelfTradeBehavior::default(),
+                    order.reduce_only,
+                )
+                .await?;
+            println!("{}", txsig);
+        }
+        Command::CancelOrder {
+            account,
+            order_id,
+            all,
+            side,
+        } => {
+            let (openbook, market_address, market) = rpc.account_client(&account).await?;
+            let txsig = match order_id {
+                Some(order_id) if !all => {
+                    openbook
+                        .cancel_order(market, market_address, order_id)
+                        .await?
+                }
+                _ => {
+                    openbook
+                        .cancel_all_orders(market, market_address, side, u8::MAX)
+                        .await?
+                }
+            };
+            println!("{}", txsig);
+        }
+        Command::EditOrder {
+            account,
+            expected_cancel_size,
+            order,
+        } => {
+            let (openbook, market_address, market) = rpc.account_client(&account).await?;
+            let (user_token_account, market_vault) =
+                order_accounts(&openbook.owner(), &market, order.side);
+            let txsig = openbook
+                .edit_order(
+                    market,
+                    market_address,
+                    order.client_order_id,
+                    expected_cancel_size,
+                    order.to_place_order_args(),
+                    user_token_account,
+                    market_vault,
+                )
+                .await?;
+            println!("{}", txsig);
+        }
+        Command::Deposit {
+            account,
+            base_amount,
+            quote_amount,
+        } => {
+            let (openbook, market_address, market) = rpc.account_client(&account).await?;
+            let owner = openbook.owner();
+            let txsig = openbook
+                .deposit(
+                    market_address,
+                    base_amount,
+                    quote_amount,
+                    get_associated_token_address(&owner, &market.base_mint),
+                    get_associated_token_address(&owner, &market.quote_mint),
+                    market.market_base_vault,
+                    market.market_quote_vault,
+                )
+                .await?;
+            println!("{}", txsig);
+        }
+        Command::Settle {
+            account,
+            referrer_account,
+        } => {
+            let (openbook, market_address, market) = rpc.account_client(&account).await?;
+            let owner = openbook.owner();
+            let txsig = openbook
+                .settle_funds(
+                    market,
+                    market_address,
+                    get_associated_token_address(&owner, &market.base_mint),
+                    get_associated_token_address(&owner, &market.quote_mint),
+                    market.market_base_vault,
+                    market.market_quote_vault,
+                    referrer_account.as_deref().map(pubkey_from_cli),
+                )
+                .await?;
+            println!("{}", txsig);
+        }
+        Command::Crank { market, limit } => {
+            let openbook = rpc.openbook_client()?;
+            let market_address = pubkey_from_cli(&market.market);
+            let market: Market = openbook.client.rpc_anchor_account(&market_address).await?;
+            let event_heap: EventHeap = openbook
+                .client
+                .rpc_anchor_account(&market.event_heap)
+                .await?;
+            if event_heap.is_empty() {
+                println!("No events to consume");
+                return Ok(());
+            }
+
+            let open_orders_accounts = consume_events_accounts(&event_heap, limit);
+            let txsig = openbook
+                .consume_events(market, market_address, open_orders_accounts, limit)
+                .await?;
+            println!("{}", txsig);
+        }
+        Command::SweepFees {
+            market,
+            token_receiver_account,
+            base_token_receiver_account,
+        } => {
+            let openbook = rpc.openbook_client()?;
+            let owner = openbook.owner();
+            let market_address = pubkey_from_cli(&market.market);
+            let market: Market = openbook.client.rpc_anchor_account(&market_address).await?;
+
+            let token_receiver_account = token_receiver_account.map_or_else(
+                || get_associated_token_address(&owner, &market.quote_mint),
+                |account| pubkey_from_cli(&account),
+            );
+            let base_token_receiver_account = match base_token_receiver_account {
+                Some(account) => Some(pubkey_from_cli(&account)),
+                None if market.has_fees_in_base() => {
+                    Some(get_associated_token_address(&owner, &market.base_mint))
+                }
+                None => None,
+            };
+            let txsig = openbook
+                .sweep_fees(
+                    market,
+                    market_address,
+                    token_receiver_account,
+                    base_token_receiver_account,
+                )
+                .await?;
+            println!("{}", txsig);
+        }
+    }
+
+    Ok(())
+}
@@ -9,12 +9,16 @@ use anchor_lang::{AccountDeserialize, Id};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::Token;
 
+use bytemuck::cast_ref;
+
 use itertools::Itertools;
 
-use openbook_v2::state::OracleConfigParams;
+use openbook_v2::state::{
+    AnyEvent, BookSide, EventHeap, EventType, FillEvent, OracleConfigParams, OutEvent,
+};
 use openbook_v2::{
     state::{Market, OpenOrdersAccount, PlaceOrderType, SelfTradeBehavior, Side},
-    PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs,
+    PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs, RouteTakeOrderArgs,
 };
 
 use solana_client::nonblocking::rpc_client::RpcClient as RpcClientAsync;
@@ -27,9 +31,10 @@ use solana_sdk::transaction::TransactionError;
 
 use crate::account_fetcher::*;
 use crate::gpa::{fetch_anchor_account, fetch_openbook_accounts};
+use crate::route::route_take_order_accounts;
 
 use anyhow::Context;
-use solana_sdk::instruction::Instruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::signature::{Keypair, Signature};
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signer::Signer};
 
@@ -300,10 +305,55 @@ impl OpenBookClient {
             .await
     }
 
+    /// Allocates the bids, asks and event heap accounts that `create_market` expects
+    /// to be zeroed and owned by the program
+    pub async fn create_market_book_accounts(
+        &self,
+    ) -> anyhow::Result<(Keypair, Keypair, Keypair, Signature)> {
+        let rpc = self.client.rpc_async();
+        let bids = Keypair::new();
+        let asks = Keypair::new();
+        let event_heap = Keypair::new();
+
+        let mut instructions = vec![];
+        for (account, space) in [
+            (&bids, 8 + std::mem::size_of::<BookSide>()),
+            (&asks, 8 + std::mem::size_of::<BookSide>()),
+            (&event_heap, 8 + std::mem::size_of::<EventHeap>()),
+        ] {
+            let lamports = rpc.get_minimum_balance_for_rent_exemption(space).await?;
+            instructions.push(solana_sdk::system_instruction::create_account(
+                &self.owner(),
+                &account.pubkey(),
+                lamports,
+                space as u64,
+                &openbook_v2::id(),
+            ));
+        }
+
+        let txsig = TransactionBuilder {
+            instructions,
+            address_lookup_tables: vec![],
+            payer: self.client.fee_payer.pubkey(),
+            signers: vec![
+                &*self.owner,
+                &*self.client.fee_payer,
+                &bids,
+                &asks,
+                &event_heap,
+            ],
+            config: self.client.transaction_builder_config,
+        }
+        .send_and_confirm(&self.client)
+        .await?;
+
+        Ok((bids, asks, event_heap, txsig))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_market(
         &self,
-        market: Pubkey,
+        market: &Keypair,
         market_authority: Pubkey,
         bids: Pubkey,
         asks: Pubkey,
@@ -312,6 +362,7 @@ impl OpenBookClient {
         quote_mint: Pubkey,
         oracle_a: Option<Pubkey>,
         oracle_b: Option<Pubkey>,
+        oracle_source: Option<Pubkey>,
         collect_fee_admin: Pubkey,
         open_orders_admin: Option<Pubkey>,
         consume_events_admin: Option<Pubkey>,
@@ -324,13 +375,18 @@ impl OpenBookClient {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        min_base_lots: i64,
+        min_quote_lots: i64,
+        tick_size_lots: i64,
+        auction_end_ts: i64,
+        fees_in_base: bool,
     ) -> anyhow::Result<Signature> {
         let ix = Instruction {
             program_id: openbook_v2::id(),
             accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
+                let mut metas = anchor_lang::ToAccountMetas::to_account_metas(
                     &openbook_v2::accounts::CreateMarket {
-                        market,
+                        market: market.pubkey(),
                         market_authority,
                         bids,
                         asks,
@@ -361,7 +417,10 @@ impl OpenBookClient {
                         associated_token_program: AssociatedToken::id(),
                     },
                     None,
-                )
+                );
+                // The pool state of vault oracles or the market of book side oracles
+                metas.extend(oracle_source.map(|pk| AccountMeta::new_readonly(pk, false)));
+                metas
             },
             data: anchor_lang::InstructionData::data(&openbook_v2::instruction::CreateMarket {
                 name,
@@ -371,9 +430,22 @@ impl OpenBookClient {
                 maker_fee,
                 taker_fee,
                 time_expiry,
+                min_base_lots,
+                min_quote_lots,
+                tick_size_lots,
+                auction_end_ts,
+                fees_in_base,
             }),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        TransactionBuilder {
+            instructions: vec![ix],
+            address_lookup_tables: vec![],
+            payer: self.client.fee_payer.pubkey(),
+            signers: vec![&*self.owner, &*self.client.fee_payer, market],
+            config: self.client.transaction_builder_config,
+        }
+        .send_and_confirm(&self.client)
+        .await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -392,6 +464,7 @@ impl OpenBookClient {
         user_token_account: Pubkey,
         market_vault: Pubkey,
         self_trade_behavior: SelfTradeBehavior,
+        reduce_only: bool,
     ) -> anyhow::Result<Signature> {
         let ix = Instruction {
             program_id: openbook_v2::id(),
@@ -407,9 +480,12 @@ impl OpenBookClient {
                         event_heap: market.event_heap,
                         oracle_a: market.oracle_a.into(),
                         oracle_b: market.oracle_b.into(),
-                        user_token_account,
+                        user_token_account: Some(user_token_account),
                         market_vault,
                         token_program: Token::id(),
+                        native_payer: None,
+                        system_program: None,
+                        market_stats: market_stats_account(&market_address, &market),
                     },
                     None,
                 )
@@ -425,12 +501,59 @@ impl OpenBookClient {
                     expiry_timestamp,
                     self_trade_behavior,
                     limit,
+                    reduce_only,
                 },
             }),
         };
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
+    /// Cancels the order with `client_order_id` and places a new one, reduced by the
+    /// base lots filled since `expected_cancel_size` was observed
+    #[allow(clippy::too_many_arguments)]
+    pub async fn edit_order(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        client_order_id: u64,
+        expected_cancel_size: i64,
+        place_order: PlaceOrderArgs,
+        user_token_account: Pubkey,
+        market_vault: Pubkey,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::PlaceOrder {
+                        open_orders_account: self.open_orders_account,
+                        open_orders_admin: None,
+                        signer: self.owner(),
+                        market: market_address,
+                        bids: market.bids,
+                        asks: market.asks,
+                        event_heap: market.event_heap,
+                        oracle_a: market.oracle_a.into(),
+                        oracle_b: market.oracle_b.into(),
+                        user_token_account: Some(user_token_account),
+                        market_vault,
+                        token_program: Token::id(),
+                        native_payer: None,
+                        system_program: None,
+                        market_stats: market_stats_account(&market_address, &market),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::EditOrder {
+                client_order_id,
+                expected_cancel_size,
+                place_order,
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn place_order_pegged(
         &self,
@@ -438,6 +561,7 @@ impl OpenBookClient {
         market_address: Pubkey,
         side: Side,
         price_offset_lots: i64,
+        price_offset_bps: Option<i32>,
         peg_limit: i64,
         max_base_lots: i64,
         max_quote_lots_including_fees: i64,
@@ -463,9 +587,12 @@ impl OpenBookClient {
                         event_heap: market.event_heap,
                         oracle_a: market.oracle_a.into(),
                         oracle_b: market.oracle_b.into(),
-                        user_token_account,
+                        user_token_account: Some(user_token_account),
                         market_vault,
                         token_program: Token::id(),
+                        native_payer: None,
+                        system_program: None,
+                        market_stats: market_stats_account(&market_address, &market),
                     },
                     None,
                 )
@@ -474,6 +601,7 @@ impl OpenBookClient {
                 args: PlaceOrderPeggedArgs {
                     side,
                     price_offset_lots,
+                    price_offset_bps,
                     peg_limit,
                     max_base_lots,
                     max_quote_lots_including_fees,
@@ -577,6 +705,7 @@ impl OpenBookClient {
                         oracle_a: market.oracle_a.into(),
                         oracle_b: market.oracle_b.into(),
                         token_program: Token::id(),
+                        market_stats: market_stats_account(&market_address, &market),
                     },
                     None,
                 )
@@ -612,11 +741,13 @@ impl OpenBookClient {
                         open_orders_account: self.open_orders_account,
                         owner: self.owner(),
                         market: market_address,
-                        user_base_account,
-                        user_quote_account,
+                        user_base_account: Some(user_base_account),
+                        user_quote_account: Some(user_quote_account),
                         market_base_vault,
                         market_quote_vault,
                         token_program: Token::id(),
+                        native_payer: None,
+                        system_program: None,
                     },
                     None,
                 )
@@ -650,13 +781,15 @@ impl OpenBookClient {
                         open_orders_account: self.open_orders_account,
                         market: market_address,
                         market_authority: market.market_authority,
-                        user_base_account,
-                        user_quote_account,
+                        user_base_account: Some(user_base_account),
+                        user_quote_account: Some(user_quote_account),
                         market_base_vault,
                         market_quote_vault,
                         referrer_account,
                         system_program: System::id(),
                         token_program: Token::id(),
+                        native_mint: None,
+                        unwrap_account: None,
                     },
                     None,
                 )
@@ -666,24 +799,71 @@ impl OpenBookClient {
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
+    /// Swap `amount_in` of `input_mint` through two markets sharing a mint, using the
+    /// owner's associated token accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn route_take_order(
+        &self,
+        first_market: Market,
+        first_market_address: Pubkey,
+        second_market: Market,
+        second_market_address: Pubkey,
+        input_mint: Pubkey,
+        amount_in: u64,
+        min_amount_out: u64,
+        limit: u8,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &route_take_order_accounts(
+                        self.owner(),
+                        &input_mint,
+                        first_market_address,
+                        &first_market,
+                        second_market_address,
+                        &second_market,
+                    )?,
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::RouteTakeOrder {
+                args: RouteTakeOrderArgs {
+                    amount_in,
+                    min_amount_out,
+                    limit,
+                },
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn consume_events(
         &self,
         market: Market,
         market_address: Pubkey,
+        open_orders_accounts: Vec<Pubkey>,
         limit: usize,
     ) -> anyhow::Result<Signature> {
         let ix = Instruction {
             program_id: openbook_v2::id(),
             accounts: {
-                anchor_lang::ToAccountMetas::to_account_metas(
+                let mut metas = anchor_lang::ToAccountMetas::to_account_metas(
                     &openbook_v2::accounts::ConsumeEvents {
                         consume_events_admin: market.consume_events_admin.into(),
                         market: market_address,
                         event_heap: market.event_heap,
                     },
                     None,
-                )
+                );
+                metas.extend(
+                    open_orders_accounts
+                        .into_iter()
+                        .map(|pk| AccountMeta::new(pk, false)),
+                );
+                metas
             },
             data: anchor_lang::InstructionData::data(&openbook_v2::instruction::ConsumeEvents {
                 limit,
@@ -692,6 +872,109 @@ impl OpenBookClient {
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
+    /// Consumes the events at the given heap slots, the open orders accounts they
+    /// refer to have to be passed
+    pub async fn consume_given_events(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        open_orders_accounts: Vec<Pubkey>,
+        slots: Vec<usize>,
+    ) -> anyhow::Result<Signature> {
+        let ix =
+            consume_given_events_instruction(&market, market_address, open_orders_accounts, slots);
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    /// Sends the fees available to `token_receiver_account`, the owner has to be the
+    /// collect fee admin. Base fees are only swept if `base_token_receiver_account` is set.
+    pub async fn sweep_fees(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        token_receiver_account: Pubkey,
+        base_token_receiver_account: Option<Pubkey>,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::SweepFees {
+                        collect_fee_admin: self.owner(),
+                        market: market_address,
+                        market_authority: market.market_authority,
+                        market_quote_vault: market.market_quote_vault,
+                        token_receiver_account,
+                        token_program: Token::id(),
+                        market_base_vault: base_token_receiver_account
+                            .map(|_| market.market_base_vault),
+                        base_token_receiver_account,
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::SweepFees {}),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    /// Creates the `MarketStats` account of a market, paid by the fee payer. Matching
+    /// orders on the market needs it from then on.
+    pub async fn create_market_stats(
+        &self,
+        market_address: Pubkey,
+    ) -> anyhow::Result<(Pubkey, Signature)> {
+        let market_stats = market_stats_address(&market_address);
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::CreateMarketStats {
+                        payer: self.client.fee_payer.pubkey(),
+                        market: market_address,
+                        market_stats,
+                        system_program: System::id(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::CreateMarketStats {},
+            ),
+        };
+        let signature = self.send_and_confirm_owner_tx(vec![ix]).await?;
+        Ok((market_stats, signature))
+    }
+
+    pub async fn uncross_auction(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        limit: u8,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::UncrossAuction {
+                        market: market_address,
+                        bids: market.bids,
+                        asks: market.asks,
+                        event_heap: market.event_heap,
+                        oracle_a: market.oracle_a.into(),
+                        oracle_b: market.oracle_b.into(),
+                        market_stats: market_stats_account(&market_address, &market),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::UncrossAuction {
+                limit,
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
     pub async fn send_and_confirm_owner_tx(
         &self,
         instructions: Vec<Instruction>,
@@ -839,6 +1122,72 @@ pub fn prettify_solana_client_error(
     err.into()
 }
 
+/// Address of the `MarketStats` account of a market
+pub fn market_stats_address(market_address: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"MarketStats".as_ref(), market_address.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The market stats account to pass to instructions matching orders, needed once
+/// the market has one
+pub fn market_stats_account(market_address: &Pubkey, market: &Market) -> Option<Pubkey> {
+    market
+        .has_market_stats()
+        .then(|| market_stats_address(market_address))
+}
+
+/// Open orders account consuming an event: the maker of a fill, the owner of an out
+pub fn event_open_orders_account(event: &AnyEvent) -> Option<Pubkey> {
+    match EventType::try_from(event.event_type) {
+        Ok(EventType::Fill) => Some(cast_ref::<AnyEvent, FillEvent>(event).maker),
+        Ok(EventType::Out) => Some(cast_ref::<AnyEvent, OutEvent>(event).owner),
+        Err(_) => None,
+    }
+}
+
+/// Open orders accounts needed to consume the next `limit` events of the heap
+pub fn consume_events_accounts(event_heap: &EventHeap, limit: usize) -> Vec<Pubkey> {
+    event_heap
+        .iter()
+        .take(limit)
+        .filter_map(|(event, _)| event_open_orders_account(event))
+        .unique()
+        .collect()
+}
+
+/// Instruction consuming the events at the heap `slots`, signed by the consume
+/// events admin of the market if it has one
+pub fn consume_given_events_instruction(
+    market: &Market,
+    market_address: Pubkey,
+    open_orders_accounts: Vec<Pubkey>,
+    slots: Vec<usize>,
+) -> Instruction {
+    let mut metas = anchor_lang::ToAccountMetas::to_account_metas(
+        &openbook_v2::accounts::ConsumeEvents {
+            consume_events_admin: market.consume_events_admin.into(),
+            market: market_address,
+            event_heap: market.event_heap,
+        },
+        None,
+    );
+    metas.extend(
+        open_orders_accounts
+            .into_iter()
+            .map(|pk| AccountMeta::new(pk, false)),
+    );
+    Instruction {
+        program_id: openbook_v2::id(),
+        accounts: metas,
+        data: anchor_lang::InstructionData::data(&openbook_v2::instruction::ConsumeGivenEvents {
+            slots,
+        }),
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum JupiterSwapMode {
     ExactIn,
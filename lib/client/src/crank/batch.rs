@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use openbook_v2::state::{EventHeap, MAX_EVENTS_CONSUME};
+use openbook_v2_client::event_open_orders_account;
+use solana_sdk::pubkey::Pubkey;
+
+/// Compute units a transaction may use at most
+const MAX_TX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Compute units of an instruction besides its events
+const IX_COMPUTE_UNITS: u32 = 20_000;
+
+/// Successful transactions in a row before the batch grows
+const GROW_AFTER: u32 = 3;
+
+/// The events of one consume_given_events instruction
+pub struct IxBatch {
+    pub slots: Vec<usize>,
+    pub open_orders_accounts: Vec<Pubkey>,
+}
+
+/// Picks up to `max_events` events, oldest first, referring to at most
+/// `max_accounts` open orders accounts, split in instructions
+///
+/// Once the account budget is used, the later events of the accounts already
+/// picked still join the batch, skipping over the events in between.
+pub fn plan_batch(event_heap: &EventHeap, max_events: usize, max_accounts: usize) -> Vec<IxBatch> {
+    let mut accounts = HashSet::new();
+    let mut picked = vec![];
+    for (event, slot) in event_heap.iter() {
+        if picked.len() >= max_events {
+            break;
+        }
+        let Some(account) = event_open_orders_account(event) else {
+            continue;
+        };
+        if accounts.contains(&account) || accounts.len() < max_accounts {
+            accounts.insert(account);
+            picked.push((slot, account));
+        }
+    }
+
+    picked
+        .chunks(MAX_EVENTS_CONSUME)
+        .map(|events| IxBatch {
+            slots: events.iter().map(|(slot, _)| *slot).collect(),
+            open_orders_accounts: events
+                .iter()
+                .map(|(_, account)| *account)
+                .unique()
+                .collect(),
+        })
+        .collect()
+}
+
+/// Number of events per transaction and the compute units they are expected to
+/// use, growing while transactions land and shrinking when they fail
+pub struct BatchSizer {
+    pub max_events: usize,
+    pub compute_units_per_event: u32,
+    limit: usize,
+    successes: u32,
+}
+
+impl BatchSizer {
+    pub fn new(limit: usize, compute_units_per_event: u32) -> Self {
+        let mut sizer = Self {
+            max_events: MAX_EVENTS_CONSUME.min(limit),
+            compute_units_per_event: compute_units_per_event.max(1),
+            limit,
+            successes: 0,
+        };
+        sizer.fit_compute_limit();
+        sizer
+    }
+
+    /// Compute unit limit to request for `batch`
+    pub fn compute_unit_limit(&self, batch: &[IxBatch]) -> u32 {
+        let events: usize = batch.iter().map(|ix| ix.slots.len()).sum();
+        (IX_COMPUTE_UNITS * batch.len() as u32 + self.compute_units_per_event * events as u32)
+            .min(MAX_TX_COMPUTE_UNITS)
+    }
+
+    pub fn on_success(&mut self) {
+        self.successes += 1;
+        if self.successes >= GROW_AFTER && self.max_events < self.limit {
+            self.max_events = (self.max_events + MAX_EVENTS_CONSUME / 2).min(self.limit);
+            self.fit_compute_limit();
+            self.successes = 0;
+        }
+    }
+
+    /// Halves the batch, and raises the compute estimate if the budget ran out
+    pub fn on_failure(&mut self, compute_exceeded: bool) {
+        self.successes = 0;
+        self.max_events = (self.max_events / 2).max(1);
+        if compute_exceeded {
+            self.compute_units_per_event =
+                (self.compute_units_per_event * 3 / 2).min(MAX_TX_COMPUTE_UNITS - IX_COMPUTE_UNITS);
+        }
+        self.fit_compute_limit();
+    }
+
+    fn fit_compute_limit(&mut self) {
+        let ixs = ((self.max_events + MAX_EVENTS_CONSUME - 1) / MAX_EVENTS_CONSUME) as u32;
+        let available = MAX_TX_COMPUTE_UNITS.saturating_sub(IX_COMPUTE_UNITS * ixs);
+        let fitting = (available / self.compute_units_per_event).max(1) as usize;
+        self.max_events = self.max_events.min(fitting);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::{cast, Zeroable};
+    use openbook_v2::state::{AnyEvent, FillEvent, OutEvent, Side};
+
+    fn push_fill(event_heap: &mut EventHeap, maker: Pubkey) {
+        let fill = FillEvent::new(
+            Side::Bid,
+            false,
+            0,
+            0,
+            0,
+            maker,
+            0,
+            0,
+            Pubkey::new_unique(),
+            0,
+            1,
+            -1,
+            1,
+        );
+        event_heap.push_back(cast(fill));
+    }
+
+    fn push_out(event_heap: &mut EventHeap, owner: Pubkey) {
+        let out = OutEvent::new(Side::Ask, 0, 0, 0, owner, 1);
+        event_heap.push_back(cast(out));
+    }
+
+    fn new_event_heap() -> EventHeap {
+        let mut event_heap = EventHeap::zeroed();
+        event_heap.init();
+        event_heap
+    }
+
+    #[test]
+    fn account_budget() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut event_heap = new_event_heap();
+        push_fill(&mut event_heap, a);
+        push_out(&mut event_heap, b);
+        push_fill(&mut event_heap, c);
+        push_out(&mut event_heap, a);
+        push_fill(&mut event_heap, b);
+
+        // The events of c are skipped, the later ones of a and b still join
+        let batch = plan_batch(&event_heap, 10, 2);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].slots, vec![0, 1, 3, 4]);
+        assert_eq!(batch[0].open_orders_accounts, vec![a, b]);
+
+        let batch = plan_batch(&event_heap, 2, 3);
+        assert_eq!(batch[0].slots, vec![0, 1]);
+        assert_eq!(batch[0].open_orders_accounts, vec![a, b]);
+    }
+
+    #[test]
+    fn chunks_by_instruction() {
+        let a = Pubkey::new_unique();
+        let mut event_heap = new_event_heap();
+        let mut unknown = AnyEvent::zeroed();
+        unknown.event_type = u8::MAX;
+        event_heap.push_back(unknown);
+        for _ in 0..20 {
+            push_fill(&mut event_heap, a);
+        }
+
+        let batch = plan_batch(&event_heap, 19, 1);
+        let sizes: Vec<_> = batch.iter().map(|ix| ix.slots.len()).collect();
+        assert_eq!(sizes, vec![MAX_EVENTS_CONSUME, MAX_EVENTS_CONSUME, 3]);
+        assert_eq!(batch[0].slots[0], 1);
+        assert!(batch.iter().all(|ix| ix.open_orders_accounts == vec![a]));
+    }
+
+    #[test]
+    fn sizer_grows_and_shrinks() {
+        let mut sizer = BatchSizer::new(32, 10_000);
+        assert_eq!(sizer.max_events, MAX_EVENTS_CONSUME);
+
+        sizer.on_success();
+        sizer.on_success();
+        assert_eq!(sizer.max_events, 8);
+        sizer.on_success();
+        assert_eq!(sizer.max_events, 12);
+
+        // A failure resets the successes in a row
+        sizer.on_success();
+        sizer.on_failure(false);
+        assert_eq!(sizer.max_events, 6);
+        assert_eq!(sizer.compute_units_per_event, 10_000);
+        sizer.on_success();
+        sizer.on_success();
+        assert_eq!(sizer.max_events, 6);
+
+        sizer.on_failure(true);
+        assert_eq!(sizer.max_events, 3);
+        assert_eq!(sizer.compute_units_per_event, 15_000);
+        for _ in 0..10 {
+            sizer.on_failure(false);
+        }
+        assert_eq!(sizer.max_events, 1);
+
+        let batch = [
+            IxBatch {
+                slots: vec![0, 1, 2],
+                open_orders_accounts: vec![],
+            },
+            IxBatch {
+                slots: vec![3, 4],
+                open_orders_accounts: vec![],
+            },
+        ];
+        assert_eq!(
+            sizer.compute_unit_limit(&batch),
+            2 * IX_COMPUTE_UNITS + 5 * 15_000
+        );
+    }
+
+    #[test]
+    fn sizer_limits() {
+        // Growth stops at the limit
+        let mut sizer = BatchSizer::new(10, 10_000);
+        for _ in 0..9 {
+            sizer.on_success();
+        }
+        assert_eq!(sizer.max_events, 10);
+
+        // Expensive events fit fewer in a transaction
+        let sizer = BatchSizer::new(100, 200_000);
+        assert_eq!(sizer.max_events, 6);
+        let batch = [IxBatch {
+            slots: (0..100).collect(),
+            open_orders_accounts: vec![],
+        }];
+        assert_eq!(sizer.compute_unit_limit(&batch), MAX_TX_COMPUTE_UNITS);
+    }
+}
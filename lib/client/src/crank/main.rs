@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anchor_client::Cluster;
+use clap::Parser;
+use log::*;
+use openbook_v2::accounts_zerocopy::LoadZeroCopy;
+use openbook_v2::state::{EventHeap, Market, MAX_NUM_EVENTS};
+use openbook_v2_client::account_update_stream::Message;
+use openbook_v2_client::chain_data::{AccountAndSlot, ChainData};
+use openbook_v2_client::market_fetch::fetch_market;
+use openbook_v2_client::websocket_source;
+use openbook_v2_client::{
+    consume_given_events_instruction, keypair_from_cli, pubkey_from_cli, CachedAccountFetcher,
+    Client, OpenBookClient, RpcAccountFetcher, TransactionBuilderConfig,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use tokio::sync::mpsc;
+
+mod batch;
+
+use batch::{plan_batch, BatchSizer};
+
+/// A market is cranked again after a landed transaction once its event heap was
+/// written, or after this long in case the write was missed
+const CONSUMED_WAIT: Duration = Duration::from_secs(10);
+
+/// Longest wait before retrying a market after failed transactions
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "crank",
+    about = "Consume the events of OpenBook v2 markets as they are pushed to their event heaps"
+)]
+struct Cli {
+    /// Cluster moniker (m, d, t, l) or RPC url
+    #[clap(short, long, env = "RPC_URL", default_value = "m")]
+    url: String,
+
+    /// Websocket url of the RPC node, derived from --url by default
+    #[clap(long, env = "RPC_WS_URL")]
+    ws_url: Option<String>,
+
+    /// Keypair paying the transactions and signing as consume events admin, as a
+    /// file path or json bytes
+    #[clap(
+        short,
+        long,
+        env = "KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+
+    /// Markets to crank
+    #[clap(long, env = "MARKETS", required = true, value_delimiter = ',')]
+    market: Vec<String>,
+
+    /// Priority fee in micro lamports per compute unit
+    #[clap(long)]
+    prioritization_micro_lamports: Option<u64>,
+
+    /// Milliseconds between two looks at the event heaps
+    #[clap(long, default_value_t = 500)]
+    interval_ms: u64,
+
+    /// Most events consumed in one transaction
+    #[clap(long, default_value_t = 32)]
+    max_events: usize,
+
+    /// Most open orders accounts passed to one transaction
+    #[clap(long, default_value_t = 20)]
+    max_accounts: usize,
+
+    /// Starting estimate of the compute units used per event, raised when
+    /// transactions run out of compute
+    #[clap(long, default_value_t = 25_000)]
+    compute_units_per_event: u32,
+
+    /// Events a heap holds at least before it is cranked
+    #[clap(long, default_value_t = 1)]
+    min_events: usize,
+}
+
+/// Cranking state of a market
+struct MarketCrank {
+    market_address: Pubkey,
+    market: Market,
+    /// Set when the crank keypair is the consume events admin of the market
+    permissioned: bool,
+    sizer: BatchSizer,
+    in_flight: bool,
+    /// Event heap slot a landed transaction was planned from, and when it landed
+    consumed: Option<(u64, Instant)>,
+    failures: u32,
+    retry_at: Instant,
+}
+
+impl MarketCrank {
+    /// Whether the last landed transaction may not show in the event heap yet
+    fn waiting_for_heap(&self, heap_slot: u64) -> bool {
+        self.consumed
+            .is_some_and(|(slot, at)| heap_slot <= slot && at.elapsed() < CONSUMED_WAIT)
+    }
+
+    fn on_result(&mut self, heap_slot: u64, result: anyhow::Result<Signature>) {
+        self.in_flight = false;
+        match result {
+            Ok(signature) => {
+                debug!("cranked {}: {}", self.market_address, signature);
+                self.sizer.on_success();
+                self.consumed = Some((heap_slot, Instant::now()));
+                self.failures = 0;
+            }
+            Err(err) => {
+                let message = format!("{:?}", err);
+                let compute_exceeded = message.contains("exceeded CUs meter")
+                    || message.contains("ComputationalBudgetExceeded");
+                self.sizer.on_failure(compute_exceeded);
+                self.consumed = None;
+                self.failures += 1;
+                let backoff = Duration::from_millis(500)
+                    .saturating_mul(1 << self.failures.min(6))
+                    .min(MAX_BACKOFF);
+                self.retry_at = Instant::now() + backoff;
+                warn!(
+                    "cranking {} failed {} times, {} events per transaction next: {}",
+                    self.market_address, self.failures, self.sizer.max_events, message
+                );
+            }
+        }
+    }
+}
+
+fn update_chain_data(chain_data: &mut ChainData, message: Message) {
+    match message {
+        // Snapshot writes can precede any slot update, add them as RPC data so
+        // they are live right away
+        Message::Snapshot(accounts) => {
+            for account in accounts {
+                chain_data.update_from_rpc(
+                    &account.pubkey,
+                    AccountAndSlot {
+                        slot: account.slot,
+                        account: account.account,
+                    },
+                );
+            }
+        }
+        message => message.update_chain_data(chain_data),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let cli = Cli::parse();
+    let cluster = Cluster::from_str(&cli.url)?;
+    let keypair = Arc::new(keypair_from_cli(&cli.keypair));
+    let client = Client::new(
+        cluster.clone(),
+        CommitmentConfig::confirmed(),
+        keypair.clone(),
+        None,
+        TransactionBuilderConfig {
+            prioritization_micro_lamports: cli.prioritization_micro_lamports,
+        },
+    );
+    let account_fetcher = Arc::new(CachedAccountFetcher::new(Arc::new(RpcAccountFetcher {
+        rpc: client.rpc_async(),
+    })));
+    let openbook_client = Arc::new(OpenBookClient::new_detail(
+        client,
+        Pubkey::default(),
+        keypair.clone(),
+        account_fetcher,
+    )?);
+
+    let rpc_client = RpcClient::new(cluster.url().to_string());
+    let mut cranks = HashMap::new();
+    for market in cli.market.iter() {
+        let market = fetch_market(&rpc_client, pubkey_from_cli(market)).await?;
+        let admin: Option<Pubkey> = market.market_data.consume_events_admin.into();
+        if let Some(admin) = admin.filter(|admin| *admin != keypair.pubkey()) {
+            warn!(
+                "skipping {}, its consume events admin {} isn't the crank keypair",
+                market.market_pubkey, admin
+            );
+            continue;
+        }
+        cranks.insert(
+            market.market_data.event_heap,
+            MarketCrank {
+                market_address: market.market_pubkey,
+                market: market.market_data,
+                permissioned: admin.is_some(),
+                sizer: BatchSizer::new(cli.max_events.max(1), cli.compute_units_per_event),
+                in_flight: false,
+                consumed: None,
+                failures: 0,
+                retry_at: Instant::now(),
+            },
+        );
+    }
+    anyhow::ensure!(!cranks.is_empty(), "no market to crank");
+    info!("cranking {} markets", cranks.len());
+
+    let (sender, receiver) = async_channel::unbounded();
+    websocket_source::start(
+        websocket_source::Config {
+            rpc_http_url: cluster.url().to_string(),
+            rpc_ws_url: cli.ws_url.unwrap_or_else(|| cluster.ws_url().to_string()),
+        },
+        cranks.keys().copied().collect(),
+        sender,
+    );
+
+    let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
+    let mut chain_data = ChainData::new();
+    let mut interval = tokio::time::interval(Duration::from_millis(cli.interval_ms.max(1)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            message = receiver.recv() => {
+                update_chain_data(&mut chain_data, message?);
+                continue;
+            }
+            Some((event_heap, heap_slot, result)) = result_receiver.recv() => {
+                if let Some(crank) = cranks.get_mut(&event_heap) {
+                    crank.on_result(heap_slot, result);
+                }
+                continue;
+            }
+            _ = interval.tick() => {}
+        }
+
+        for (event_heap_address, crank) in cranks.iter_mut() {
+            if crank.in_flight || Instant::now() < crank.retry_at {
+                continue;
+            }
+            let Ok(account) = chain_data.account_and_slot(event_heap_address) else {
+                continue;
+            };
+            if crank.waiting_for_heap(account.slot) {
+                continue;
+            }
+            let event_heap = match account.account.load::<EventHeap>() {
+                Ok(event_heap) => event_heap,
+                Err(err) => {
+                    warn!(
+                        "loading event heap {} failed: {:?}",
+                        event_heap_address, err
+                    );
+                    continue;
+                }
+            };
+            if event_heap.len() < cli.min_events.max(1) {
+                continue;
+            }
+            if event_heap.len() * 4 >= MAX_NUM_EVENTS as usize * 3 {
+                warn!(
+                    "event heap of {} holds {} of {} events",
+                    crank.market_address,
+                    event_heap.len(),
+                    MAX_NUM_EVENTS
+                );
+            }
+
+            let batch = plan_batch(event_heap, crank.sizer.max_events, cli.max_accounts);
+            if batch.is_empty() {
+                continue;
+            }
+            let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+                crank.sizer.compute_unit_limit(&batch),
+            )];
+            instructions.extend(batch.into_iter().map(|ix| {
+                consume_given_events_instruction(
+                    &crank.market,
+                    crank.market_address,
+                    ix.open_orders_accounts,
+                    ix.slots,
+                )
+            }));
+
+            crank.in_flight = true;
+            let client = openbook_client.clone();
+            let permissioned = crank.permissioned;
+            let event_heap_address = *event_heap_address;
+            let heap_slot = account.slot;
+            let result_sender = result_sender.clone();
+            tokio::spawn(async move {
+                let result = if permissioned {
+                    client.send_and_confirm_owner_tx(instructions).await
+                } else {
+                    client
+                        .send_and_confirm_permissionless_tx(instructions)
+                        .await
+                };
+                let _ = result_sender.send((event_heap_address, heap_slot, result));
+            });
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use anchor_lang::event::EVENT_IX_TAG;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::Result;
+use futures::Stream;
+use openbook_v2::logs::*;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInstruction, UiTransactionEncoding,
+};
+
+/// An event of the openbook program, from a `Program data:` log line written by
+/// `emit_stack` or from the self CPI of `emit_cpi!`
+pub enum OpenBookEvent {
+    Deposit(DepositLog),
+    Fill(FillLog),
+    TakerSignature(TakerSignatureLog),
+    MarketMetaData(MarketMetaDataLog),
+    TotalOrderFill(TotalOrderFillEvent),
+    SetDelegate(SetDelegateLog),
+    SettleFunds(SettleFundsLog),
+    SweepFees(SweepFeesLog),
+    OpenOrdersPosition(OpenOrdersPositionLog),
+    OrderPlaced(OrderPlacedLog),
+    OrderCancelled(OrderCancelledLog),
+    OrderExpired(OrderExpiredLog),
+}
+
+/// An event with the transaction it was emitted in
+pub struct DecodedEvent {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub event: OpenBookEvent,
+}
+
+macro_rules! decode_events {
+    ($data:expr, $($log:ident => $variant:ident),* $(,)?) => {{
+        let data: &[u8] = $data;
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut payload) = data.split_at(8);
+        $(
+            if discriminator == $log::DISCRIMINATOR {
+                return $log::deserialize(&mut payload).ok().map(OpenBookEvent::$variant);
+            }
+        )*
+        None
+    }};
+}
+
+/// Decodes an event serialized as its discriminator followed by the borsh data,
+/// None if the discriminator is unknown or the data invalid
+pub fn decode_event(data: &[u8]) -> Option<OpenBookEvent> {
+    decode_events!(
+        data,
+        DepositLog => Deposit,
+        FillLog => Fill,
+        TakerSignatureLog => TakerSignature,
+        MarketMetaDataLog => MarketMetaData,
+        TotalOrderFillEvent => TotalOrderFill,
+        SetDelegateLog => SetDelegate,
+        SettleFundsLog => SettleFunds,
+        SweepFeesLog => SweepFees,
+        OpenOrdersPositionLog => OpenOrdersPosition,
+        OrderPlacedLog => OrderPlaced,
+        OrderCancelledLog => OrderCancelled,
+        OrderExpiredLog => OrderExpired,
+    )
+}
+
+/// Decodes the `Program data:` lines logged while the openbook program is the one
+/// executing, data logged by other programs is ignored
+pub fn decode_log_events(logs: &[String]) -> Vec<OpenBookEvent> {
+    let program_id = openbook_v2::id().to_string();
+    let mut invoke_stack: Vec<&str> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if invoke_stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            // sol_log_data separates the slices with spaces, emit_stack logs only one
+            let Some(Ok(bytes)) = data.split(' ').next().map(base64::decode) else {
+                continue;
+            };
+            events.extend(decode_event(&bytes));
+            continue;
+        }
+
+        let mut words = rest.split(' ');
+        match (words.next(), words.next()) {
+            (Some(program), Some("invoke")) => invoke_stack.push(program),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                invoke_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+/// Decodes the logged events of a transaction, then the ones emitted through
+/// self CPIs to the openbook program found in its inner instructions
+///
+/// Expects the transaction to be fetched with a binary encoding, so the account
+/// keys of versioned transactions can be resolved from the loaded addresses.
+pub fn decode_transaction_events(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<OpenBookEvent>> {
+    let Some(meta) = tx.transaction.meta.as_ref() else {
+        return Ok(vec![]);
+    };
+
+    let mut events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => decode_log_events(logs),
+        _ => vec![],
+    };
+
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Ok(events);
+    };
+    let versioned_tx = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow::anyhow!("transaction is not binary encoded"))?;
+
+    let mut account_keys = versioned_tx.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for address in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(Pubkey::from_str(address)?);
+        }
+    }
+
+    for instruction in inner_instructions
+        .iter()
+        .flat_map(|inner| inner.instructions.iter())
+    {
+        let UiInstruction::Compiled(instruction) = instruction else {
+            continue;
+        };
+        if account_keys.get(instruction.program_id_index as usize) != Some(&openbook_v2::id()) {
+            continue;
+        }
+        let data = bs58::decode(&instruction.data).into_vec()?;
+        if let Some(event_data) = data.strip_prefix(&EVENT_IX_TAG.to_le_bytes()) {
+            events.extend(decode_event(event_data));
+        }
+    }
+
+    Ok(events)
+}
+
+struct EventStreamState<'a> {
+    client: &'a RpcClient,
+    address: Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+    signatures: VecDeque<RpcConfirmedTransactionStatusWithSignature>,
+    pending: VecDeque<DecodedEvent>,
+    done: bool,
+}
+
+impl<'a> EventStreamState<'a> {
+    /// Queues the next page of signatures, older than the ones seen so far
+    async fn fetch_signatures(&mut self) -> Result<()> {
+        let signatures = self
+            .client
+            .get_signatures_for_address_with_config(
+                &self.address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before: self.before,
+                    until: self.until,
+                    limit: Some(1000),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+        if let Some(last) = signatures.last() {
+            self.before = Some(Signature::from_str(&last.signature)?);
+        } else {
+            self.done = true;
+        }
+        self.signatures.extend(signatures);
+        Ok(())
+    }
+
+    /// Decodes the events of the transaction into `pending`
+    async fn fetch_events(&mut self, signature: Signature) -> Result<()> {
+        let tx = self
+            .client
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        for event in decode_transaction_events(&tx)? {
+            self.pending.push_back(DecodedEvent {
+                signature,
+                slot: tx.slot,
+                block_time: tx.block_time,
+                event,
+            });
+        }
+        Ok(())
+    }
+
+    async fn next_event(&mut self) -> Result<Option<DecodedEvent>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+            if let Some(signature_info) = self.signatures.pop_front() {
+                if signature_info.err.is_none() {
+                    let signature = Signature::from_str(&signature_info.signature)?;
+                    self.fetch_events(signature).await?;
+                }
+                continue;
+            }
+            if self.done {
+                return Ok(None);
+            }
+            self.fetch_signatures().await?;
+        }
+    }
+}
+
+/// Streams the events of the successful transactions mentioning `address`, newest
+/// transaction first, starting before `before` and stopping at `until`
+///
+/// The stream ends after the first error.
+pub fn event_stream(
+    client: &RpcClient,
+    address: Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+) -> impl Stream<Item = Result<DecodedEvent>> + '_ {
+    let state = EventStreamState {
+        client,
+        address,
+        before,
+        until,
+        signatures: VecDeque::new(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    futures::stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        match state.next_event().await {
+            Ok(Some(event)) => Some((Ok(event), Some(state))),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+
+    fn sweep_fees_data(amount: u64) -> String {
+        let log = SweepFeesLog {
+            market: Pubkey::new_unique(),
+            amount,
+            receiver: Pubkey::new_unique(),
+        };
+        format!("Program data: {}", base64::encode(log.data()))
+    }
+
+    fn swept_amounts(logs: &[String]) -> Vec<u64> {
+        decode_log_events(logs)
+            .iter()
+            .map(|event| match event {
+                OpenBookEvent::SweepFees(log) => log.amount,
+                _ => panic!("unexpected event"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decode_event_data() {
+        let log = DepositLog {
+            open_orders_account: Pubkey::new_unique(),
+            signer: Pubkey::new_unique(),
+            base_amount: 1,
+            quote_amount: 2,
+        };
+        let Some(OpenBookEvent::Deposit(decoded)) = decode_event(&log.data()) else {
+            panic!("expected a deposit");
+        };
+        assert_eq!(decoded.open_orders_account, log.open_orders_account);
+        assert_eq!((decoded.base_amount, decoded.quote_amount), (1, 2));
+
+        assert!(decode_event(&[0; 7]).is_none());
+        assert!(decode_event(&[0; 40]).is_none());
+        assert!(decode_event(&log.data()[..20]).is_none());
+    }
+
+    #[test]
+    fn only_openbook_data_is_decoded() {
+        let openbook = openbook_v2::id().to_string();
+        let token = Pubkey::new_unique().to_string();
+        let router = Pubkey::new_unique().to_string();
+        let logs = vec![
+            // A foreign program logs its own data, then calls openbook
+            format!("Program {} invoke [1]", router),
+            sweep_fees_data(1),
+            format!("Program {} invoke [2]", openbook),
+            "Program log: Instruction: SweepFees".to_string(),
+            // Data logged by a program openbook calls isn't openbook's
+            format!("Program {} invoke [3]", token),
+            sweep_fees_data(2),
+            format!("Program {} consumed 4645 of 180000 compute units", token),
+            format!("Program {} success", token),
+            sweep_fees_data(3),
+            format!(
+                "Program {} consumed 20000 of 200000 compute units",
+                openbook
+            ),
+            format!("Program {} success", openbook),
+            sweep_fees_data(4),
+            format!("Program {} success", router),
+            // A failed openbook instruction pops the stack as well
+            format!("Program {} invoke [1]", openbook),
+            sweep_fees_data(5),
+            "Program data: not base64".to_string(),
+            format!("Program {} failed: custom program error: 0x1770", openbook),
+            sweep_fees_data(6),
+        ];
+        assert_eq!(swept_amounts(&logs), vec![3, 5]);
+    }
+}
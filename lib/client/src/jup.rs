@@ -12,7 +12,7 @@ use openbook_v2::{
 
 use crate::{
     book::{amounts_from_book, Amounts},
-    remaining_accounts_to_crank,
+    market_stats_account, remaining_accounts_to_crank,
     util::ZeroCopyDeserialize,
 };
 use jupiter_amm_interface::{
@@ -117,14 +117,15 @@ impl Amm for OpenBookMarket {
                 Some(accounts_zerocopy::KeyedAccount { key, account })
             };
 
+        self.timestamp = clock.unix_timestamp.try_into().unwrap();
+
         self.oracle_price = self.market.oracle_price(
             oracle_acc(self.market.oracle_a).as_ref(),
             oracle_acc(self.market.oracle_b).as_ref(),
+            self.timestamp,
             clock.slot,
         )?;
 
-        self.timestamp = clock.unix_timestamp.try_into().unwrap();
-
         Ok(())
     }
 
@@ -144,8 +145,7 @@ impl Amm for OpenBookMarket {
 
         let input_amount = i64::try_from(quote_params.in_amount)?;
 
-        // quote params can have exact in (which is implemented here) and exact out which is not implemented
-        // check with jupiter to add to their API exact_out support
+        // quote params only carry an input amount, exact out swaps are quoted by `quote_exact_out`
         let (max_base_lots, max_quote_lots_including_fees) = match side {
             Side::Bid => (
                 self.market.max_base_lots(),
@@ -157,42 +157,7 @@ impl Amm for OpenBookMarket {
             ),
         };
 
-        let bids_ref = RefCell::new(self.bids);
-        let asks_ref = RefCell::new(self.asks);
-        let book = Orderbook {
-            bids: bids_ref.borrow_mut(),
-            asks: asks_ref.borrow_mut(),
-        };
-
-        let order_amounts: Amounts = amounts_from_book(
-            book,
-            side,
-            max_base_lots,
-            max_quote_lots_including_fees,
-            &self.market,
-            self.oracle_price,
-            0,
-        )?;
-
-        let (in_amount, out_amount) = match side {
-            Side::Bid => (
-                order_amounts.total_quote_taken_native - order_amounts.fee,
-                order_amounts.total_base_taken_native,
-            ),
-            Side::Ask => (
-                order_amounts.total_base_taken_native,
-                order_amounts.total_quote_taken_native + order_amounts.fee,
-            ),
-        };
-
-        Ok(Quote {
-            in_amount,
-            out_amount,
-            fee_mint: self.market.quote_mint,
-            fee_amount: order_amounts.fee,
-            not_enough_liquidity: order_amounts.not_enough_liquidity,
-            ..Quote::default()
-        })
+        self.quote_from_book(side, max_base_lots, max_quote_lots_including_fees, false)
     }
 
     fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
@@ -231,8 +196,8 @@ impl Amm for OpenBookMarket {
                 market_authority: self.market.market_authority,
                 bids: self.market.bids,
                 asks: self.market.asks,
-                user_base_account,
-                user_quote_account,
+                user_base_account: Some(user_base_account),
+                user_quote_account: Some(user_quote_account),
                 market_base_vault: self.market.market_base_vault,
                 market_quote_vault: self.market.market_quote_vault,
                 event_heap: self.market.event_heap,
@@ -241,6 +206,9 @@ impl Amm for OpenBookMarket {
                 token_program: Token::id(),
                 system_program: System::id(),
                 open_orders_admin: None,
+                native_mint: None,
+                unwrap_account: None,
+                market_stats: market_stats_account(&self.key, &self.market),
             };
 
             let mut account_metas = accounts.to_account_metas(None);
@@ -278,6 +246,105 @@ impl Amm for OpenBookMarket {
     }
 }
 
+impl OpenBookMarket {
+    /// Quote a swap that receives `out_amount` of `output_mint` net of taker fees,
+    /// rounded up to whole lots, as executed by a `PlaceOrderType::ExactOut` take order.
+    ///
+    /// Swaps into the quote token may receive up to a base lot's worth more, as they
+    /// fill whole base lots.
+    pub fn quote_exact_out(&self, output_mint: Pubkey, out_amount: u64) -> Result<Quote> {
+        if self.is_permissioned {
+            return Ok(Quote {
+                not_enough_liquidity: true,
+                ..Quote::default()
+            });
+        }
+
+        let side = if output_mint == self.market.base_mint {
+            Side::Bid
+        } else {
+            Side::Ask
+        };
+
+        let output_amount = i64::try_from(out_amount)?;
+
+        let (max_base_lots, max_quote_lots_including_fees) = match side {
+            Side::Bid => (
+                (output_amount + self.market.base_lot_size - 1) / self.market.base_lot_size,
+                self.market.max_quote_lots(),
+            ),
+            Side::Ask => (
+                self.market.max_base_lots(),
+                (output_amount + self.market.quote_lot_size - 1) / self.market.quote_lot_size,
+            ),
+        };
+
+        self.quote_from_book(side, max_base_lots, max_quote_lots_including_fees, true)
+    }
+
+    fn quote_from_book(
+        &self,
+        side: Side,
+        max_base_lots: i64,
+        max_quote_lots_including_fees: i64,
+        exact_out: bool,
+    ) -> Result<Quote> {
+        let bids_ref = RefCell::new(self.bids);
+        let asks_ref = RefCell::new(self.asks);
+        let book = Orderbook {
+            bids: bids_ref.borrow_mut(),
+            asks: asks_ref.borrow_mut(),
+        };
+
+        let order_amounts: Amounts = amounts_from_book(
+            book,
+            side,
+            max_base_lots,
+            max_quote_lots_including_fees,
+            exact_out,
+            &self.market,
+            self.oracle_price,
+            0,
+        )?;
+
+        // Exact out quotes promise the output net of the taker fees taken out of it
+        let fees_in_base = self.market.has_fees_in_base();
+        let (in_amount, out_amount) = match side {
+            Side::Bid if exact_out && fees_in_base => (
+                order_amounts.total_quote_taken_native - order_amounts.fee,
+                order_amounts.total_base_taken_native
+                    - self
+                        .market
+                        .taker_fees_ceil(order_amounts.total_base_taken_native),
+            ),
+            Side::Bid => (
+                order_amounts.total_quote_taken_native - order_amounts.fee,
+                order_amounts.total_base_taken_native,
+            ),
+            Side::Ask if exact_out && !fees_in_base => (
+                order_amounts.total_base_taken_native,
+                order_amounts.total_quote_taken_native
+                    - self
+                        .market
+                        .taker_fees_ceil(order_amounts.total_quote_taken_native),
+            ),
+            Side::Ask => (
+                order_amounts.total_base_taken_native,
+                order_amounts.total_quote_taken_native + order_amounts.fee,
+            ),
+        };
+
+        Ok(Quote {
+            in_amount,
+            out_amount,
+            fee_mint: self.market.quote_mint,
+            fee_amount: order_amounts.fee,
+            not_enough_liquidity: order_amounts.not_enough_liquidity,
+            ..Quote::default()
+        })
+    }
+}
+
 #[cfg(all(test, feature = "enable-gpl"))]
 mod test {
     use super::*;
@@ -451,8 +518,8 @@ mod test {
                             signer: user.pubkey(),
                             penalty_payer: user.pubkey(),
                             market,
-                            user_base_account,
-                            user_quote_account,
+                            user_base_account: Some(user_base_account),
+                            user_quote_account: Some(user_quote_account),
                             market_authority: market_data.market_authority,
                             bids: market_data.bids,
                             asks: market_data.asks,
@@ -464,6 +531,9 @@ mod test {
                             token_program: Token::id(),
                             system_program: System::id(),
                             open_orders_admin: None,
+                            native_mint: None,
+                            unwrap_account: None,
+                            market_stats: crate::market_stats_account(&market, &market_data),
                         },
                         None,
                     ),
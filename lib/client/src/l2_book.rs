@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use openbook_v2::accounts_zerocopy::{KeyedAccountSharedData, LoadZeroCopy};
+use openbook_v2::state::{BookSide, Market, Side};
+use serde::{Serialize, Serializer};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::chain_data::{AccountAndSlot, ChainData};
+use crate::market_fetch::fetch_market;
+use crate::report::{as_string, ui_base_lots, ui_price};
+
+pub fn serialize_side<S: Serializer>(side: &Side, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match side {
+        Side::Bid => "bid",
+        Side::Ask => "ask",
+    })
+}
+
+/// The valid orders of one side at one price, fixed and oracle pegged ones alike
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct L2Level {
+    #[serde(serialize_with = "serialize_side")]
+    pub side: Side,
+    pub price_lots: i64,
+    pub base_lots: i64,
+    pub orders: u32,
+    /// Quote native units per base native unit
+    pub native_price: f64,
+    pub native_quantity: i64,
+    /// Quote UI units per base UI unit
+    pub price: f64,
+    pub quantity: f64,
+}
+
+impl L2Level {
+    fn new(market: &Market, side: Side, price_lots: i64, base_lots: i64, orders: u32) -> Self {
+        Self {
+            side,
+            price_lots,
+            base_lots,
+            orders,
+            native_price: market.lot_to_native_price(price_lots).to_num::<f64>(),
+            native_quantity: base_lots * market.base_lot_size,
+            price: ui_price(market, price_lots),
+            quantity: ui_base_lots(market, base_lots),
+        }
+    }
+}
+
+/// Aggregates the valid orders of a book side into levels, best price first
+///
+/// Oracle pegged orders are priced at `oracle_price_lots` and left out without it.
+pub fn aggregate_levels(
+    market: &Market,
+    book_side: &BookSide,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+) -> Vec<L2Level> {
+    // iter_valid merges both order trees by price, so the orders of a level are adjacent
+    let mut levels: Vec<(i64, i64, u32)> = vec![];
+    for item in book_side.iter_valid(now_ts, oracle_price_lots) {
+        match levels.last_mut() {
+            Some((price_lots, base_lots, orders)) if *price_lots == item.price_lots => {
+                *base_lots += item.node.quantity;
+                *orders += 1;
+            }
+            _ => levels.push((item.price_lots, item.node.quantity, 1)),
+        }
+    }
+
+    let side = book_side.side();
+    levels
+        .into_iter()
+        .map(|(price_lots, base_lots, orders)| {
+            L2Level::new(market, side, price_lots, base_lots, orders)
+        })
+        .collect()
+}
+
+/// The market, book sides and oracle price a book is built from, as of `slot`
+pub struct BookState<'a> {
+    pub market: Market,
+    pub bids: &'a BookSide,
+    pub asks: &'a BookSide,
+    pub oracle_price_lots: Option<i64>,
+    pub slot: u64,
+}
+
+impl<'a> BookState<'a> {
+    /// Loads the latest live writes of the market, its book sides and oracles,
+    /// `slot` is the newest of them
+    pub fn from_chain_data(
+        chain_data: &'a ChainData,
+        market_address: Pubkey,
+        now_ts: u64,
+    ) -> Result<Self> {
+        let market_account = chain_data.account_and_slot(&market_address)?;
+        let mut market = *market_account
+            .account
+            .load::<Market>()
+            .with_context(|| format!("loading market {}", market_address))?;
+        let bids = chain_data.account_and_slot(&market.bids)?;
+        let asks = chain_data.account_and_slot(&market.asks)?;
+        let mut slot = market_account.slot.max(bids.slot).max(asks.slot);
+
+        let mut oracle = |address: Option<Pubkey>| -> Result<Option<KeyedAccountSharedData>> {
+            let Some(address) = address else {
+                return Ok(None);
+            };
+            let oracle = chain_data.account_and_slot(&address)?;
+            slot = slot.max(oracle.slot);
+            Ok(Some(KeyedAccountSharedData::new(
+                address,
+                oracle.account.clone(),
+            )))
+        };
+        let oracle_a = oracle(market.oracle_a.into())?;
+        let oracle_b = oracle(market.oracle_b.into())?;
+        let oracle_price_lots =
+            market.oracle_price_lots(oracle_a.as_ref(), oracle_b.as_ref(), now_ts, slot)?;
+
+        Ok(Self {
+            market,
+            bids: bids
+                .account
+                .load::<BookSide>()
+                .with_context(|| format!("loading bids {}", market.bids))?,
+            asks: asks
+                .account
+                .load::<BookSide>()
+                .with_context(|| format!("loading asks {}", market.asks))?,
+            oracle_price_lots,
+            slot,
+        })
+    }
+
+    /// Accounts the book of `market` is built from, to subscribe to or fetch
+    pub fn accounts(market_address: Pubkey, market: &Market) -> Vec<Pubkey> {
+        let mut accounts = vec![market_address, market.bids, market.asks];
+        accounts.extend(Option::<Pubkey>::from(market.oracle_a));
+        accounts.extend(Option::<Pubkey>::from(market.oracle_b));
+        accounts
+    }
+}
+
+/// Price levels of both sides of a market as of `slot`, best price first
+#[derive(Clone, Debug)]
+pub struct L2Book {
+    pub market_address: Pubkey,
+    pub slot: u64,
+    pub now_ts: u64,
+    pub oracle_price_lots: Option<i64>,
+    pub bids: Vec<L2Level>,
+    pub asks: Vec<L2Level>,
+}
+
+impl L2Book {
+    pub fn new(market_address: Pubkey, state: &BookState, now_ts: u64) -> Self {
+        let levels = |book_side: &BookSide| {
+            aggregate_levels(&state.market, book_side, now_ts, state.oracle_price_lots)
+        };
+        Self {
+            market_address,
+            slot: state.slot,
+            now_ts,
+            oracle_price_lots: state.oracle_price_lots,
+            bids: levels(state.bids),
+            asks: levels(state.asks),
+        }
+    }
+
+    pub fn from_chain_data(
+        chain_data: &ChainData,
+        market_address: Pubkey,
+        now_ts: u64,
+    ) -> Result<Self> {
+        let state = BookState::from_chain_data(chain_data, market_address, now_ts)?;
+        Ok(Self::new(market_address, &state, now_ts))
+    }
+
+    pub fn levels(&self, side: Side) -> &[L2Level] {
+        match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<&L2Level> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&L2Level> {
+        self.asks.first()
+    }
+
+    /// Keeps the `depth` best levels of each side
+    pub fn truncate(&mut self, depth: usize) {
+        self.bids.truncate(depth);
+        self.asks.truncate(depth);
+    }
+
+    /// Changes turning `self` into `newer`: the bids then the asks, each side best
+    /// price first
+    pub fn diff(&self, newer: &L2Book) -> Vec<L2LevelChange> {
+        let mut changes = diff_levels(newer, Side::Bid, &self.bids, &newer.bids);
+        changes.extend(diff_levels(newer, Side::Ask, &self.asks, &newer.asks));
+        changes
+    }
+
+    /// Changes turning the `depth` best levels of `self` into those of `newer`
+    ///
+    /// A level pushed out of the `depth` best ones while still on the book isn't
+    /// reported removed, and one moving into them is reported added. Keeping the
+    /// `depth` best levels after applying the changes gives those of `newer`.
+    pub fn diff_depth(&self, newer: &L2Book, depth: usize) -> Vec<L2LevelChange> {
+        let side_changes = |side: Side| {
+            let (old, new) = (self.levels(side), newer.levels(side));
+            let mut changes = diff_levels(
+                newer,
+                side,
+                &old[..old.len().min(depth)],
+                &new[..new.len().min(depth)],
+            );
+            changes.retain(|change| {
+                change.kind != L2ChangeKind::Removed
+                    || !new
+                        .iter()
+                        .any(|level| level.price_lots == change.price_lots)
+            });
+            changes
+        };
+        let mut changes = side_changes(Side::Bid);
+        changes.extend(side_changes(Side::Ask));
+        changes
+    }
+}
+
+/// Fetches the accounts the book of `market_address` is built from into `chain_data`
+pub async fn fetch_book_accounts(
+    client: &RpcClient,
+    chain_data: &mut ChainData,
+    market_address: Pubkey,
+) -> Result<()> {
+    let market = fetch_market(client, market_address).await?;
+    let addresses = BookState::accounts(market_address, &market.market_data);
+    let response = client
+        .get_multiple_accounts_with_commitment(&addresses, client.commitment())
+        .await?;
+    for (address, account) in addresses.iter().zip(response.value) {
+        let account = account.ok_or_else(|| anyhow::anyhow!("account {} not found", address))?;
+        chain_data.update_from_rpc(
+            address,
+            AccountAndSlot {
+                slot: response.context.slot,
+                account: account.into(),
+            },
+        );
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum L2ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// A level that differs between two books, with its new size, zero once removed
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct L2LevelChange {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    pub slot: u64,
+    #[serde(serialize_with = "serialize_side")]
+    pub side: Side,
+    pub kind: L2ChangeKind,
+    pub price_lots: i64,
+    pub base_lots: i64,
+    pub orders: u32,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+fn diff_levels(newer: &L2Book, side: Side, old: &[L2Level], new: &[L2Level]) -> Vec<L2LevelChange> {
+    let mut levels: BTreeMap<i64, (Option<&L2Level>, Option<&L2Level>)> = BTreeMap::new();
+    for level in old {
+        levels.entry(level.price_lots).or_default().0 = Some(level);
+    }
+    for level in new {
+        levels.entry(level.price_lots).or_default().1 = Some(level);
+    }
+
+    let change = |kind: L2ChangeKind, level: &L2Level, removed: bool| L2LevelChange {
+        market: newer.market_address,
+        slot: newer.slot,
+        side,
+        kind,
+        price_lots: level.price_lots,
+        base_lots: if removed { 0 } else { level.base_lots },
+        orders: if removed { 0 } else { level.orders },
+        price: level.price,
+        quantity: if removed { 0.0 } else { level.quantity },
+    };
+    let changes = levels.into_values().filter_map(|levels| match levels {
+        (None, Some(new)) => Some(change(L2ChangeKind::Added, new, false)),
+        (Some(old), Some(new)) if (old.base_lots, old.orders) != (new.base_lots, new.orders) => {
+            Some(change(L2ChangeKind::Changed, new, false))
+        }
+        (Some(old), None) => Some(change(L2ChangeKind::Removed, old, true)),
+        _ => None,
+    });
+
+    // Levels are keyed by price ascending, bids are listed best price first
+    match side {
+        Side::Bid => changes.rev().collect(),
+        Side::Ask => changes.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_market, TestBook};
+    use openbook_v2::state::BookSideOrderTree;
+
+    fn l2_book(book: &TestBook, oracle_price_lots: Option<i64>) -> L2Book {
+        let state = BookState {
+            market: test_market(),
+            bids: &book.bids.borrow(),
+            asks: &book.asks.borrow(),
+            oracle_price_lots,
+            slot: 0,
+        };
+        L2Book::new(Pubkey::default(), &state, 0)
+    }
+
+    fn kinds(changes: &[L2LevelChange]) -> Vec<(Side, L2ChangeKind, i64, i64)> {
+        changes
+            .iter()
+            .map(|c| (c.side, c.kind, c.price_lots, c.base_lots))
+            .collect()
+    }
+
+    #[test]
+    fn fixed_and_pegged_orders_share_levels() {
+        let owner = Pubkey::new_unique();
+        let mut book = TestBook::default();
+        book.add_fixed(Side::Bid, 98, 1, owner);
+        book.add_fixed(Side::Bid, 100, 2, owner);
+        book.add_pegged(Side::Bid, -1, -1, 3, owner);
+        book.add_fixed(Side::Bid, 99, 4, owner);
+        book.add_fixed(Side::Ask, 102, 5, owner);
+        book.add_pegged(Side::Ask, 1, -1, 6, owner);
+
+        // Bids are listed from the highest price, the pegged bid joins the fixed one at 100
+        let l2 = l2_book(&book, Some(101));
+        let levels = |levels: &[L2Level]| {
+            levels
+                .iter()
+                .map(|l| (l.price_lots, l.base_lots, l.orders))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(levels(&l2.bids), vec![(100, 5, 2), (99, 4, 1), (98, 1, 1)]);
+        assert_eq!(levels(&l2.asks), vec![(102, 11, 2)]);
+        assert_eq!(l2.best_bid().unwrap().price_lots, 100);
+        assert_eq!(l2.best_ask().unwrap().price_lots, 102);
+
+        // Without an oracle price the pegged orders are left out
+        let l2 = l2_book(&book, None);
+        assert_eq!(levels(&l2.bids), vec![(100, 2, 1), (99, 4, 1), (98, 1, 1)]);
+        assert_eq!(levels(&l2.asks), vec![(102, 5, 1)]);
+    }
+
+    #[test]
+    fn diff_added_changed_removed() {
+        let owner = Pubkey::new_unique();
+        let mut book = TestBook::default();
+        let at_98 = book.add_fixed(Side::Bid, 98, 1, owner);
+        book.add_fixed(Side::Bid, 99, 2, owner);
+        book.add_fixed(Side::Ask, 102, 3, owner);
+        let older = l2_book(&book, None);
+        assert!(older.diff(&older).is_empty());
+
+        book.remove(Side::Bid, BookSideOrderTree::Fixed, at_98);
+        book.add_fixed(Side::Bid, 99, 4, owner);
+        book.add_fixed(Side::Bid, 100, 5, owner);
+        book.add_fixed(Side::Ask, 103, 6, owner);
+        let newer = l2_book(&book, None);
+
+        // The bids from the best price, then the asks
+        assert_eq!(
+            kinds(&older.diff(&newer)),
+            vec![
+                (Side::Bid, L2ChangeKind::Added, 100, 5),
+                (Side::Bid, L2ChangeKind::Changed, 99, 6),
+                (Side::Bid, L2ChangeKind::Removed, 98, 0),
+                (Side::Ask, L2ChangeKind::Added, 103, 6),
+            ]
+        );
+        let changed = &older.diff(&newer)[1];
+        assert_eq!((changed.orders, changed.quantity), (2, 6.0));
+    }
+
+    #[test]
+    fn diff_depth_keeps_levels_pushed_out() {
+        let owner = Pubkey::new_unique();
+        let mut book = TestBook::default();
+        let at_100 = book.add_fixed(Side::Ask, 100, 1, owner);
+        book.add_fixed(Side::Ask, 101, 2, owner);
+        book.add_fixed(Side::Ask, 102, 3, owner);
+        let first = l2_book(&book, None);
+
+        // 101 leaves the two best levels but is still on the book
+        let at_99 = book.add_fixed(Side::Ask, 99, 4, owner);
+        let second = l2_book(&book, None);
+        assert_eq!(
+            kinds(&first.diff_depth(&second, 2)),
+            vec![(Side::Ask, L2ChangeKind::Added, 99, 4)]
+        );
+
+        // Deeper levels move into the two best ones
+        book.remove(Side::Ask, BookSideOrderTree::Fixed, at_99);
+        book.remove(Side::Ask, BookSideOrderTree::Fixed, at_100);
+        let third = l2_book(&book, None);
+        assert_eq!(
+            kinds(&second.diff_depth(&third, 2)),
+            vec![
+                (Side::Ask, L2ChangeKind::Removed, 99, 0),
+                (Side::Ask, L2ChangeKind::Removed, 100, 0),
+                (Side::Ask, L2ChangeKind::Added, 101, 2),
+                (Side::Ask, L2ChangeKind::Added, 102, 3),
+            ]
+        );
+
+        // Without a depth limit the same changes are reported for the whole book
+        assert_eq!(
+            kinds(&second.diff(&third)),
+            vec![
+                (Side::Ask, L2ChangeKind::Removed, 99, 0),
+                (Side::Ask, L2ChangeKind::Removed, 100, 0),
+            ]
+        );
+    }
+}
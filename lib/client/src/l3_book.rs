@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use openbook_v2::state::{
+    is_oracle_pegged_bps_price_data, oracle_pegged_price, oracle_pegged_price_offset,
+    oracle_pegged_price_offset_bps, BookSide, BookSideOrderTree, LeafNode, Market, OrderState,
+    Side,
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::chain_data::ChainData;
+use crate::l2_book::{serialize_side, BookState};
+use crate::report::{as_string, ui_base_lots, ui_price};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum L3OrderState {
+    /// Can be matched at `price_lots`
+    Valid,
+    /// Past its expiry, removed by the next instruction that reaches it
+    Expired,
+    /// Oracle pegged order priced beyond its peg limit
+    PegLimitExceeded,
+    /// Oracle pegged order whose price leaves the valid range at the oracle price
+    Skipped,
+    /// Oracle pegged order that can't be priced without an oracle price
+    NoOraclePrice,
+}
+
+/// An order of the book, the order id is the key of its leaf node
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct L3Order {
+    #[serde(serialize_with = "as_string")]
+    pub order_id: u128,
+    #[serde(serialize_with = "serialize_side")]
+    pub side: Side,
+    #[serde(serialize_with = "as_string")]
+    pub owner: Pubkey,
+    pub owner_slot: u8,
+    pub client_order_id: u64,
+    pub timestamp: u64,
+    /// None if the order doesn't expire
+    pub expiry: Option<u64>,
+    pub oracle_pegged: bool,
+    pub peg_offset_lots: Option<i64>,
+    pub peg_offset_bps: Option<i32>,
+    pub peg_limit: Option<i64>,
+    pub state: L3OrderState,
+    /// Effective price, None for oracle pegged orders that can't be priced
+    pub price_lots: Option<i64>,
+    pub base_lots: i64,
+    pub price: Option<f64>,
+    pub quantity: f64,
+}
+
+impl L3Order {
+    fn new(
+        market: &Market,
+        side: Side,
+        order_tree: BookSideOrderTree,
+        node: &LeafNode,
+        state: L3OrderState,
+        price_lots: Option<i64>,
+    ) -> Self {
+        let oracle_pegged = order_tree == BookSideOrderTree::OraclePegged;
+        let price_data = node.price_data();
+        let is_bps = oracle_pegged && is_oracle_pegged_bps_price_data(price_data);
+        Self {
+            order_id: node.key,
+            side,
+            owner: node.owner,
+            owner_slot: node.owner_slot,
+            client_order_id: node.client_order_id,
+            timestamp: node.timestamp,
+            expiry: (node.time_in_force != 0).then(|| node.expiry()),
+            oracle_pegged,
+            peg_offset_lots: (oracle_pegged && !is_bps)
+                .then(|| oracle_pegged_price_offset(price_data)),
+            peg_offset_bps: is_bps.then(|| oracle_pegged_price_offset_bps(price_data)),
+            peg_limit: (oracle_pegged && node.peg_limit != -1).then_some(node.peg_limit),
+            state,
+            price_lots,
+            base_lots: node.quantity,
+            price: price_lots.map(|price_lots| ui_price(market, price_lots)),
+            quantity: ui_base_lots(market, node.quantity),
+        }
+    }
+}
+
+/// Lists all the orders of a book side, best price first
+///
+/// Unlike matching, invalid orders are kept and flagged. Oracle pegged orders that
+/// can't be priced at the oracle price, or at all without one, follow unpriced.
+pub fn list_orders(
+    market: &Market,
+    book_side: &BookSide,
+    now_ts: u64,
+    oracle_price_lots: Option<i64>,
+) -> Vec<L3Order> {
+    let side = book_side.side();
+    let mut orders: Vec<L3Order> = book_side
+        .iter_all_including_invalid(now_ts, oracle_price_lots)
+        .map(|item| {
+            let state = match item.state {
+                _ if item.node.is_expired(now_ts) => L3OrderState::Expired,
+                OrderState::Valid => L3OrderState::Valid,
+                OrderState::Invalid => L3OrderState::PegLimitExceeded,
+                OrderState::Skipped => L3OrderState::Skipped,
+            };
+            L3Order::new(
+                market,
+                side,
+                item.handle.order_tree,
+                item.node,
+                state,
+                Some(item.price_lots),
+            )
+        })
+        .collect();
+
+    // The iterator leaves out the oracle pegged orders it can't price
+    let root = book_side.root(BookSideOrderTree::OraclePegged);
+    orders.extend(book_side.nodes.iter(root).filter_map(|(_, node)| {
+        let state = match oracle_price_lots {
+            None => L3OrderState::NoOraclePrice,
+            Some(oracle_price_lots) => match oracle_pegged_price(oracle_price_lots, node, side).0 {
+                OrderState::Skipped => L3OrderState::Skipped,
+                OrderState::Valid | OrderState::Invalid => return None,
+            },
+        };
+        let state = if node.is_expired(now_ts) {
+            L3OrderState::Expired
+        } else {
+            state
+        };
+        Some(L3Order::new(
+            market,
+            side,
+            BookSideOrderTree::OraclePegged,
+            node,
+            state,
+            None,
+        ))
+    }));
+
+    orders
+}
+
+/// All the orders of both sides of a market as of `slot`, best price first
+#[derive(Clone, Debug)]
+pub struct L3Book {
+    pub market_address: Pubkey,
+    pub slot: u64,
+    pub now_ts: u64,
+    pub oracle_price_lots: Option<i64>,
+    pub bids: Vec<L3Order>,
+    pub asks: Vec<L3Order>,
+}
+
+impl L3Book {
+    pub fn new(market_address: Pubkey, state: &BookState, now_ts: u64) -> Self {
+        let orders = |book_side: &BookSide| {
+            list_orders(&state.market, book_side, now_ts, state.oracle_price_lots)
+        };
+        Self {
+            market_address,
+            slot: state.slot,
+            now_ts,
+            oracle_price_lots: state.oracle_price_lots,
+            bids: orders(state.bids),
+            asks: orders(state.asks),
+        }
+    }
+
+    pub fn from_chain_data(
+        chain_data: &ChainData,
+        market_address: Pubkey,
+        now_ts: u64,
+    ) -> Result<Self> {
+        let state = BookState::from_chain_data(chain_data, market_address, now_ts)?;
+        Ok(Self::new(market_address, &state, now_ts))
+    }
+
+    pub fn orders(&self, side: Side) -> &[L3Order] {
+        match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        }
+    }
+
+    pub fn order(&self, side: Side, order_id: u128) -> Option<&L3Order> {
+        self.orders(side).iter().find(|o| o.order_id == order_id)
+    }
+
+    /// Orders of an open orders account, bids first
+    pub fn orders_of<'a>(&'a self, owner: &'a Pubkey) -> impl Iterator<Item = &'a L3Order> {
+        self.bids
+            .iter()
+            .chain(self.asks.iter())
+            .filter(move |o| o.owner == *owner)
+    }
+
+    /// Changes turning `self` into `newer`: the bids then the asks, each side by
+    /// order id from the best to the worst key
+    pub fn diff(&self, newer: &L3Book) -> Vec<L3OrderChange> {
+        let mut changes = diff_orders(newer, Side::Bid, &self.bids, &newer.bids);
+        changes.extend(diff_orders(newer, Side::Ask, &self.asks, &newer.asks));
+        changes
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum L3ChangeKind {
+    /// Placed since the older book
+    New,
+    /// Partially filled
+    Reduced,
+    /// Expired on the book, or removed after its expiry without being seen expired
+    Expired,
+    /// Filled, cancelled or pruned
+    Removed,
+}
+
+/// An order that changed between two books, with its remaining size, zero once
+/// it left the book
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct L3OrderChange {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    pub slot: u64,
+    #[serde(serialize_with = "serialize_side")]
+    pub side: Side,
+    pub kind: L3ChangeKind,
+    #[serde(serialize_with = "as_string")]
+    pub order_id: u128,
+    #[serde(serialize_with = "as_string")]
+    pub owner: Pubkey,
+    pub client_order_id: u64,
+    pub price_lots: Option<i64>,
+    pub previous_base_lots: i64,
+    pub base_lots: i64,
+}
+
+fn diff_orders(newer: &L3Book, side: Side, old: &[L3Order], new: &[L3Order]) -> Vec<L3OrderChange> {
+    let mut orders: BTreeMap<u128, (Option<&L3Order>, Option<&L3Order>)> = BTreeMap::new();
+    for order in old {
+        orders.entry(order.order_id).or_default().0 = Some(order);
+    }
+    for order in new {
+        orders.entry(order.order_id).or_default().1 = Some(order);
+    }
+
+    let change = |kind: L3ChangeKind, order: &L3Order, previous_base_lots: i64, base_lots: i64| {
+        L3OrderChange {
+            market: newer.market_address,
+            slot: newer.slot,
+            side,
+            kind,
+            order_id: order.order_id,
+            owner: order.owner,
+            client_order_id: order.client_order_id,
+            price_lots: order.price_lots,
+            previous_base_lots,
+            base_lots,
+        }
+    };
+    let changes = orders.into_values().filter_map(|orders| match orders {
+        (None, Some(new)) => Some(change(L3ChangeKind::New, new, 0, new.base_lots)),
+        (Some(old), Some(new)) => {
+            let kind = if new.state == L3OrderState::Expired && old.state != L3OrderState::Expired {
+                L3ChangeKind::Expired
+            } else if new.base_lots < old.base_lots {
+                L3ChangeKind::Reduced
+            } else {
+                return None;
+            };
+            Some(change(kind, new, old.base_lots, new.base_lots))
+        }
+        (Some(old), None) => {
+            let expired = old.state != L3OrderState::Expired
+                && matches!(old.expiry, Some(expiry) if expiry <= newer.now_ts);
+            let kind = if expired {
+                L3ChangeKind::Expired
+            } else {
+                L3ChangeKind::Removed
+            };
+            Some(change(kind, old, old.base_lots, 0))
+        }
+        (None, None) => None,
+    });
+
+    // Keys ascend with the price or peg offset, bids are listed from the best one
+    match side {
+        Side::Bid => changes.rev().collect(),
+        Side::Ask => changes.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_market, TestBook};
+
+    fn l3_book(book: &TestBook, oracle_price_lots: Option<i64>, slot: u64) -> L3Book {
+        let state = BookState {
+            market: test_market(),
+            bids: &book.bids.borrow(),
+            asks: &book.asks.borrow(),
+            oracle_price_lots,
+            slot,
+        };
+        L3Book::new(Pubkey::default(), &state, 0)
+    }
+
+    #[test]
+    fn skipped_pegged_orders_stay_listed() {
+        let owner = Pubkey::new_unique();
+        let mut book = TestBook::default();
+        let pegged = book.add_pegged(Side::Bid, -100, -1, 5, owner);
+        let fixed = book.add_fixed(Side::Bid, 800, 3, owner);
+
+        let priced = l3_book(&book, Some(1000), 1);
+        let states: Vec<_> = priced
+            .bids
+            .iter()
+            .map(|o| (o.order_id, o.state, o.price_lots))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (pegged, L3OrderState::Valid, Some(900)),
+                (fixed, L3OrderState::Valid, Some(800)),
+            ]
+        );
+
+        // Priced below one lot, the pegged order can't match but is still on the book
+        let skipped = l3_book(&book, Some(50), 2);
+        let states: Vec<_> = skipped
+            .bids
+            .iter()
+            .map(|o| (o.order_id, o.state, o.price_lots))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (fixed, L3OrderState::Valid, Some(800)),
+                (pegged, L3OrderState::Skipped, None),
+            ]
+        );
+        assert!(priced.diff(&skipped).is_empty());
+        assert!(skipped.diff(&priced).is_empty());
+
+        let unpriced = l3_book(&book, None, 3);
+        assert_eq!(unpriced.bids[1].state, L3OrderState::NoOraclePrice);
+        assert!(priced.diff(&unpriced).is_empty());
+    }
+
+    #[test]
+    fn diff_orders_by_id() {
+        let owner = Pubkey::new_unique();
+        let mut book = TestBook::default();
+        let pegged = book.add_pegged(Side::Bid, -100, -1, 5, owner);
+        let ask = book.add_fixed(Side::Ask, 1100, 2, owner);
+        let older = l3_book(&book, Some(1000), 1);
+
+        book.remove(Side::Ask, BookSideOrderTree::Fixed, ask);
+        let bid = book.add_fixed(Side::Bid, 950, 4, owner);
+        let newer = l3_book(&book, Some(50), 2);
+
+        let changes: Vec<_> = older
+            .diff(&newer)
+            .iter()
+            .map(|c| {
+                (
+                    c.side,
+                    c.kind,
+                    c.order_id,
+                    c.previous_base_lots,
+                    c.base_lots,
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (Side::Bid, L3ChangeKind::New, bid, 0, 4),
+                (Side::Ask, L3ChangeKind::Removed, ask, 2, 0),
+            ]
+        );
+
+        let mut reduced = newer.clone();
+        reduced.bids.iter_mut().for_each(|o| {
+            if o.order_id == pegged {
+                o.base_lots = 2;
+            }
+        });
+        let changes: Vec<_> = newer
+            .diff(&reduced)
+            .iter()
+            .map(|c| (c.kind, c.order_id, c.previous_base_lots, c.base_lots))
+            .collect();
+        assert_eq!(changes, vec![(L3ChangeKind::Reduced, pegged, 5, 2)]);
+    }
+}
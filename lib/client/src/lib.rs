@@ -3,6 +3,7 @@ pub use book::*;
 pub use client::*;
 pub use context::*;
 pub use jup::*;
+pub use route::*;
 pub use util::*;
 
 mod account_fetcher;
@@ -14,7 +15,10 @@ mod client;
 mod context;
 mod gpa;
 mod jup;
+mod route;
 pub mod snapshot_source;
+#[cfg(test)]
+mod test_util;
 mod util;
 pub mod client_init;
 pub mod market_fetch;
@@ -22,4 +26,11 @@ pub mod vault_balances;
 pub mod get_vault_mint;
 pub mod event_heap_reader;
 pub mod trade_fetcher;
-pub mod get_event_log;
\ No newline at end of file
+pub mod get_event_log;
+pub mod report;
+pub mod event_decoder;
+pub mod trade_backfill;
+pub mod candles;
+pub mod l2_book;
+pub mod l3_book;
+pub mod websocket_source;
\ No newline at end of file
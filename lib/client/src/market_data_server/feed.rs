@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use bytemuck::cast_ref;
+use log::*;
+use openbook_v2::accounts_zerocopy::LoadZeroCopy;
+use openbook_v2::state::{AnyEvent, EventHeap, EventType, FillEvent, Market};
+use openbook_v2_client::chain_data::ChainData;
+use openbook_v2_client::l2_book::{BookState, L2Book, L2Level, L2LevelChange};
+use openbook_v2_client::l3_book::{L3Book, L3Order, L3OrderChange};
+use openbook_v2_client::report::{as_string, FillReport};
+use openbook_v2_client::trade_fetcher::FillEventInfo;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::broadcast;
+
+/// Messages kept for subscribers that fall behind, a subscriber lagging further
+/// gets a new snapshot
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum FeedMessage<'a> {
+    /// The full books, updates continue at the next sequence number
+    Snapshot {
+        event_heap_seq_num: Option<u64>,
+        bids: &'a [L2Level],
+        asks: &'a [L2Level],
+        bid_orders: &'a [L3Order],
+        ask_orders: &'a [L3Order],
+    },
+    L2Update {
+        changes: &'a [L2LevelChange],
+    },
+    L3Update {
+        changes: &'a [L3OrderChange],
+    },
+    Fill {
+        /// Sequence number of the fill event in the event heap
+        event_heap_seq_num: u64,
+        fill: FillReport,
+    },
+}
+
+/// A message of a market, `seq_num` grows by one with every message published
+#[derive(Serialize)]
+struct Envelope<'a> {
+    #[serde(serialize_with = "as_string")]
+    market: Pubkey,
+    seq_num: u64,
+    slot: u64,
+    #[serde(flatten)]
+    message: FeedMessage<'a>,
+}
+
+/// Books and fills of a market, published to the subscribers as they change
+pub struct MarketFeed {
+    pub market_address: Pubkey,
+    pub market: Market,
+    depth: usize,
+    seq_num: u64,
+    /// The whole book, only its `depth` best levels are published
+    l2: Option<L2Book>,
+    l3: Option<L3Book>,
+    event_heap_seq_num: Option<u64>,
+    sender: broadcast::Sender<Arc<String>>,
+}
+
+impl MarketFeed {
+    pub fn new(market_address: Pubkey, market: Market, depth: usize) -> Self {
+        Self {
+            market_address,
+            market,
+            depth,
+            seq_num: 0,
+            l2: None,
+            l3: None,
+            event_heap_seq_num: None,
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Accounts to subscribe to
+    pub fn accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = BookState::accounts(self.market_address, &self.market);
+        accounts.push(self.market.event_heap);
+        accounts
+    }
+
+    /// The snapshot to start from and the receiver of the following messages, no
+    /// snapshot before the books were first built, it's published then
+    pub fn subscribe(&self) -> Result<(Option<Arc<String>>, broadcast::Receiver<Arc<String>>)> {
+        let snapshot = match (&self.l2, &self.l3) {
+            (Some(l2), Some(l3)) => Some(self.snapshot(l2, l3)?),
+            _ => None,
+        };
+        Ok((snapshot, self.sender.subscribe()))
+    }
+
+    fn snapshot(&self, l2: &L2Book, l3: &L3Book) -> Result<Arc<String>> {
+        let envelope = Envelope {
+            market: self.market_address,
+            seq_num: self.seq_num,
+            slot: l2.slot,
+            message: FeedMessage::Snapshot {
+                event_heap_seq_num: self.event_heap_seq_num,
+                bids: &l2.bids[..l2.bids.len().min(self.depth)],
+                asks: &l2.asks[..l2.asks.len().min(self.depth)],
+                bid_orders: &l3.bids,
+                ask_orders: &l3.asks,
+            },
+        };
+        Ok(Arc::new(serde_json::to_string(&envelope)?))
+    }
+
+    fn publish(&mut self, slot: u64, message: FeedMessage) -> Result<()> {
+        self.seq_num += 1;
+        let envelope = Envelope {
+            market: self.market_address,
+            seq_num: self.seq_num,
+            slot,
+            message,
+        };
+        // Only fails without subscribers
+        let _ = self
+            .sender
+            .send(Arc::new(serde_json::to_string(&envelope)?));
+        Ok(())
+    }
+
+    /// Rebuilds the books from `chain_data` and publishes their changes, then the
+    /// fills added to the event heap
+    pub fn update(&mut self, chain_data: &ChainData, now_ts: u64) -> Result<()> {
+        let state = BookState::from_chain_data(chain_data, self.market_address, now_ts)?;
+        let l2 = L2Book::new(self.market_address, &state, now_ts);
+        let l3 = L3Book::new(self.market_address, &state, now_ts);
+
+        match (self.l2.take(), self.l3.take()) {
+            (Some(old_l2), Some(old_l3)) => {
+                let changes = old_l2.diff_depth(&l2, self.depth);
+                if !changes.is_empty() {
+                    self.publish(l2.slot, FeedMessage::L2Update { changes: &changes })?;
+                }
+                let changes = old_l3.diff(&l3);
+                if !changes.is_empty() {
+                    self.publish(l3.slot, FeedMessage::L3Update { changes: &changes })?;
+                }
+            }
+            _ => {
+                self.seq_num += 1;
+                let snapshot = self.snapshot(&l2, &l3)?;
+                let _ = self.sender.send(snapshot);
+            }
+        }
+        self.l2 = Some(l2);
+        self.l3 = Some(l3);
+
+        self.update_fills(chain_data)
+    }
+
+    /// Publishes the fill events pushed to the event heap since the last update
+    ///
+    /// Events are pushed at the tail, so the newest ones are the last of the heap.
+    /// The events already in the heap when the feed starts aren't published.
+    fn update_fills(&mut self, chain_data: &ChainData) -> Result<()> {
+        let account = chain_data.account_and_slot(&self.market.event_heap)?;
+        let event_heap = account
+            .account
+            .load::<EventHeap>()
+            .with_context(|| format!("loading event heap {}", self.market.event_heap))?;
+        let seq_num = event_heap.header.seq_num;
+        let Some(last_seq_num) = self.event_heap_seq_num.replace(seq_num) else {
+            return Ok(());
+        };
+
+        let new_events = seq_num.saturating_sub(last_seq_num) as usize;
+        if new_events > event_heap.len() {
+            warn!(
+                "{} events of {} were consumed before being seen",
+                new_events - event_heap.len(),
+                self.market_address
+            );
+        }
+        let skip = event_heap.len().saturating_sub(new_events);
+        let first_seq_num = seq_num - (event_heap.len() - skip) as u64;
+        for (i, (event, _)) in event_heap.iter().skip(skip).enumerate() {
+            if event.event_type != EventType::Fill as u8 {
+                continue;
+            }
+            let fill = cast_ref::<AnyEvent, FillEvent>(event);
+            // The ask's event of an auction match mirrors the bid's one
+            if fill.is_auction_mirror() {
+                continue;
+            }
+            let fill =
+                FillReport::new(&self.market, &FillEventInfo::new(self.market_address, fill));
+            self.publish(
+                account.slot,
+                FeedMessage::Fill {
+                    event_heap_seq_num: first_seq_num + i as u64,
+                    fill,
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::Cluster;
+use clap::Parser;
+use log::*;
+use openbook_v2_client::account_update_stream::Message;
+use openbook_v2_client::chain_data::{AccountAndSlot, ChainData};
+use openbook_v2_client::market_fetch::fetch_market;
+use openbook_v2_client::pubkey_from_cli;
+use openbook_v2_client::websocket_source;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use tokio::net::TcpListener;
+
+mod feed;
+mod server;
+
+use feed::MarketFeed;
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "market-data-server",
+    about = "Publish the order books and fills of OpenBook v2 markets over a websocket"
+)]
+struct Cli {
+    /// Cluster moniker (m, d, t, l) or RPC url
+    #[clap(short, long, env = "RPC_URL", default_value = "m")]
+    url: String,
+
+    /// Websocket url of the RPC node, derived from --url by default
+    #[clap(long, env = "RPC_WS_URL")]
+    ws_url: Option<String>,
+
+    /// Markets to serve
+    #[clap(long, env = "MARKETS", required = true, value_delimiter = ',')]
+    market: Vec<String>,
+
+    /// Address the websocket server listens on
+    #[clap(long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+
+    /// Number of price levels published on each side of the L2 books
+    #[clap(long, default_value_t = 50)]
+    depth: usize,
+}
+
+fn update_chain_data(chain_data: &mut ChainData, message: Message) {
+    match message {
+        // Snapshot writes can precede any slot update, add them as RPC data so
+        // they are live right away
+        Message::Snapshot(accounts) => {
+            for account in accounts {
+                chain_data.update_from_rpc(
+                    &account.pubkey,
+                    AccountAndSlot {
+                        slot: account.slot,
+                        account: account.account,
+                    },
+                );
+            }
+        }
+        message => message.update_chain_data(chain_data),
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let cli = Cli::parse();
+    let cluster = Cluster::from_str(&cli.url)?;
+    let rpc_client = RpcClient::new(cluster.url().to_string());
+
+    let mut feeds = HashMap::new();
+    let mut accounts = vec![];
+    for market in cli.market.iter() {
+        let market = fetch_market(&rpc_client, pubkey_from_cli(market)).await?;
+        let feed = MarketFeed::new(market.market_pubkey, market.market_data, cli.depth);
+        accounts.extend(feed.accounts());
+        feeds.insert(market.market_pubkey, feed);
+    }
+    let feeds = Arc::new(Mutex::new(feeds));
+
+    let listener = TcpListener::bind(cli.bind).await?;
+    info!("Listening on {}", cli.bind);
+
+    let (sender, receiver) = async_channel::unbounded();
+    websocket_source::start(
+        websocket_source::Config {
+            rpc_http_url: cluster.url().to_string(),
+            rpc_ws_url: cli.ws_url.unwrap_or_else(|| cluster.ws_url().to_string()),
+        },
+        accounts,
+        sender,
+    );
+    let server_feeds = feeds.clone();
+    tokio::spawn(async move {
+        if let Err(err) = server::serve(listener, server_feeds).await {
+            error!("websocket server failed: {:?}", err);
+            std::process::exit(1);
+        }
+    });
+
+    let mut chain_data = ChainData::new();
+    loop {
+        let message = receiver.recv().await?;
+        update_chain_data(&mut chain_data, message);
+        // Apply the queued updates before rebuilding the books
+        while let Ok(message) = receiver.try_recv() {
+            update_chain_data(&mut chain_data, message);
+        }
+
+        let now_ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        for feed in feeds.lock().unwrap().values_mut() {
+            if let Err(err) = feed.update(&chain_data, now_ts) {
+                debug!("market {} not updated: {:?}", feed.market_address, err);
+            }
+        }
+    }
+}
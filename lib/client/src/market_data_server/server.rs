@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use futures::{SinkExt, StreamExt};
+use log::*;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::feed::MarketFeed;
+
+pub type Feeds = Arc<Mutex<HashMap<Pubkey, MarketFeed>>>;
+
+/// Messages queued for a connection, a slow client makes its subscriptions lag
+const CONNECTION_QUEUE: usize = 1024;
+
+/// A client message, like `{"command": "subscribe", "market": "<pubkey>"}`
+///
+/// A subscription starts with a snapshot of the market, followed by its L2 and L3
+/// book changes and fills. The messages of a market carry consecutive sequence
+/// numbers, a client seeing a gap subscribes again to resync from a new snapshot.
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Command {
+    Subscribe { market: String },
+    Unsubscribe { market: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Reply {
+    Unsubscribed { market: String },
+    Error { message: String },
+}
+
+pub async fn serve(listener: TcpListener, feeds: Feeds) -> Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let feeds = feeds.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, feeds).await {
+                debug!("connection {} failed: {:?}", peer, err);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, feeds: Feeds) -> Result<()> {
+    let (mut sink, mut stream) = tokio_tungstenite::accept_async(stream).await?.split();
+    let (sender, mut receiver) = mpsc::channel::<Arc<String>>(CONNECTION_QUEUE);
+    let mut subscriptions: HashMap<Pubkey, JoinHandle<()>> = HashMap::new();
+
+    let result = loop {
+        tokio::select! {
+            message = stream.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break Ok(()),
+                    Some(Ok(_)) => continue,
+                    Some(Err(err)) => break Err(err.into()),
+                };
+                if let Some(reply) = handle_command(&text, &feeds, &sender, &mut subscriptions) {
+                    let reply = serde_json::to_string(&reply)?;
+                    if let Err(err) = sink.send(Message::Text(reply)).await {
+                        break Err(err.into());
+                    }
+                }
+            }
+            Some(message) = receiver.recv() => {
+                if let Err(err) = sink.send(Message::Text(message.to_string())).await {
+                    break Err(err.into());
+                }
+            }
+        }
+    };
+
+    for (_, subscription) in subscriptions {
+        subscription.abort();
+    }
+    result
+}
+
+fn handle_command(
+    text: &str,
+    feeds: &Feeds,
+    sender: &mpsc::Sender<Arc<String>>,
+    subscriptions: &mut HashMap<Pubkey, JoinHandle<()>>,
+) -> Option<Reply> {
+    let error = |message: String| Some(Reply::Error { message });
+    let command = match serde_json::from_str::<Command>(text) {
+        Ok(command) => command,
+        Err(err) => return error(format!("invalid command: {}", err)),
+    };
+    match command {
+        Command::Subscribe { market } => {
+            let Ok(address) = Pubkey::from_str(&market) else {
+                return error(format!("invalid market {}", market));
+            };
+            if !feeds.lock().unwrap().contains_key(&address) {
+                return error(format!("market {} is not served", market));
+            }
+            let subscription = tokio::spawn(forward(feeds.clone(), address, sender.clone()));
+            if let Some(previous) = subscriptions.insert(address, subscription) {
+                previous.abort();
+            }
+            None
+        }
+        Command::Unsubscribe { market } => {
+            let subscription = Pubkey::from_str(&market)
+                .ok()
+                .and_then(|address| subscriptions.remove(&address));
+            match subscription {
+                Some(subscription) => {
+                    subscription.abort();
+                    Some(Reply::Unsubscribed { market })
+                }
+                None => error(format!("not subscribed to {}", market)),
+            }
+        }
+    }
+}
+
+/// Sends the snapshot of a market then its messages to a connection, starting over
+/// from a new snapshot when the connection falls behind
+async fn forward(feeds: Feeds, market: Pubkey, sender: mpsc::Sender<Arc<String>>) {
+    loop {
+        let subscription = feeds.lock().unwrap().get(&market).map(|f| f.subscribe());
+        let (snapshot, mut receiver) = match subscription {
+            Some(Ok(subscription)) => subscription,
+            Some(Err(err)) => {
+                warn!("snapshot of {} failed: {:?}", market, err);
+                let reply = Reply::Error {
+                    message: err.to_string(),
+                };
+                if let Ok(reply) = serde_json::to_string(&reply) {
+                    let _ = sender.send(Arc::new(reply)).await;
+                }
+                return;
+            }
+            None => return,
+        };
+        if let Some(snapshot) = snapshot {
+            if sender.send(snapshot).await.is_err() {
+                return;
+            }
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(message) => {
+                    if sender.send(message).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!("subscriber of {} skipped {} messages", market, skipped);
+                    break;
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+}
@@ -2,14 +2,33 @@ use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use anchor_lang::AccountDeserialize;
-use openbook_v2::state::Market;
+use openbook_v2::state::{Market, MarketStats, TradeStats};
+use std::collections::HashMap;
 use std::str::FromStr;
 
+use crate::market_stats_address;
+
 /// Struct to hold both the market Pubkey and the Market struct
 #[derive(Debug)] // Derive the Debug trait to allow raw data logging
 pub struct MarketInfo {
     pub market_pubkey: Pubkey,
     pub market_data: Market,
+    /// The `MarketStats` account, None if the market has none
+    pub market_stats: Option<MarketStats>,
+}
+
+impl MarketInfo {
+    /// Price in lots and timestamp of the last trade, None if the market never traded
+    pub fn last_trade(&self) -> Option<(i64, u64)> {
+        (self.market_data.last_price_lots != 0)
+            .then_some((self.market_data.last_price_lots, self.market_data.last_trade_ts))
+    }
+
+    /// High and low price in lots and base lots volume of the trades over the last 24h,
+    /// None if the market has no market stats
+    pub fn trade_stats_24h(&self, now_ts: u64) -> Option<TradeStats> {
+        self.market_stats.as_ref().map(|stats| stats.last_24h(now_ts))
+    }
 }
 
 /// Fetch all OpenBook markets and return them as a vector of `MarketInfo` structs
@@ -19,6 +38,12 @@ pub async fn fetch_all_markets(client: &RpcClient) -> Result<Vec<MarketInfo>> {
     // Fetch all accounts associated with the OpenBook program
     let accounts = client.get_program_accounts(&program_id).await?;
 
+    // The market stats accounts come along, keyed by their market
+    let mut market_stats: HashMap<Pubkey, MarketStats> = accounts.iter()
+        .filter_map(|(_, account_data)| MarketStats::try_deserialize(&mut &account_data.data[..]).ok())
+        .map(|stats| (stats.market, stats))
+        .collect();
+
     // Deserialize the account data and store both the Pubkey and the Market data
     let markets: Vec<MarketInfo> = accounts.into_iter()
         .filter_map(|(pubkey, account_data)| {
@@ -27,9 +52,27 @@ pub async fn fetch_all_markets(client: &RpcClient) -> Result<Vec<MarketInfo>> {
                 .map(|market_data| MarketInfo {
                     market_pubkey: pubkey,
                     market_data,
+                    market_stats: market_stats.remove(&pubkey),
                 })
         })
         .collect();
 
     Ok(markets)
 }
+
+/// Fetch a single OpenBook market, with its market stats if it has them
+pub async fn fetch_market(client: &RpcClient, market_pubkey: Pubkey) -> Result<MarketInfo> {
+    let account_data = client.get_account_data(&market_pubkey).await?;
+    let market_data = Market::try_deserialize(&mut &account_data[..])?;
+    let market_stats = if market_data.has_market_stats() {
+        let stats_data = client.get_account_data(&market_stats_address(&market_pubkey)).await?;
+        Some(MarketStats::try_deserialize(&mut &stats_data[..])?)
+    } else {
+        None
+    };
+    Ok(MarketInfo {
+        market_pubkey,
+        market_data,
+        market_stats,
+    })
+}
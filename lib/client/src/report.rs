@@ -0,0 +1,378 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+use anyhow::Result;
+use openbook_v2::state::Market;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::market_fetch::MarketInfo;
+use crate::trade_fetcher::FillEventInfo;
+
+/// How reports are printed: a padded table, JSON lines or CSV with a header row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => anyhow::bail!("unknown output format {}, expected table, json or csv", s),
+        }
+    }
+}
+
+pub fn as_string<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Price in quote UI units per base UI unit
+pub fn ui_price(market: &Market, price_lots: i64) -> f64 {
+    let decimals = market.base_decimals as i32 - market.quote_decimals as i32;
+    price_lots as f64 * market.quote_lot_size as f64 / market.base_lot_size as f64
+        * 10f64.powi(decimals)
+}
+
+pub fn ui_base_lots(market: &Market, base_lots: i64) -> f64 {
+    ui_base_native(market, base_lots as i128 * market.base_lot_size as i128)
+}
+
+pub fn ui_base_native(market: &Market, native: impl Into<i128>) -> f64 {
+    native.into() as f64 / 10f64.powi(market.base_decimals as i32)
+}
+
+pub fn ui_quote_native(market: &Market, native: impl Into<i128>) -> f64 {
+    native.into() as f64 / 10f64.powi(market.quote_decimals as i32)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MarketReport {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    pub name: String,
+    #[serde(serialize_with = "as_string")]
+    pub base_mint: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub quote_mint: Pubkey,
+    pub base_lot_size: i64,
+    pub quote_lot_size: i64,
+    /// In units of 1e-6
+    pub maker_fee: i64,
+    /// In units of 1e-6
+    pub taker_fee: i64,
+    pub time_expiry: i64,
+    pub fees_accrued: f64,
+    pub fees_available: f64,
+    /// Taker fees collected in base on markets with fees in base
+    pub base_fees_available: f64,
+    pub base_deposit_total: f64,
+    pub quote_deposit_total: f64,
+    pub last_price: Option<f64>,
+    /// Trade stats over the last 24h, empty for markets without market stats
+    pub high_price_24h: Option<f64>,
+    pub low_price_24h: Option<f64>,
+    pub volume_24h: Option<f64>,
+}
+
+impl MarketReport {
+    pub fn new(info: &MarketInfo, now_ts: u64) -> Self {
+        let market = &info.market_data;
+        let stats = info.trade_stats_24h(now_ts);
+        let traded = stats.filter(|stats| stats.volume_base_lots != 0);
+        Self {
+            market: info.market_pubkey,
+            name: market.name().to_string(),
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            base_lot_size: market.base_lot_size,
+            quote_lot_size: market.quote_lot_size,
+            maker_fee: market.maker_fee,
+            taker_fee: market.taker_fee,
+            time_expiry: market.time_expiry,
+            fees_accrued: ui_quote_native(market, market.fees_accrued as i128),
+            fees_available: ui_quote_native(market, market.fees_available),
+            base_fees_available: ui_base_native(market, market.base_fees_available),
+            base_deposit_total: ui_base_native(market, market.base_deposit_total),
+            quote_deposit_total: ui_quote_native(market, market.quote_deposit_total),
+            last_price: info
+                .last_trade()
+                .map(|(price_lots, _)| ui_price(market, price_lots)),
+            high_price_24h: traded.map(|stats| ui_price(market, stats.high_price_lots)),
+            low_price_24h: traded.map(|stats| ui_price(market, stats.low_price_lots)),
+            volume_24h: stats.map(|stats| ui_base_lots(market, stats.volume_base_lots)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct VaultReport {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub base_vault: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub quote_vault: Pubkey,
+}
+
+impl VaultReport {
+    pub fn new(info: &MarketInfo) -> Self {
+        Self {
+            market: info.market_pubkey,
+            base_vault: info.market_data.market_base_vault,
+            quote_vault: info.market_data.market_quote_vault,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MintReport {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub base_mint: Pubkey,
+    pub base_decimals: u8,
+    #[serde(serialize_with = "as_string")]
+    pub quote_mint: Pubkey,
+    pub quote_decimals: u8,
+}
+
+impl MintReport {
+    pub fn new(info: &MarketInfo) -> Self {
+        Self {
+            market: info.market_pubkey,
+            base_mint: info.market_data.base_mint,
+            base_decimals: info.market_data.base_decimals,
+            quote_mint: info.market_data.quote_mint,
+            quote_decimals: info.market_data.quote_decimals,
+        }
+    }
+}
+
+/// Vault balances next to the deposits the market accounts for
+#[derive(Clone, Debug, Serialize)]
+pub struct DepositReport {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    pub base_vault_balance: f64,
+    pub quote_vault_balance: f64,
+    pub base_deposit_total: f64,
+    pub quote_deposit_total: f64,
+}
+
+impl DepositReport {
+    pub fn new(info: &MarketInfo, base_vault_balance: u64, quote_vault_balance: u64) -> Self {
+        let market = &info.market_data;
+        Self {
+            market: info.market_pubkey,
+            base_vault_balance: ui_base_native(market, base_vault_balance),
+            quote_vault_balance: ui_quote_native(market, quote_vault_balance),
+            base_deposit_total: ui_base_native(market, market.base_deposit_total),
+            quote_deposit_total: ui_quote_native(market, market.quote_deposit_total),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FillReport {
+    #[serde(serialize_with = "as_string")]
+    pub market: Pubkey,
+    pub price: f64,
+    pub quantity: f64,
+    pub taker_side: &'static str,
+    #[serde(serialize_with = "as_string")]
+    pub maker: Pubkey,
+    #[serde(serialize_with = "as_string")]
+    pub taker: Pubkey,
+    pub timestamp: u64,
+    pub market_seq_num: u64,
+    pub maker_out: bool,
+    pub maker_client_order_id: u64,
+    pub taker_client_order_id: u64,
+}
+
+impl FillReport {
+    pub fn new(market: &Market, fill: &FillEventInfo) -> Self {
+        Self {
+            market: fill.market_pubkey,
+            price: ui_price(market, fill.price),
+            quantity: ui_base_lots(market, fill.quantity),
+            taker_side: if fill.taker_side == 0 { "bid" } else { "ask" },
+            maker: fill.maker,
+            taker: fill.taker,
+            timestamp: fill.timestamp,
+            market_seq_num: fill.market_seq_num,
+            maker_out: fill.maker_out != 0,
+            maker_client_order_id: fill.maker_client_order_id,
+            taker_client_order_id: fill.taker_client_order_id,
+        }
+    }
+}
+
+/// Writes `rows` to stdout in `format`
+pub fn print_report<T: Serialize>(rows: &[T], format: OutputFormat) -> Result<()> {
+    write_report(&mut std::io::stdout().lock(), rows, format)
+}
+
+/// Writes `rows` to `out` in `format`, nothing at all without rows
+pub fn write_report<T: Serialize, W: Write>(
+    out: &mut W,
+    rows: &[T],
+    format: OutputFormat,
+) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    if format == OutputFormat::Json {
+        for row in rows {
+            writeln!(out, "{}", serde_json::to_string(row)?)?;
+        }
+        return Ok(());
+    }
+
+    let mut headers: Vec<String> = vec![];
+    let mut cells: Vec<Vec<String>> = vec![];
+    for row in rows {
+        let Value::Object(fields) = serde_json::to_value(row)? else {
+            anyhow::bail!("report rows must serialize to objects");
+        };
+        if headers.is_empty() {
+            headers = fields.keys().cloned().collect();
+        }
+        cells.push(fields.into_iter().map(|(_, v)| cell(v)).collect());
+    }
+
+    match format {
+        OutputFormat::Csv => {
+            writeln!(out, "{}", csv_line(&headers))?;
+            for row in cells {
+                writeln!(out, "{}", csv_line(&row))?;
+            }
+        }
+        OutputFormat::Table => {
+            let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+            for row in cells.iter() {
+                for (width, value) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(value.len());
+                }
+            }
+            let line = |row: &[String]| {
+                row.iter()
+                    .zip(widths.iter())
+                    .map(|(value, width)| format!("{:<width$}", value, width = width))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            writeln!(out, "{}", line(&headers))?;
+            writeln!(
+                out,
+                "{}",
+                "-".repeat(widths.iter().sum::<usize>() + widths.len())
+            )?;
+            for row in cells {
+                writeln!(out, "{}", line(&row))?;
+            }
+        }
+        OutputFormat::Json => unreachable!(),
+    }
+    Ok(())
+}
+
+fn cell(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s,
+        v => v.to_string(),
+    }
+}
+
+fn csv_line(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| {
+            if v.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", v.replace('"', "\"\""))
+            } else {
+                v.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: &'static str,
+        price: Option<f64>,
+        size: i64,
+    }
+
+    fn report(rows: &[Row], format: OutputFormat) -> String {
+        let mut out = vec![];
+        write_report(&mut out, rows, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_quoting() {
+        let line =
+            |values: &[&str]| csv_line(&values.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+        assert_eq!(line(&["a", "", "1.5"]), "a,,1.5");
+        assert_eq!(line(&["a,b", "c"]), "\"a,b\",c");
+        assert_eq!(line(&["say \"hi\""]), "\"say \"\"hi\"\"\"");
+        assert_eq!(line(&["two\nlines", "cr\r"]), "\"two\nlines\",\"cr\r\"");
+    }
+
+    #[test]
+    fn empty_reports_print_nothing() {
+        for format in [OutputFormat::Table, OutputFormat::Json, OutputFormat::Csv] {
+            assert_eq!(report(&[], format), "");
+        }
+    }
+
+    #[test]
+    fn report_formats() {
+        let rows = [
+            Row {
+                name: "SOL/USDC",
+                price: Some(1.5),
+                size: 2,
+            },
+            Row {
+                name: "a, \"b\"",
+                price: None,
+                size: 10,
+            },
+        ];
+        assert_eq!(
+            report(&rows, OutputFormat::Csv),
+            "name,price,size\nSOL/USDC,1.5,2\n\"a, \"\"b\"\"\",,10\n"
+        );
+        assert_eq!(
+            report(&rows, OutputFormat::Json),
+            "{\"name\":\"SOL/USDC\",\"price\":1.5,\"size\":2}\n\
+             {\"name\":\"a, \\\"b\\\"\",\"price\":null,\"size\":10}\n"
+        );
+        assert_eq!(
+            report(&rows, OutputFormat::Table),
+            "name     price size\n\
+             --------------------\n\
+             SOL/USDC 1.5   2   \n\
+             a, \"b\"         10  \n"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::System;
+use anchor_lang::Id;
+use anchor_spl::token::Token;
+use anyhow::Result;
+use openbook_v2::state::Market;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::market_stats_account;
+
+/// The mint a swap of `input_mint` on `market` outputs, if the market trades it
+fn other_mint(market: &Market, input_mint: &Pubkey) -> Option<Pubkey> {
+    if *input_mint == market.base_mint {
+        Some(market.quote_mint)
+    } else if *input_mint == market.quote_mint {
+        Some(market.base_mint)
+    } else {
+        None
+    }
+}
+
+/// Intermediate and output mints of a swap of `input_mint` through both markets
+pub fn route_mints(
+    input_mint: &Pubkey,
+    first_market: &Market,
+    second_market: &Market,
+) -> Result<(Pubkey, Pubkey)> {
+    let intermediate_mint = other_mint(first_market, input_mint)
+        .ok_or_else(|| anyhow::anyhow!("first market does not trade {}", input_mint))?;
+    let output_mint = other_mint(second_market, &intermediate_mint)
+        .ok_or_else(|| anyhow::anyhow!("second market does not trade {}", intermediate_mint))?;
+    Ok((intermediate_mint, output_mint))
+}
+
+/// Accounts for a `route_take_order` swapping `input_mint` through both markets,
+/// using the owner's associated token accounts for all three mints.
+pub fn route_take_order_accounts(
+    owner: Pubkey,
+    input_mint: &Pubkey,
+    first_market_address: Pubkey,
+    first_market: &Market,
+    second_market_address: Pubkey,
+    second_market: &Market,
+) -> Result<openbook_v2::accounts::RouteTakeOrder> {
+    let (intermediate_mint, output_mint) = route_mints(input_mint, first_market, second_market)?;
+
+    Ok(openbook_v2::accounts::RouteTakeOrder {
+        signer: owner,
+        penalty_payer: owner,
+        first_market: first_market_address,
+        first_market_authority: first_market.market_authority,
+        first_bids: first_market.bids,
+        first_asks: first_market.asks,
+        first_market_base_vault: first_market.market_base_vault,
+        first_market_quote_vault: first_market.market_quote_vault,
+        first_event_heap: first_market.event_heap,
+        first_oracle_a: first_market.oracle_a.into(),
+        first_oracle_b: first_market.oracle_b.into(),
+        second_market: second_market_address,
+        second_market_authority: second_market.market_authority,
+        second_bids: second_market.bids,
+        second_asks: second_market.asks,
+        second_market_base_vault: second_market.market_base_vault,
+        second_market_quote_vault: second_market.market_quote_vault,
+        second_event_heap: second_market.event_heap,
+        second_oracle_a: second_market.oracle_a.into(),
+        second_oracle_b: second_market.oracle_b.into(),
+        user_input_account: spl_associated_token_account::get_associated_token_address(
+            &owner, input_mint,
+        ),
+        user_intermediate_account: spl_associated_token_account::get_associated_token_address(
+            &owner,
+            &intermediate_mint,
+        ),
+        user_output_account: spl_associated_token_account::get_associated_token_address(
+            &owner,
+            &output_mint,
+        ),
+        token_program: Token::id(),
+        system_program: System::id(),
+        first_market_stats: market_stats_account(&first_market_address, first_market),
+        second_market_stats: market_stats_account(&second_market_address, second_market),
+    })
+}
@@ -0,0 +1,145 @@
+//! Markets and books for unit tests
+
+use std::cell::RefCell;
+
+use bytemuck::Zeroable;
+use openbook_v2::state::{
+    fixed_price_data, new_node_key, oracle_pegged_price_data, BookSide, BookSideOrderTree,
+    LeafNode, Market, OrderTreeType, Orderbook, Side,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::trade_backfill::StoredFill;
+
+/// A market without fees, with lot sizes of one and no decimals
+pub fn test_market() -> Market {
+    let mut market = Market::zeroed();
+    market.base_lot_size = 1;
+    market.quote_lot_size = 1;
+    market
+}
+
+/// A stored fill of `quantity` base lots at `price` lots, taken by a bid
+pub fn stored_fill(
+    market: Pubkey,
+    seq_num: u64,
+    timestamp: u64,
+    price: i64,
+    quantity: i64,
+) -> StoredFill {
+    StoredFill {
+        market,
+        seq_num,
+        signature: Signature::new_unique(),
+        slot: seq_num,
+        timestamp,
+        taker_side: 0,
+        maker: Pubkey::new_unique(),
+        maker_slot: 0,
+        maker_client_order_id: 0,
+        maker_fee: 0,
+        taker: Pubkey::new_unique(),
+        taker_client_order_id: 0,
+        taker_fee_ceil: 0,
+        taker_fee_in_base: false,
+        price,
+        quantity,
+    }
+}
+
+/// The bid and ask book sides of a test market
+pub struct TestBook {
+    pub bids: Box<RefCell<BookSide>>,
+    pub asks: Box<RefCell<BookSide>>,
+    seq_num: u64,
+}
+
+impl Default for TestBook {
+    fn default() -> Self {
+        let book = Self {
+            bids: Box::new(RefCell::new(BookSide::zeroed())),
+            asks: Box::new(RefCell::new(BookSide::zeroed())),
+            seq_num: 0,
+        };
+        book.bids.borrow_mut().nodes.order_tree_type = OrderTreeType::Bids.into();
+        book.asks.borrow_mut().nodes.order_tree_type = OrderTreeType::Asks.into();
+        book
+    }
+}
+
+impl TestBook {
+    pub fn orderbook(&self) -> Orderbook<'_> {
+        Orderbook {
+            bids: self.bids.borrow_mut(),
+            asks: self.asks.borrow_mut(),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        side: Side,
+        order_tree: BookSideOrderTree,
+        price_data: u64,
+        quantity: i64,
+        peg_limit: i64,
+        owner: Pubkey,
+    ) -> u128 {
+        self.seq_num += 1;
+        let key = new_node_key(side, price_data, self.seq_num);
+        let leaf = LeafNode::new(0, key, owner, quantity, 0, 0, peg_limit, self.seq_num);
+        let book_side = match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        };
+        book_side
+            .borrow_mut()
+            .insert_leaf(order_tree, &leaf)
+            .unwrap();
+        key
+    }
+
+    /// Posts an order at a fixed price, returns its order id
+    pub fn add_fixed(&mut self, side: Side, price_lots: i64, quantity: i64, owner: Pubkey) -> u128 {
+        let price_data = fixed_price_data(price_lots).unwrap();
+        self.insert(
+            side,
+            BookSideOrderTree::Fixed,
+            price_data,
+            quantity,
+            -1,
+            owner,
+        )
+    }
+
+    /// Posts an order pegged `price_offset_lots` from the oracle, returns its order id
+    pub fn add_pegged(
+        &mut self,
+        side: Side,
+        price_offset_lots: i64,
+        peg_limit: i64,
+        quantity: i64,
+        owner: Pubkey,
+    ) -> u128 {
+        let price_data = oracle_pegged_price_data(price_offset_lots);
+        self.insert(
+            side,
+            BookSideOrderTree::OraclePegged,
+            price_data,
+            quantity,
+            peg_limit,
+            owner,
+        )
+    }
+
+    pub fn remove(&mut self, side: Side, order_tree: BookSideOrderTree, order_id: u128) {
+        let book_side = match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        };
+        book_side
+            .borrow_mut()
+            .remove_by_key(order_tree, order_id)
+            .unwrap();
+    }
+}
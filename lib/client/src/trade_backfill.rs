@@ -0,0 +1,458 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Result;
+use log::info;
+use openbook_v2::logs::FillLog;
+use rusqlite::{params, Connection, OptionalExtension};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::event_decoder::{decode_transaction_events, OpenBookEvent};
+use crate::market_fetch::fetch_market;
+
+/// A fill of the store, u64 values are stored as their i64 bit pattern
+#[derive(Clone, Debug)]
+pub struct StoredFill {
+    pub market: Pubkey,
+    pub seq_num: u64,
+    pub signature: Signature,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub taker_side: u8,
+    pub maker: Pubkey,
+    pub maker_slot: u8,
+    pub maker_client_order_id: u64,
+    /// In quote native units
+    pub maker_fee: u64,
+    pub taker: Pubkey,
+    pub taker_client_order_id: u64,
+    /// In base native units if `taker_fee_in_base`, in quote native units otherwise
+    pub taker_fee_ceil: u64,
+    pub taker_fee_in_base: bool,
+    pub price: i64,
+    pub quantity: i64,
+}
+
+/// Where the backfill of a market stands
+///
+/// A run walks the signatures of the market from the newest to `until`, the newest
+/// signature of the previous complete run. `cursor` is the last processed signature
+/// of the run in progress, if any.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub until: Option<Signature>,
+    pub run_newest: Option<Signature>,
+    pub cursor: Option<Signature>,
+}
+
+/// SQLite store of the fills of backfilled markets
+///
+/// All the fills of a taker order share its `seq_num`, so fills are deduplicated by
+/// `(market, seq_num, maker, maker_slot)`: a taker order matches a maker order once.
+pub struct TradeStore {
+    conn: Connection,
+}
+
+impl TradeStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS fills (
+                market TEXT NOT NULL,
+                seq_num INTEGER NOT NULL,
+                signature TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL,
+                taker_side INTEGER NOT NULL,
+                maker TEXT NOT NULL,
+                maker_slot INTEGER NOT NULL,
+                maker_client_order_id INTEGER NOT NULL,
+                maker_fee INTEGER NOT NULL,
+                taker TEXT NOT NULL,
+                taker_client_order_id INTEGER NOT NULL,
+                taker_fee_ceil INTEGER NOT NULL,
+                taker_fee_in_base INTEGER NOT NULL,
+                price INTEGER NOT NULL,
+                quantity INTEGER NOT NULL,
+                PRIMARY KEY (market, seq_num, maker, maker_slot)
+            );
+            CREATE INDEX IF NOT EXISTS fills_market_timestamp ON fills (market, timestamp);
+            CREATE TABLE IF NOT EXISTS checkpoints (
+                market TEXT PRIMARY KEY,
+                until_signature TEXT,
+                run_newest_signature TEXT,
+                cursor_signature TEXT
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn checkpoint(&self, market: &Pubkey) -> Result<Checkpoint> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT until_signature, run_newest_signature, cursor_signature
+                FROM checkpoints WHERE market = ?1",
+                params![market.to_string()],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((until, run_newest, cursor)) = row else {
+            return Ok(Checkpoint::default());
+        };
+        let parse = |s: Option<String>| s.as_deref().map(Signature::from_str).transpose();
+        Ok(Checkpoint {
+            until: parse(until)?,
+            run_newest: parse(run_newest)?,
+            cursor: parse(cursor)?,
+        })
+    }
+
+    fn set_checkpoint(conn: &Connection, market: &Pubkey, checkpoint: &Checkpoint) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO checkpoints
+            (market, until_signature, run_newest_signature, cursor_signature)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                market.to_string(),
+                checkpoint.until.map(|s| s.to_string()),
+                checkpoint.run_newest.map(|s| s.to_string()),
+                checkpoint.cursor.map(|s| s.to_string()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Inserts the fills of a transaction and moves the checkpoint atomically,
+    /// returns the number of fills that weren't stored yet
+    pub fn commit(
+        &mut self,
+        market: &Pubkey,
+        fills: &[StoredFill],
+        checkpoint: &Checkpoint,
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut inserted = 0;
+        for fill in fills {
+            inserted += tx.execute(
+                "INSERT OR IGNORE INTO fills (
+                    market, seq_num, signature, slot, timestamp, taker_side,
+                    maker, maker_slot, maker_client_order_id, maker_fee,
+                    taker, taker_client_order_id, taker_fee_ceil, taker_fee_in_base,
+                    price, quantity
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    fill.market.to_string(),
+                    fill.seq_num as i64,
+                    fill.signature.to_string(),
+                    fill.slot as i64,
+                    fill.timestamp as i64,
+                    fill.taker_side,
+                    fill.maker.to_string(),
+                    fill.maker_slot,
+                    fill.maker_client_order_id as i64,
+                    fill.maker_fee as i64,
+                    fill.taker.to_string(),
+                    fill.taker_client_order_id as i64,
+                    fill.taker_fee_ceil as i64,
+                    fill.taker_fee_in_base,
+                    fill.price,
+                    fill.quantity,
+                ],
+            )?;
+        }
+        Self::set_checkpoint(&tx, market, checkpoint)?;
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Fills of `market` with `from_ts <= timestamp < to_ts`, by sequence number and
+    /// then in the order they were logged
+    pub fn fills(&self, market: &Pubkey, from_ts: u64, to_ts: u64) -> Result<Vec<StoredFill>> {
+        let mut statement = self.conn.prepare(
+            "SELECT market, seq_num, signature, slot, timestamp, taker_side,
+                maker, maker_slot, maker_client_order_id, maker_fee,
+                taker, taker_client_order_id, taker_fee_ceil, taker_fee_in_base,
+                price, quantity
+            FROM fills WHERE market = ?1 AND timestamp >= ?2 AND timestamp < ?3
+            ORDER BY seq_num, rowid",
+        )?;
+        let rows = statement.query_map(
+            params![market.to_string(), from_ts as i64, to_ts as i64],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, u8>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, u8>(7)?,
+                    row.get::<_, i64>(8)?,
+                    row.get::<_, i64>(9)?,
+                    row.get::<_, String>(10)?,
+                    row.get::<_, i64>(11)?,
+                    row.get::<_, i64>(12)?,
+                    row.get::<_, bool>(13)?,
+                    row.get::<_, i64>(14)?,
+                    row.get::<_, i64>(15)?,
+                ))
+            },
+        )?;
+
+        let mut fills = vec![];
+        for row in rows {
+            let row = row?;
+            fills.push(StoredFill {
+                market: Pubkey::from_str(&row.0)?,
+                seq_num: row.1 as u64,
+                signature: Signature::from_str(&row.2)?,
+                slot: row.3 as u64,
+                timestamp: row.4 as u64,
+                taker_side: row.5,
+                maker: Pubkey::from_str(&row.6)?,
+                maker_slot: row.7,
+                maker_client_order_id: row.8 as u64,
+                maker_fee: row.9 as u64,
+                taker: Pubkey::from_str(&row.10)?,
+                taker_client_order_id: row.11 as u64,
+                taker_fee_ceil: row.12 as u64,
+                taker_fee_in_base: row.13,
+                price: row.14,
+                quantity: row.15,
+            });
+        }
+        Ok(fills)
+    }
+}
+
+impl StoredFill {
+    pub fn new(fill: &FillLog, fees_in_base: bool, signature: Signature, slot: u64) -> Self {
+        Self {
+            market: fill.market,
+            seq_num: fill.seq_num,
+            signature,
+            slot,
+            timestamp: fill.timestamp,
+            taker_side: fill.taker_side,
+            maker: fill.maker,
+            maker_slot: fill.maker_slot,
+            maker_client_order_id: fill.maker_client_order_id,
+            maker_fee: fill.maker_fee,
+            taker: fill.taker,
+            taker_client_order_id: fill.taker_client_order_id,
+            taker_fee_ceil: fill.taker_fee_ceil,
+            taker_fee_in_base: fees_in_base,
+            price: fill.price,
+            quantity: fill.quantity,
+        }
+    }
+}
+
+/// Decodes the `FillLog`s of `market` in a transaction
+async fn fetch_transaction_fills(
+    client: &RpcClient,
+    market: &Pubkey,
+    fees_in_base: bool,
+    signature: Signature,
+) -> Result<Vec<StoredFill>> {
+    let tx = client
+        .get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    Ok(decode_transaction_events(&tx)?
+        .into_iter()
+        .filter_map(|event| match event {
+            OpenBookEvent::Fill(fill) if fill.market == *market => {
+                Some(StoredFill::new(&fill, fees_in_base, signature, tx.slot))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Stores the fills of all the transactions of `market` not backfilled yet, newest
+/// first, and returns the number of new fills
+///
+/// The checkpoint moves with every transaction, so an interrupted backfill resumes
+/// where it stopped. Once it reaches the transactions of the previous run, the next
+/// one only looks at the transactions newer than the ones of this run.
+pub async fn backfill_fills(
+    client: &RpcClient,
+    store: &mut TradeStore,
+    market: Pubkey,
+) -> Result<usize> {
+    let fees_in_base = fetch_market(client, market)
+        .await?
+        .market_data
+        .has_fees_in_base();
+    let mut checkpoint = store.checkpoint(&market)?;
+    if let Some(cursor) = checkpoint.cursor {
+        info!("Resuming the backfill of {} before {}", market, cursor);
+    }
+
+    let mut inserted = 0;
+    loop {
+        let signatures = client
+            .get_signatures_for_address_with_config(
+                &market,
+                GetConfirmedSignaturesForAddress2Config {
+                    before: checkpoint.cursor,
+                    until: checkpoint.until,
+                    limit: Some(1000),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+        info!("Fetched {} signatures", signatures.len());
+        if signatures.is_empty() {
+            break;
+        }
+
+        for signature_info in signatures {
+            let signature = Signature::from_str(&signature_info.signature)?;
+            let fills = if signature_info.err.is_some() {
+                vec![]
+            } else {
+                fetch_transaction_fills(client, &market, fees_in_base, signature).await?
+            };
+
+            checkpoint.run_newest = checkpoint.run_newest.or(Some(signature));
+            checkpoint.cursor = Some(signature);
+            inserted += store.commit(&market, &fills, &checkpoint)?;
+        }
+    }
+
+    // The run is complete, the next one stops at its newest signature
+    let complete = Checkpoint {
+        until: checkpoint.run_newest.or(checkpoint.until),
+        run_newest: None,
+        cursor: None,
+    };
+    if complete != checkpoint {
+        store.commit(&market, &[], &complete)?;
+    } else {
+        info!("No new transactions for {}", market);
+    }
+    info!("Backfilled {} new fills of {}", inserted, market);
+
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::stored_fill;
+
+    #[test]
+    fn commit_keeps_all_fills_of_a_taker_order() {
+        let mut store = TradeStore::open_in_memory().unwrap();
+        let market = Pubkey::new_unique();
+        let other_market = Pubkey::new_unique();
+        let checkpoint = Checkpoint::default();
+
+        // A taker order matching three maker orders, two of them of the same maker
+        let first = stored_fill(market, 1, 10, 100, 1);
+        let second = stored_fill(market, 1, 10, 101, 2);
+        let third = StoredFill {
+            maker_slot: 1,
+            price: 102,
+            quantity: 3,
+            ..first.clone()
+        };
+        let fills = [
+            first.clone(),
+            second,
+            third,
+            stored_fill(market, 2, 11, 103, 4),
+        ];
+        assert_eq!(store.commit(&market, &fills, &checkpoint).unwrap(), 4);
+
+        // A transaction seen again only stores what's new
+        let mut again = fills.to_vec();
+        again.push(stored_fill(market, 3, 12, 104, 5));
+        assert_eq!(store.commit(&market, &again, &checkpoint).unwrap(), 1);
+
+        // Sequence numbers are per market
+        let other = [StoredFill {
+            market: other_market,
+            ..first
+        }];
+        assert_eq!(store.commit(&other_market, &other, &checkpoint).unwrap(), 1);
+
+        let stored = store.fills(&market, 0, u64::MAX / 2).unwrap();
+        let stored: Vec<_> = stored
+            .iter()
+            .map(|f| (f.seq_num, f.maker_slot, f.price, f.quantity))
+            .collect();
+        assert_eq!(
+            stored,
+            vec![
+                (1, 0, 100, 1),
+                (1, 0, 101, 2),
+                (1, 1, 102, 3),
+                (2, 0, 103, 4),
+                (3, 0, 104, 5)
+            ]
+        );
+
+        // The timestamp range includes its start only
+        let stored = store.fills(&market, 11, 12).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].seq_num, 2);
+        assert_eq!(stored[0].signature, fills[3].signature);
+    }
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let mut store = TradeStore::open_in_memory().unwrap();
+        let market = Pubkey::new_unique();
+        assert_eq!(store.checkpoint(&market).unwrap(), Checkpoint::default());
+
+        let in_progress = Checkpoint {
+            until: Some(Signature::new_unique()),
+            run_newest: Some(Signature::new_unique()),
+            cursor: Some(Signature::new_unique()),
+        };
+        store.commit(&market, &[], &in_progress).unwrap();
+        assert_eq!(store.checkpoint(&market).unwrap(), in_progress);
+
+        let complete = Checkpoint {
+            until: in_progress.run_newest,
+            run_newest: None,
+            cursor: None,
+        };
+        store.commit(&market, &[], &complete).unwrap();
+        assert_eq!(store.checkpoint(&market).unwrap(), complete);
+        assert_eq!(
+            store.checkpoint(&Pubkey::new_unique()).unwrap(),
+            Checkpoint::default()
+        );
+    }
+}
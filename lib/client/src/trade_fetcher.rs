@@ -4,17 +4,23 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use openbook_v2::state::{EventHeap, Market, FillEvent, EventType, AnyEvent};
 use anchor_lang::AccountDeserialize;
+use serde::Serialize;
+use crate::report::as_string;
 
 /// Struct to hold the information about a fetched fill event
+#[derive(Debug, Serialize)]
 pub struct FillEventInfo {
+    #[serde(serialize_with = "as_string")]
     pub market_pubkey: Pubkey,
     pub taker_side: u8,
     pub maker_out: u8,
     pub maker_slot: u8,
     pub timestamp: u64,
     pub market_seq_num: u64,
+    #[serde(serialize_with = "as_string")]
     pub maker: Pubkey,
     pub maker_timestamp: u64,
+    #[serde(serialize_with = "as_string")]
     pub taker: Pubkey,
     pub taker_client_order_id: u64,
     pub price: i64,
@@ -23,6 +29,27 @@ pub struct FillEventInfo {
     pub maker_client_order_id: u64,
 }
 
+impl FillEventInfo {
+    pub fn new(market_pubkey: Pubkey, fill_event: &FillEvent) -> Self {
+        FillEventInfo {
+            market_pubkey,
+            taker_side: fill_event.taker_side,
+            maker_out: fill_event.maker_out as u8,
+            maker_slot: fill_event.maker_slot,
+            timestamp: fill_event.timestamp,
+            market_seq_num: fill_event.market_seq_num,
+            maker: fill_event.maker,
+            maker_timestamp: fill_event.maker_timestamp,
+            taker: fill_event.taker,
+            taker_client_order_id: fill_event.taker_client_order_id,
+            price: fill_event.price,
+            peg_limit: fill_event.peg_limit,
+            quantity: fill_event.quantity,
+            maker_client_order_id: fill_event.maker_client_order_id,
+        }
+    }
+}
+
 /// Fetch all fill events (matched trades) for a given market
 pub async fn fetch_fill_events(client: &RpcClient, market_pubkey: Pubkey) -> Result<Vec<FillEventInfo>> {
     // Log the market pubkey
@@ -77,23 +104,13 @@ pub async fn fetch_fill_events(client: &RpcClient, market_pubkey: Pubkey) -> Res
         if let Some(fill_event) = event.as_fill_event() {
             info!("Found FillEvent: price = {}, quantity = {}", fill_event.price, fill_event.quantity);
 
+            // The ask's event of an auction match mirrors the bid's one
+            if fill_event.is_auction_mirror() {
+                continue;
+            }
+
             if fill_event.price != 0 && fill_event.quantity != 0 && fill_event.maker != Pubkey::default() && fill_event.taker != Pubkey::default() {
-                let fill_info = FillEventInfo {
-                    market_pubkey,
-                    taker_side: fill_event.taker_side,  
-                    maker_out: fill_event.maker_out as u8,  
-                    maker_slot: fill_event.maker_slot,  
-                    timestamp: fill_event.timestamp,  
-                    market_seq_num: fill_event.market_seq_num,  
-                    maker: fill_event.maker,  
-                    maker_timestamp: fill_event.maker_timestamp,  
-                    taker: fill_event.taker,  
-                    taker_client_order_id: fill_event.taker_client_order_id,  
-                    price: fill_event.price,  
-                    peg_limit: fill_event.peg_limit,  
-                    quantity: fill_event.quantity,  
-                    maker_client_order_id: fill_event.maker_client_order_id,  
-                };
+                let fill_info = FillEventInfo::new(market_pubkey, &fill_event);
 
                 fill_events.push(fill_info);
             }
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::stream::{select_all, BoxStream};
+use futures::StreamExt;
+use log::*;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_client::rpc_response::SlotUpdate;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::account_update_stream::{AccountUpdate, Message};
+
+pub struct Config {
+    pub rpc_http_url: String,
+    pub rpc_ws_url: String,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Sends a snapshot of `accounts` fetched over http, then their updates and the
+/// slot updates until a subscription ends
+async fn feed_data(
+    config: &Config,
+    accounts: &[Pubkey],
+    sender: &async_channel::Sender<Message>,
+) -> anyhow::Result<()> {
+    let client = PubsubClient::new(&config.rpc_ws_url).await?;
+    let account_info_config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::processed()),
+        data_slice: None,
+        min_context_slot: None,
+    };
+
+    let mut streams: Vec<BoxStream<anyhow::Result<Vec<Message>>>> = vec![];
+    for &pubkey in accounts {
+        let (stream, _unsubscribe) = client
+            .account_subscribe(&pubkey, Some(account_info_config.clone()))
+            .await?;
+        streams.push(
+            stream
+                .map(move |response| {
+                    let account = response
+                        .value
+                        .decode()
+                        .ok_or_else(|| anyhow::anyhow!("could not decode account"))?;
+                    Ok(vec![Message::Account(AccountUpdate {
+                        pubkey,
+                        slot: response.context.slot,
+                        account,
+                    })])
+                })
+                .boxed(),
+        );
+    }
+    // slotSubscribe is more widely available than slotsUpdatesSubscribe, it carries
+    // the parent and the newest root of each new slot
+    let (slots, _unsubscribe) = client.slot_subscribe().await?;
+    streams.push(
+        slots
+            .map(|info| {
+                let timestamp = now_ms();
+                Ok(vec![
+                    Message::Slot(Arc::new(SlotUpdate::CreatedBank {
+                        slot: info.slot,
+                        parent: info.parent,
+                        timestamp,
+                    })),
+                    Message::Slot(Arc::new(SlotUpdate::Root {
+                        slot: info.root,
+                        timestamp,
+                    })),
+                ])
+            })
+            .boxed(),
+    );
+
+    // Subscribed before the snapshot, so no write falls between the two
+    let rpc_client =
+        RpcClient::new_with_commitment(config.rpc_http_url.clone(), CommitmentConfig::processed());
+    let mut snapshot = vec![];
+    for keys in accounts.chunks(100) {
+        let response = rpc_client
+            .get_multiple_accounts_with_commitment(keys, rpc_client.commitment())
+            .await?;
+        for (&pubkey, account) in keys.iter().zip(response.value) {
+            if let Some(account) = account {
+                snapshot.push(AccountUpdate {
+                    pubkey,
+                    slot: response.context.slot,
+                    account: account.into(),
+                });
+            }
+        }
+    }
+    sender.send(Message::Snapshot(snapshot)).await?;
+
+    let mut updates = select_all(streams);
+    while let Some(messages) = updates.next().await {
+        for message in messages? {
+            sender.send(message).await?;
+        }
+    }
+    anyhow::bail!("websocket subscription ended")
+}
+
+/// Streams the writes to `accounts` and the slot updates, reconnecting and sending
+/// a new snapshot when the websocket connection fails
+pub fn start(config: Config, accounts: Vec<Pubkey>, sender: async_channel::Sender<Message>) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = feed_data(&config, &accounts, &sender).await {
+                warn!("websocket error: {:?}", err);
+            }
+            if sender.is_closed() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    });
+}
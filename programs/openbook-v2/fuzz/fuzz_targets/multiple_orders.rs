@@ -451,6 +451,8 @@ mod error_parser {
             e if e == OpenBookError::InvalidInputNameLength.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputMarketExpired.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputMarketFees.into() => Corpus::Reject,
+            e if e == OpenBookError::InvalidInputMarketLimits.into() => Corpus::Reject,
+            e if e == OpenBookError::InvalidInputAuction.into() => Corpus::Reject,
             _ => panic!("{}", err),
         }
     }
@@ -480,6 +482,10 @@ mod error_parser {
             e if e == OpenBookError::OpenOrdersFull.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
+            e if e == OpenBookError::WouldNotFillExactOut.into() => Corpus::Keep,
+            e if e == OpenBookError::InvalidInputTickSize.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderBelowMinimumSize.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
             _ => panic!("{}", err),
         }
@@ -490,6 +496,7 @@ mod error_parser {
             e if e == OpenBookError::InvalidInputLots.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputLotsSize.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputPegLimit.into() => Corpus::Reject,
+            e if e == OpenBookError::InvalidInputPegOffset.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidInputPriceLots.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidOraclePrice.into() => Corpus::Keep,
             e if e == OpenBookError::InvalidOrderPostIOC.into() => Corpus::Keep,
@@ -500,6 +507,8 @@ mod error_parser {
             e if e == OpenBookError::OraclePegInvalidOracleState.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderBelowMinimumSize.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
             _ => panic!("{}", err),
         }
@@ -513,6 +522,9 @@ mod error_parser {
             e if e == OpenBookError::InvalidInputPriceLots.into() => Corpus::Reject,
             e if e == OpenBookError::InvalidOraclePrice.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
+            e if e == OpenBookError::WouldNotFillExactOut.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderBelowMinimumSize.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
             _ => panic!("{}", err),
         }
@@ -548,6 +560,10 @@ mod error_parser {
             e if e == OpenBookError::OpenOrdersFull.into() => Corpus::Keep,
             e if e == OpenBookError::WouldSelfTrade.into() => Corpus::Keep,
             e if e == OpenBookError::WouldExecutePartially.into() => Corpus::Keep,
+            e if e == OpenBookError::WouldNotFillExactOut.into() => Corpus::Keep,
+            e if e == OpenBookError::InvalidInputTickSize.into() => Corpus::Keep,
+            e if e == OpenBookError::OrderBelowMinimumSize.into() => Corpus::Keep,
+            e if e == OpenBookError::MarketInAuction.into() => Corpus::Keep,
             e if e == TokenError::InsufficientFunds.into() => Corpus::Keep,
             _ => panic!("{}", err),
         }
@@ -324,13 +324,15 @@ impl FuzzContext {
 
         let accounts = openbook_v2::accounts::Deposit {
             owner: user.owner,
-            user_base_account: user.base_vault,
-            user_quote_account: user.quote_vault,
+            user_base_account: Some(user.base_vault),
+            user_quote_account: Some(user.quote_vault),
             open_orders_account: user.open_orders,
             market: self.market,
             market_base_vault: self.market_base_vault,
             market_quote_vault: self.market_quote_vault,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
         };
 
         process_instruction(&mut self.state, data, &accounts, &[])
@@ -345,13 +347,15 @@ impl FuzzContext {
 
         let accounts = openbook_v2::accounts::Deposit {
             owner: user.owner,
-            user_base_account: user.base_vault,
-            user_quote_account: user.quote_vault,
+            user_base_account: Some(user.base_vault),
+            user_quote_account: Some(user.quote_vault),
             open_orders_account: user.open_orders,
             market: self.market,
             market_base_vault: self.market_base_vault,
             market_quote_vault: self.market_quote_vault,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
         };
 
         process_instruction(&mut self.state, data, &accounts, &[])
@@ -377,7 +381,7 @@ impl FuzzContext {
         let accounts = openbook_v2::accounts::PlaceOrder {
             open_orders_account: user.open_orders,
             signer: user.owner,
-            user_token_account,
+            user_token_account: Some(user_token_account),
             open_orders_admin: None,
             market: self.market,
             bids: self.bids,
@@ -387,6 +391,9 @@ impl FuzzContext {
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -429,7 +436,7 @@ impl FuzzContext {
         let accounts = openbook_v2::accounts::PlaceOrder {
             open_orders_account: user.open_orders,
             signer: user.owner,
-            user_token_account,
+            user_token_account: Some(user_token_account),
             open_orders_admin: None,
             market: self.market,
             bids: self.bids,
@@ -439,6 +446,9 @@ impl FuzzContext {
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -468,8 +478,8 @@ impl FuzzContext {
         let accounts = openbook_v2::accounts::PlaceTakeOrder {
             signer: user.owner,
             penalty_payer: user.owner,
-            user_base_account: user.base_vault,
-            user_quote_account: user.quote_vault,
+            user_base_account: Some(user.base_vault),
+            user_quote_account: Some(user.quote_vault),
             market: self.market,
             market_authority: self.market_authority,
             bids: self.bids,
@@ -482,6 +492,9 @@ impl FuzzContext {
             token_program: spl_token::ID,
             system_program: system_program::ID,
             open_orders_admin: None,
+            native_mint: None,
+            unwrap_account: None,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -520,7 +533,7 @@ impl FuzzContext {
         let accounts = openbook_v2::accounts::PlaceOrder {
             open_orders_account: user.open_orders,
             signer: user.owner,
-            user_token_account,
+            user_token_account: Some(user_token_account),
             open_orders_admin: None,
             market: self.market,
             bids: self.bids,
@@ -530,6 +543,9 @@ impl FuzzContext {
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -572,7 +588,7 @@ impl FuzzContext {
         let accounts = openbook_v2::accounts::PlaceOrder {
             open_orders_account: user.open_orders,
             signer: user.owner,
-            user_token_account,
+            user_token_account: Some(user_token_account),
             open_orders_admin: None,
             market: self.market,
             bids: self.bids,
@@ -582,6 +598,9 @@ impl FuzzContext {
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             token_program: spl_token::ID,
+            native_payer: None,
+            system_program: None,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -623,6 +642,7 @@ impl FuzzContext {
             oracle_a: self.oracle_a,
             oracle_b: self.oracle_b,
             token_program: spl_token::ID,
+            market_stats: None,
         };
 
         let remaining = makers.map_or_else(Vec::new, |makers| {
@@ -764,8 +784,8 @@ impl FuzzContext {
             owner: user.owner,
             penalty_payer: user.owner,
             open_orders_account: user.open_orders,
-            user_base_account: user.base_vault,
-            user_quote_account: user.quote_vault,
+            user_base_account: Some(user.base_vault),
+            user_quote_account: Some(user.quote_vault),
             market: self.market,
             market_authority: self.market_authority,
             market_base_vault: self.market_base_vault,
@@ -773,6 +793,8 @@ impl FuzzContext {
             token_program: spl_token::ID,
             system_program: system_program::ID,
             referrer_account,
+            native_mint: None,
+            unwrap_account: None,
         };
 
         process_instruction(&mut self.state, data, &accounts, &[])
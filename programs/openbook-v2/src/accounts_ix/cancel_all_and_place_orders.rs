@@ -36,7 +36,8 @@ pub struct CancelAllAndPlaceOrders<'info> {
         has_one = market_quote_vault,
         constraint = market.load()?.oracle_a == oracle_a.non_zero_key(),
         constraint = market.load()?.oracle_b == oracle_b.non_zero_key(),
-        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin
+        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin,
+        constraint = !market.load()?.has_market_stats() || market_stats.is_some() @ OpenBookError::MissingMarketStats
     )]
     pub market: AccountLoader<'info, Market>,
     #[account(mut)]
@@ -57,4 +58,8 @@ pub struct CancelAllAndPlaceOrders<'info> {
     pub oracle_b: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Needed once the market has market stats
+    #[account(mut, has_one = market)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
 }
@@ -13,7 +13,8 @@ pub struct CloseMarket<'info> {
         has_one = event_heap,
         close = sol_destination,
         constraint = market.load()?.close_market_admin.is_some() @ OpenBookError::NoCloseMarketAdmin,
-        constraint = market.load()?.close_market_admin == close_market_admin.key() @ OpenBookError::InvalidCloseMarketAdmin
+        constraint = market.load()?.close_market_admin == close_market_admin.key() @ OpenBookError::InvalidCloseMarketAdmin,
+        constraint = !market.load()?.has_market_stats() || market_stats.is_some() @ OpenBookError::MissingMarketStats
     )]
     pub market: AccountLoader<'info, Market>,
 
@@ -40,4 +41,12 @@ pub struct CloseMarket<'info> {
     pub sol_destination: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Closed with the market once it has market stats
+    #[account(
+        mut,
+        has_one = market,
+        close = sol_destination
+    )]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
 }
@@ -0,0 +1,25 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateTwapOrder<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub signer: Signer<'info>,
+    #[account(
+        has_one = market,
+        constraint = open_orders_account.load()?.is_owner_or_delegate(signer.key()) @ OpenBookError::NoOwnerOrDelegate
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    #[account(
+        init,
+        seeds = [b"TwapOrder".as_ref(), open_orders_account.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TwapOrder>(),
+    )]
+    pub twap_order: AccountLoader<'info, TwapOrder>,
+    pub market: AccountLoader<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
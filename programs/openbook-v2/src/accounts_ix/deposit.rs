@@ -9,12 +9,12 @@ pub struct Deposit<'info> {
         mut,
         token::mint = market_base_vault.mint
     )]
-    pub user_base_account: Account<'info, TokenAccount>,
+    pub user_base_account: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
         token::mint = market_quote_vault.mint
     )]
-    pub user_quote_account: Account<'info, TokenAccount>,
+    pub user_quote_account: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
         has_one = market,
@@ -33,4 +33,9 @@ pub struct Deposit<'info> {
     pub market_quote_vault: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Wraps its lamports for a native mint side without user token account
+    #[account(mut)]
+    pub native_payer: Option<Signer<'info>>,
+    pub system_program: Option<Program<'info, System>>,
 }
@@ -20,7 +20,7 @@ pub struct PlaceOrder<'info> {
         mut,
         token::mint = market_vault.mint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
 
     #[account(
         mut,
@@ -29,7 +29,8 @@ pub struct PlaceOrder<'info> {
         has_one = event_heap,
         constraint = market.load()?.oracle_a == oracle_a.non_zero_key(),
         constraint = market.load()?.oracle_b == oracle_b.non_zero_key(),
-        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin
+        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin,
+        constraint = !market.load()?.has_market_stats() || market_stats.is_some() @ OpenBookError::MissingMarketStats
     )]
     pub market: AccountLoader<'info, Market>,
     #[account(mut)]
@@ -51,6 +52,15 @@ pub struct PlaceOrder<'info> {
     pub oracle_b: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Wraps its lamports for a native mint vault without user token account
+    #[account(mut)]
+    pub native_payer: Option<Signer<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+
+    /// Needed once the market has market stats
+    #[account(mut, has_one = market)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
 }
 
 impl<'info> PlaceOrder<'info> {
@@ -21,7 +21,8 @@ pub struct PlaceTakeOrder<'info> {
         has_one = market_authority,
         constraint = market.load()?.oracle_a == oracle_a.non_zero_key(),
         constraint = market.load()?.oracle_b == oracle_b.non_zero_key(),
-        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin
+        constraint = market.load()?.open_orders_admin == open_orders_admin.non_zero_key() @ OpenBookError::InvalidOpenOrdersAdmin,
+        constraint = !market.load()?.has_market_stats() || market_stats.is_some() @ OpenBookError::MissingMarketStats
     )]
     pub market: AccountLoader<'info, Market>,
     /// CHECK: checked on has_one in market
@@ -41,12 +42,12 @@ pub struct PlaceTakeOrder<'info> {
         mut,
         token::mint = market_base_vault.mint
     )]
-    pub user_base_account: Box<Account<'info, TokenAccount>>,
+    pub user_base_account: Option<Box<Account<'info, TokenAccount>>>,
     #[account(
         mut,
         token::mint = market_quote_vault.mint
     )]
-    pub user_quote_account: Box<Account<'info, TokenAccount>>,
+    pub user_quote_account: Option<Box<Account<'info, TokenAccount>>>,
 
     /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
     pub oracle_a: Option<UncheckedAccount<'info>>,
@@ -56,4 +57,15 @@ pub struct PlaceTakeOrder<'info> {
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub open_orders_admin: Option<Signer<'info>>,
+
+    /// CHECK: Only needed to unwrap native SOL to the signer, the address is checked
+    #[account(address = anchor_spl::token::spl_token::native_mint::ID)]
+    pub native_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: Temporary token account to unwrap native SOL, its address is checked in the ix
+    #[account(mut)]
+    pub unwrap_account: Option<UncheckedAccount<'info>>,
+
+    /// Needed once the market has market stats
+    #[account(mut, has_one = market)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
 }
@@ -0,0 +1,100 @@
+use crate::error::OpenBookError;
+use crate::pubkey_option::NonZeroKey;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RouteTakeOrder<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub penalty_payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = first_market.load()?.bids == first_bids.key(),
+        constraint = first_market.load()?.asks == first_asks.key(),
+        constraint = first_market.load()?.event_heap == first_event_heap.key(),
+        constraint = first_market.load()?.market_base_vault == first_market_base_vault.key(),
+        constraint = first_market.load()?.market_quote_vault == first_market_quote_vault.key(),
+        constraint = first_market.load()?.market_authority == first_market_authority.key(),
+        constraint = first_market.load()?.oracle_a == first_oracle_a.non_zero_key(),
+        constraint = first_market.load()?.oracle_b == first_oracle_b.non_zero_key(),
+        constraint = first_market.load()?.open_orders_admin.is_none() @ OpenBookError::InvalidOpenOrdersAdmin,
+        constraint = !first_market.load()?.has_market_stats() || first_market_stats.is_some() @ OpenBookError::MissingMarketStats
+    )]
+    pub first_market: AccountLoader<'info, Market>,
+    /// CHECK: checked by a constraint on first_market
+    pub first_market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub first_bids: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub first_asks: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub first_market_base_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub first_market_quote_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub first_event_heap: AccountLoader<'info, EventHeap>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub first_oracle_a: Option<UncheckedAccount<'info>>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub first_oracle_b: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = second_market.key() != first_market.key(),
+        constraint = second_market.load()?.bids == second_bids.key(),
+        constraint = second_market.load()?.asks == second_asks.key(),
+        constraint = second_market.load()?.event_heap == second_event_heap.key(),
+        constraint = second_market.load()?.market_base_vault == second_market_base_vault.key(),
+        constraint = second_market.load()?.market_quote_vault == second_market_quote_vault.key(),
+        constraint = second_market.load()?.market_authority == second_market_authority.key(),
+        constraint = second_market.load()?.oracle_a == second_oracle_a.non_zero_key(),
+        constraint = second_market.load()?.oracle_b == second_oracle_b.non_zero_key(),
+        constraint = second_market.load()?.open_orders_admin.is_none() @ OpenBookError::InvalidOpenOrdersAdmin,
+        constraint = !second_market.load()?.has_market_stats() || second_market_stats.is_some() @ OpenBookError::MissingMarketStats
+    )]
+    pub second_market: AccountLoader<'info, Market>,
+    /// CHECK: checked by a constraint on second_market
+    pub second_market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub second_bids: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub second_asks: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub second_market_base_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub second_market_quote_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub second_event_heap: AccountLoader<'info, EventHeap>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub second_oracle_a: Option<UncheckedAccount<'info>>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub second_oracle_b: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub user_input_account: Box<Account<'info, TokenAccount>>,
+    /// Receives the part of the first leg's output that the second leg could not use
+    #[account(mut)]
+    pub user_intermediate_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub user_output_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// Needed once the first market has market stats
+    #[account(
+        mut,
+        constraint = first_market_stats.load()?.market == first_market.key()
+    )]
+    pub first_market_stats: Option<AccountLoader<'info, MarketStats>>,
+    /// Needed once the second market has market stats
+    #[account(
+        mut,
+        constraint = second_market_stats.load()?.market == second_market.key()
+    )]
+    pub second_market_stats: Option<AccountLoader<'info, MarketStats>>,
+}
@@ -33,13 +33,13 @@ pub struct SettleFunds<'info> {
         token::mint = market_base_vault.mint,
         constraint = open_orders_account.load()?.is_settle_destination_allowed(owner.key(), user_base_account.owner)
     )]
-    pub user_base_account: Account<'info, TokenAccount>,
+    pub user_base_account: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
         token::mint = market_quote_vault.mint,
         constraint = open_orders_account.load()?.is_settle_destination_allowed(owner.key(), user_quote_account.owner)
     )]
-    pub user_quote_account: Account<'info, TokenAccount>,
+    pub user_quote_account: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
         token::mint = market_quote_vault.mint
@@ -47,4 +47,11 @@ pub struct SettleFunds<'info> {
     pub referrer_account: Option<Box<Account<'info, TokenAccount>>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Only needed to unwrap native SOL to the owner, the address is checked
+    #[account(address = anchor_spl::token::spl_token::native_mint::ID)]
+    pub native_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: Temporary token account to unwrap native SOL, its address is checked in the ix
+    #[account(mut)]
+    pub unwrap_account: Option<UncheckedAccount<'info>>,
 }
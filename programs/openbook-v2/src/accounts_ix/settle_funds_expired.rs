@@ -62,11 +62,13 @@ impl<'info> SettleFundsExpired<'info> {
             market_authority: self.market_authority.clone(),
             market_base_vault: self.market_base_vault.clone(),
             market_quote_vault: self.market_quote_vault.clone(),
-            user_base_account: self.user_base_account.clone(),
-            user_quote_account: self.user_quote_account.clone(),
+            user_base_account: Some(self.user_base_account.clone()),
+            user_quote_account: Some(self.user_quote_account.clone()),
             referrer_account: self.referrer_account.clone(),
             token_program: self.token_program.clone(),
             system_program: self.system_program.clone(),
+            native_mint: None,
+            unwrap_account: None,
         }
     }
 }
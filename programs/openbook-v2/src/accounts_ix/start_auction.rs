@@ -0,0 +1,14 @@
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StartAuction<'info> {
+    pub close_market_admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = market.load()?.close_market_admin.is_some() @ OpenBookError::NoCloseMarketAdmin,
+        constraint = market.load()?.close_market_admin == close_market_admin.key() @ OpenBookError::InvalidCloseMarketAdmin
+    )]
+    pub market: AccountLoader<'info, Market>,
+}
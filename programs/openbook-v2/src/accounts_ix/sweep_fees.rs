@@ -23,4 +23,16 @@ pub struct SweepFees<'info> {
     )]
     pub token_receiver_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+
+    /// Only needed to sweep the base fees of markets with fees in base
+    #[account(
+        mut,
+        constraint = market.load()?.market_base_vault == market_base_vault.key()
+    )]
+    pub market_base_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = market.load()?.base_mint == base_token_receiver_account.mint
+    )]
+    pub base_token_receiver_account: Option<Account<'info, TokenAccount>>,
 }
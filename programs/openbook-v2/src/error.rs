@@ -95,6 +95,48 @@ pub enum OpenBookError {
     NonEmptyOpenOrdersPosition,
     #[msg("Fill-Or-Kill order would generate a partial execution")]
     WouldExecutePartially,
+    #[msg("Peg offset should be given either in lots or in bps")]
+    InvalidInputPegOffset,
+    #[msg("Market minimum order size and tick size cannot be negative")]
+    InvalidInputMarketLimits,
+    #[msg("Order size is below the market minimum")]
+    OrderBelowMinimumSize,
+    #[msg("Order price is not a multiple of the market tick size")]
+    InvalidInputTickSize,
+    #[msg("Exact-out order would not deliver the full output amount")]
+    WouldNotFillExactOut,
+    #[msg("Route input, intermediate and output mints do not match the markets")]
+    InvalidInputRouteMints,
+    #[msg("Route output is below the requested minimum")]
+    RouteBelowMinimumOutput,
+    #[msg("Auction end must be in the future and maker fees can't be negative")]
+    InvalidInputAuction,
+    #[msg("Orders can't be matched while the market is in auction")]
+    MarketInAuction,
+    #[msg("Market is not in auction")]
+    MarketNotInAuction,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Native SOL transfers without a token account need a native mint vault and the native accounts")]
+    InvalidInputNativeTransfer,
+    #[msg("Pool vault oracles need the base vault as oracle a and the quote vault as oracle b, of the pool passed as the first remaining account")]
+    InvalidInputVaultOracle,
+    #[msg("Book oracles need the bids and asks of a market with the same mints and lot sizes, passed as the first remaining account")]
+    InvalidInputBookOracle,
+    #[msg("TWAP total size, slice size and interval should be greater than zero")]
+    InvalidInputTwap,
+    #[msg("The next slice of the TWAP order is not due yet")]
+    TwapSliceNotDue,
+    #[msg("All slices of the TWAP order were executed or skipped")]
+    TwapOrderCompleted,
+    #[msg("Pool oracles need to price the base mint in the quote mint and a max twap deviation")]
+    InvalidInputPoolOracle,
+    #[msg("The market has market stats, which need to be passed")]
+    MissingMarketStats,
+    #[msg("OpenBook market and book side oracles need a max twap deviation")]
+    InvalidInputMarketOracle,
+    #[msg("Reduce-only orders can only use the free balance, including taker fees in base")]
+    ReduceOnlyExceedsFreeBalance,
 }
 
 impl From<OpenBookError> for ProgramError {
@@ -30,6 +30,12 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
         asks: ctx.accounts.asks.load_mut()?,
     };
     let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut market_stats = ctx
+        .accounts
+        .market_stats
+        .as_ref()
+        .map(|stats| stats.load_mut())
+        .transpose()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -37,6 +43,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        now_ts,
         clock.slot,
     )?;
 
@@ -76,6 +83,20 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             }
         }
 
+        if order.reduce_only {
+            let position = &open_orders_account.position;
+            order.cap_to_free_balance(
+                &market,
+                position.base_free_native.saturating_sub(base_amount),
+                position.quote_free_native.saturating_sub(quote_amount),
+            );
+            if order.max_base_lots == 0 || order.max_quote_lots_including_fees == 0 {
+                msg!("Reduce-only order has no free balance to use");
+                order_ids.push(None);
+                continue;
+            }
+        }
+
         let OrderWithAmounts {
             order_id,
             total_base_taken_native,
@@ -83,6 +104,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             posted_base_native,
             posted_quote_native,
             taker_fees,
+            taker_fees_base,
             maker_fees,
             ..
         } = book.new_order(
@@ -90,6 +112,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             &mut market,
             &ctx.accounts.market.key(),
             &mut event_heap,
+            market_stats.as_deref_mut(),
             oracle_price_lots,
             Some(&mut open_orders_account),
             &open_orders_account_pk,
@@ -98,17 +121,34 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             ctx.remaining_accounts,
         )?;
 
+        let position = &open_orders_account.position;
         match order.side {
             Side::Bid => {
+                let order_quote =
+                    total_quote_taken_native + posted_quote_native + taker_fees + maker_fees;
+                if order.reduce_only {
+                    require_gte!(
+                        position.quote_free_native.saturating_sub(quote_amount),
+                        order_quote,
+                        OpenBookError::ReduceOnlyExceedsFreeBalance
+                    );
+                }
                 quote_amount = quote_amount
-                    .checked_add(
-                        total_quote_taken_native + posted_quote_native + taker_fees + maker_fees,
-                    )
+                    .checked_add(order_quote)
                     .ok_or(OpenBookError::InvalidInputOrdersAmounts)?;
             }
             Side::Ask => {
+                // Reduce-only orders never pull from the wallet, taker fees in base included
+                let order_base = total_base_taken_native + posted_base_native + taker_fees_base;
+                if order.reduce_only {
+                    require_gte!(
+                        position.base_free_native.saturating_sub(base_amount),
+                        order_base,
+                        OpenBookError::ReduceOnlyExceedsFreeBalance
+                    );
+                }
                 base_amount = base_amount
-                    .checked_add(total_base_taken_native + posted_base_native)
+                    .checked_add(order_base)
                     .ok_or(OpenBookError::InvalidInputOrdersAmounts)?;
             }
         };
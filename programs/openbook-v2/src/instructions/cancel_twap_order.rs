@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+
+pub fn cancel_twap_order(_ctx: Context<CancelTwapOrder>) -> Result<()> {
+    // The account, with the keeper fees of the slices left, is closed by anchor
+    Ok(())
+}
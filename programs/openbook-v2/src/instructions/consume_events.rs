@@ -7,9 +7,6 @@ use crate::state::*;
 
 use crate::accounts_ix::*;
 
-// Max events to consume per ix.
-pub const MAX_EVENTS_CONSUME: usize = 8;
-
 /// Load a open_orders account by key from the list of account infos.
 ///
 /// Message and return Ok() if it's missing, to lock in successful processing
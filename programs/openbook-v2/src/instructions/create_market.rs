@@ -18,6 +18,11 @@ pub fn create_market(
     maker_fee: i64,
     taker_fee: i64,
     time_expiry: i64,
+    min_base_lots: i64,
+    min_quote_lots: i64,
+    tick_size_lots: i64,
+    auction_end_ts: i64,
+    fees_in_base: bool,
 ) -> Result<()> {
     let registration_time = Clock::get()?.unix_timestamp;
 
@@ -33,6 +38,11 @@ pub fn create_market(
         taker_fee >= 0 && (maker_fee >= 0 || maker_fee.abs() <= taker_fee),
         OpenBookError::InvalidInputMarketFees
     );
+    // Makers can only get rebates in base, the fees of their bids are locked in quote
+    require!(
+        !fees_in_base || maker_fee <= 0,
+        OpenBookError::InvalidInputMarketFees
+    );
 
     require!(
         time_expiry == 0 || time_expiry > Clock::get()?.unix_timestamp,
@@ -42,23 +52,105 @@ pub fn create_market(
     require_gt!(quote_lot_size, 0, OpenBookError::InvalidInputLots);
     require_gt!(base_lot_size, 0, OpenBookError::InvalidInputLots);
 
+    require_gte!(min_base_lots, 0, OpenBookError::InvalidInputMarketLimits);
+    require_gte!(min_quote_lots, 0, OpenBookError::InvalidInputMarketLimits);
+    require_gte!(tick_size_lots, 0, OpenBookError::InvalidInputMarketLimits);
+
+    require!(
+        auction_end_ts == 0 || (auction_end_ts > registration_time && maker_fee >= 0),
+        OpenBookError::InvalidInputAuction
+    );
+
     let oracle_a = ctx.accounts.oracle_a.non_zero_key();
     let oracle_b = ctx.accounts.oracle_b.non_zero_key();
 
-    if oracle_a.is_some() && oracle_b.is_some() {
+    let oracle_type = if oracle_a.is_some() && oracle_b.is_some() {
         let oracle_a = AccountInfoRef::borrow(ctx.accounts.oracle_a.as_ref().unwrap())?;
         let oracle_b = AccountInfoRef::borrow(ctx.accounts.oracle_b.as_ref().unwrap())?;
 
         require_keys_neq!(*oracle_a.key, *oracle_b.key);
+        let oracle_type = oracle::determine_oracle_type(&oracle_a)?;
         require!(
-            oracle::determine_oracle_type(&oracle_a)? == oracle::determine_oracle_type(&oracle_b)?,
+            oracle_type == oracle::determine_oracle_type(&oracle_b)?,
             OpenBookError::InvalidOracleTypes
         );
+        match oracle_type {
+            OracleType::OpenBookBookSide => validate_book_oracle(
+                &ctx,
+                oracle_a.key,
+                oracle_b.key,
+                quote_lot_size,
+                base_lot_size,
+            )?,
+            OracleType::RaydiumCPMM => {
+                let pool = ctx
+                    .remaining_accounts
+                    .first()
+                    .ok_or(OpenBookError::InvalidInputVaultOracle)?;
+                oracle::validate_vault_oracles(
+                    &oracle_a,
+                    &oracle_b,
+                    &AccountInfoRef::borrow(pool)?,
+                    &ctx.accounts.base_mint.key(),
+                    &ctx.accounts.quote_mint.key(),
+                )?;
+            }
+            OracleType::OrcaWhirlpool => oracle::validate_whirlpool_oracles(
+                &oracle_a,
+                Some(&oracle_b),
+                &ctx.accounts.base_mint.key(),
+                &ctx.accounts.quote_mint.key(),
+            )?,
+            _ => {}
+        }
+        Some(oracle_type)
     } else if oracle_a.is_some() {
         let oracle_a = AccountInfoRef::borrow(ctx.accounts.oracle_a.as_ref().unwrap())?;
-        oracle::determine_oracle_type(&oracle_a)?;
+        let oracle_type = oracle::determine_oracle_type(&oracle_a)?;
+        match oracle_type {
+            OracleType::RaydiumCPMM => return Err(OpenBookError::InvalidInputVaultOracle.into()),
+            OracleType::OpenBookBookSide => {
+                return Err(OpenBookError::InvalidInputBookOracle.into())
+            }
+            OracleType::OrcaWhirlpool => oracle::validate_whirlpool_oracles(
+                &oracle_a,
+                None,
+                &ctx.accounts.base_mint.key(),
+                &ctx.accounts.quote_mint.key(),
+            )?,
+            _ => {}
+        }
+        Some(oracle_type)
     } else if oracle_b.is_some() {
         return Err(OpenBookError::InvalidSecondOracle.into());
+    } else {
+        None
+    };
+
+    // Pool prices can be moved within one transaction, only the twap keeps them in check
+    if matches!(
+        oracle_type,
+        Some(OracleType::OrcaWhirlpool | OracleType::RaydiumCPMM)
+    ) {
+        require!(
+            oracle_config
+                .max_twap_deviation
+                .is_some_and(|deviation| deviation > 0.0),
+            OpenBookError::InvalidInputPoolOracle
+        );
+    }
+
+    // As can the fills and the book of another market, by trading on it
+    if matches!(
+        oracle_type,
+        Some(OracleType::OpenBookMarket | OracleType::OpenBookBookSide)
+    ) {
+        require!(
+            oracle_config
+                .max_twap_deviation
+                .is_some_and(|deviation| deviation > 0.0),
+            OpenBookError::InvalidInputMarketOracle
+        );
     }
 
     let mut openbook_market = ctx.accounts.market.load_init()?;
@@ -71,6 +163,8 @@ pub fn create_market(
         bump: ctx.bumps.market_authority,
         base_decimals: ctx.accounts.base_mint.decimals,
         quote_decimals: ctx.accounts.quote_mint.decimals,
+        fees_in_base: fees_in_base.into(),
+        has_market_stats: 0,
         padding1: Default::default(),
         time_expiry,
         name: fill_from_str(&name)?,
@@ -80,6 +174,8 @@ pub fn create_market(
         oracle_a,
         oracle_b,
         oracle_config: oracle_config.to_oracle_config(),
+        oracle_twap_price: 0f64,
+        oracle_twap_last_update_ts: 0,
         quote_lot_size,
         base_lot_size,
         seq_num: 0,
@@ -98,8 +194,16 @@ pub fn create_market(
         quote_deposit_total: 0,
         fees_available: 0,
         referrer_rebates_accrued: 0,
-
-        reserved: [0; 128],
+        min_base_lots,
+        min_quote_lots,
+        tick_size_lots,
+        auction_end_ts,
+        auction_price_lots: 0,
+        last_price_lots: 0,
+        last_trade_ts: 0,
+        fill_twap_price_lots: 0f64,
+        base_fees_available: 0,
+        reserved: [0; 56],
     };
 
     let mut orderbook = Orderbook {
@@ -124,3 +228,29 @@ pub fn create_market(
 
     Ok(())
 }
+
+/// Book oracles are the bids and asks of the market passed as the first remaining
+/// account. Its price lots are used as is, so it needs the same mints and lot sizes.
+fn validate_book_oracle(
+    ctx: &Context<CreateMarket>,
+    bids: &Pubkey,
+    asks: &Pubkey,
+    quote_lot_size: i64,
+    base_lot_size: i64,
+) -> Result<()> {
+    let market_ai = ctx
+        .remaining_accounts
+        .first()
+        .ok_or(OpenBookError::InvalidInputBookOracle)?;
+    let market = market_ai.load::<Market>()?;
+    require!(
+        market.bids == *bids
+            && market.asks == *asks
+            && market.base_mint == ctx.accounts.base_mint.key()
+            && market.quote_mint == ctx.accounts.quote_mint.key()
+            && market.quote_lot_size == quote_lot_size
+            && market.base_lot_size == base_lot_size,
+        OpenBookError::InvalidInputBookOracle
+    );
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use crate::accounts_ix::CreateMarketStats;
+use anchor_lang::prelude::*;
+
+pub fn create_market_stats(ctx: Context<CreateMarketStats>) -> Result<()> {
+    let mut market_stats = ctx.accounts.market_stats.load_init()?;
+    market_stats.market = ctx.accounts.market.key();
+    market_stats.bump = ctx.bumps.market_stats;
+
+    ctx.accounts.market.load_mut()?.has_market_stats = 1;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::token_utils::*;
+use crate::CreateTwapOrderArgs;
+
+pub fn create_twap_order(ctx: Context<CreateTwapOrder>, args: CreateTwapOrderArgs) -> Result<()> {
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+
+    // Each slice needs to be a valid order on its own
+    let market = ctx.accounts.market.load()?;
+    require!(
+        market.is_above_min_size(
+            args.slice_base_lots.min(args.total_base_lots),
+            args.limit_price_lots
+        ),
+        OpenBookError::OrderBelowMinimumSize
+    );
+
+    let mut twap_order = ctx.accounts.twap_order.load_init()?;
+    twap_order.open_orders_account = ctx.accounts.open_orders_account.key();
+    twap_order.market = ctx.accounts.market.key();
+    twap_order.bump = ctx.bumps.twap_order;
+    twap_order.side = args.side.into();
+    twap_order.limit_price_lots = args.limit_price_lots;
+    twap_order.total_base_lots = args.total_base_lots;
+    twap_order.slice_base_lots = args.slice_base_lots;
+    twap_order.remaining_base_lots = args.total_base_lots;
+    twap_order.filled_base_lots = 0;
+    twap_order.start_ts = args.start_ts.max(now_ts);
+    twap_order.interval_seconds = args.interval_seconds;
+    twap_order.next_slice = 0;
+    twap_order.keeper_fee_lamports = args.keeper_fee_lamports;
+
+    // Fund the keeper fees of all slices, the unused ones are returned on cancel
+    let keeper_fees = args
+        .keeper_fee_lamports
+        .checked_mul(twap_order.num_slices())
+        .ok_or(OpenBookError::InvalidInputTwap)?;
+    drop(twap_order);
+
+    system_program_transfer(
+        keeper_fees,
+        &ctx.accounts.system_program,
+        &ctx.accounts.payer,
+        &ctx.accounts.twap_order,
+    )
+}
@@ -12,22 +12,26 @@ pub fn deposit(ctx: Context<Deposit>, base_amount: u64, quote_amount: u64) -> Re
         OpenBookError::MarketHasExpired
     );
 
-    token_transfer(
+    token_or_native_transfer(
         base_amount,
         &ctx.accounts.token_program,
-        &ctx.accounts.user_base_account,
+        ctx.accounts.user_base_account.as_ref(),
         &ctx.accounts.market_base_vault,
         &ctx.accounts.owner,
+        ctx.accounts.native_payer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
     )?;
     open_orders_account.position.base_free_native += base_amount;
     market.base_deposit_total += base_amount;
 
-    token_transfer(
+    token_or_native_transfer(
         quote_amount,
         &ctx.accounts.token_program,
-        &ctx.accounts.user_quote_account,
+        ctx.accounts.user_quote_account.as_ref(),
         &ctx.accounts.market_quote_vault,
         &ctx.accounts.owner,
+        ctx.accounts.native_payer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
     )?;
     open_orders_account.position.quote_free_native += quote_amount;
     market.quote_deposit_total += quote_amount;
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::AccountInfoRef;
+use crate::error::*;
+use crate::state::*;
+
+pub fn execute_twap_slice<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ExecuteTwapSlice<'info>>,
+    limit: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let mut twap_order = ctx.accounts.twap_order.load_mut()?;
+    require_gt!(
+        twap_order.remaining_base_lots,
+        0,
+        OpenBookError::TwapOrderCompleted
+    );
+    let slice = twap_order
+        .current_slice(now_ts)
+        .filter(|slice| *slice >= twap_order.next_slice)
+        .ok_or(OpenBookError::TwapSliceNotDue)?;
+
+    let mut market = ctx.accounts.market.load_mut()?;
+    require!(
+        !market.is_expired(clock.unix_timestamp),
+        OpenBookError::MarketHasExpired
+    );
+
+    let min_base_lots = market.min_base_lots_at(twap_order.limit_price_lots);
+    let slice_base_lots = twap_order.take_slice(slice, min_base_lots);
+    if slice_base_lots == 0 {
+        msg!("The remaining slices of the TWAP order missed their window");
+        return Ok(());
+    }
+
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let open_orders_account_pk = ctx.accounts.open_orders_account.key();
+
+    let mut order = Order {
+        side: twap_order.side(),
+        max_base_lots: slice_base_lots,
+        max_quote_lots_including_fees: market.max_quote_lots(),
+        client_order_id: 0,
+        time_in_force: 0,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        reduce_only: false,
+        params: OrderParams::ImmediateOrCancel {
+            price_lots: twap_order.limit_price_lots,
+        },
+    };
+
+    // Slices are paid with the free balances only, the keeper can't move the owner's tokens
+    let position = &open_orders_account.position;
+    order.cap_to_free_balance(
+        &market,
+        position.base_free_native,
+        position.quote_free_native,
+    );
+
+    // A slice capped below the minimum size is skipped rather than failing
+    if order.max_base_lots >= min_base_lots.max(1) && order.max_quote_lots_including_fees > 0 {
+        let mut book = Orderbook {
+            bids: ctx.accounts.bids.load_mut()?,
+            asks: ctx.accounts.asks.load_mut()?,
+        };
+        let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+        let mut market_stats = ctx
+            .accounts
+            .market_stats
+            .as_ref()
+            .map(|stats| stats.load_mut())
+            .transpose()?;
+        let event_heap_size_before = event_heap.len();
+
+        let oracle_price_lots = market.oracle_price_lots(
+            AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
+            AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+            now_ts,
+            clock.slot,
+        )?;
+
+        let OrderWithAmounts {
+            total_base_taken_native,
+            total_quote_taken_native,
+            taker_fees,
+            taker_fees_base,
+            ..
+        } = book.new_order(
+            &order,
+            &mut market,
+            &ctx.accounts.market.key(),
+            &mut event_heap,
+            market_stats.as_deref_mut(),
+            oracle_price_lots,
+            Some(&mut open_orders_account),
+            &open_orders_account_pk,
+            now_ts,
+            limit,
+            ctx.remaining_accounts,
+        )?;
+
+        let position = &mut open_orders_account.position;
+        match order.side {
+            Side::Bid => {
+                position.quote_free_native -= total_quote_taken_native + taker_fees;
+            }
+            Side::Ask => {
+                position.base_free_native -= total_base_taken_native + taker_fees_base;
+            }
+        }
+
+        if event_heap.len() > event_heap_size_before {
+            position.penalty_heap_count += 1;
+        }
+
+        twap_order.filled_base_lots +=
+            (total_base_taken_native / market.base_lot_size as u64) as i64;
+    } else {
+        msg!("Not enough free balance to execute the TWAP slice");
+    }
+
+    let keeper_fee = twap_order.keeper_fee_lamports;
+    let twap_order_ai = ctx.accounts.twap_order.to_account_info();
+    **twap_order_ai.try_borrow_mut_lamports()? -= keeper_fee;
+    **ctx.accounts.keeper.try_borrow_mut_lamports()? += keeper_fee;
+
+    Ok(())
+}
@@ -2,48 +2,62 @@ pub use cancel_all_and_place_orders::*;
 pub use cancel_all_orders::*;
 pub use cancel_order::*;
 pub use cancel_order_by_client_order_id::*;
+pub use cancel_twap_order::*;
 pub use close_market::*;
 pub use close_open_orders_account::*;
 pub use close_open_orders_indexer::*;
 pub use consume_events::*;
 pub use create_market::*;
+pub use create_market_stats::*;
 pub use create_open_orders_account::*;
 pub use create_open_orders_indexer::*;
+pub use create_twap_order::*;
 pub use deposit::*;
 pub use edit_order::*;
+pub use execute_twap_slice::*;
 pub use place_order::*;
 pub use place_take_order::*;
 pub use prune_orders::*;
+pub use route_take_order::*;
 pub use set_delegate::*;
 pub use set_market_expired::*;
 pub use settle_funds::*;
 pub use settle_funds_expired::*;
+pub use start_auction::*;
 pub use stub_oracle_close::*;
 pub use stub_oracle_create::*;
 pub use stub_oracle_set::*;
 pub use sweep_fees::*;
+pub use uncross_auction::*;
 
 mod cancel_all_and_place_orders;
 mod cancel_all_orders;
 mod cancel_order;
 mod cancel_order_by_client_order_id;
+mod cancel_twap_order;
 mod close_market;
 mod close_open_orders_account;
 mod close_open_orders_indexer;
 mod consume_events;
 mod create_market;
+mod create_market_stats;
 mod create_open_orders_account;
 mod create_open_orders_indexer;
+mod create_twap_order;
 mod deposit;
 mod edit_order;
+mod execute_twap_slice;
 mod place_order;
 mod place_take_order;
 mod prune_orders;
+mod route_take_order;
 mod set_delegate;
 mod set_market_expired;
 mod settle_funds;
 mod settle_funds_expired;
+mod start_auction;
 mod stub_oracle_close;
 mod stub_oracle_create;
 mod stub_oracle_set;
 mod sweep_fees;
+mod uncross_auction;
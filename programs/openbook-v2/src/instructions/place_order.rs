@@ -10,7 +10,7 @@ use crate::token_utils::*;
 #[allow(clippy::too_many_arguments)]
 pub fn place_order<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, PlaceOrder<'info>>,
-    order: Order,
+    mut order: Order,
     limit: u8,
 ) -> Result<Option<u128>> {
     require_gte!(order.max_base_lots, 0, OpenBookError::InvalidInputLots);
@@ -41,6 +41,12 @@ pub fn place_order<'c: 'info, 'info>(
         asks: ctx.accounts.asks.load_mut()?,
     };
     let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut market_stats = ctx
+        .accounts
+        .market_stats
+        .as_ref()
+        .map(|stats| stats.load_mut())
+        .transpose()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -48,9 +54,23 @@ pub fn place_order<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        now_ts,
         clock.slot,
     )?;
 
+    if order.reduce_only {
+        let position = &open_orders_account.position;
+        order.cap_to_free_balance(
+            &market,
+            position.base_free_native,
+            position.quote_free_native,
+        );
+        if order.max_base_lots == 0 || order.max_quote_lots_including_fees == 0 {
+            msg!("Reduce-only order has no free balance to use");
+            return Ok(None);
+        }
+    }
+
     let OrderWithAmounts {
         order_id,
         total_base_taken_native,
@@ -58,6 +78,7 @@ pub fn place_order<'c: 'info, 'info>(
         posted_base_native,
         posted_quote_native,
         taker_fees,
+        taker_fees_base,
         maker_fees,
         ..
     } = book.new_order(
@@ -65,6 +86,7 @@ pub fn place_order<'c: 'info, 'info>(
         &mut market,
         &ctx.accounts.market.key(),
         &mut event_heap,
+        market_stats.as_deref_mut(),
         oracle_price_lots,
         Some(&mut open_orders_account),
         &open_orders_account_pk,
@@ -92,7 +114,7 @@ pub fn place_order<'c: 'info, 'info>(
 
         Side::Ask => {
             let free_base = position.base_free_native;
-            let max_base_native = total_base_taken_native + posted_base_native;
+            let max_base_native = total_base_taken_native + posted_base_native + taker_fees_base;
 
             let free_qty_to_lock = cmp::min(max_base_native, free_base);
             let deposit_amount = max_base_native - free_qty_to_lock;
@@ -105,16 +127,27 @@ pub fn place_order<'c: 'info, 'info>(
         }
     };
 
+    // Reduce-only orders never pull from the wallet, taker fees in base included
+    if order.reduce_only {
+        require_eq!(
+            deposit_amount,
+            0,
+            OpenBookError::ReduceOnlyExceedsFreeBalance
+        );
+    }
+
     if event_heap.len() > event_heap_size_before {
         position.penalty_heap_count += 1;
     }
 
-    token_transfer(
+    token_or_native_transfer(
         deposit_amount,
         &ctx.accounts.token_program,
-        &ctx.accounts.user_token_account,
+        ctx.accounts.user_token_account.as_ref(),
         &ctx.accounts.market_vault,
         &ctx.accounts.signer,
+        ctx.accounts.native_payer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
     )?;
 
     Ok(order_id)
@@ -33,6 +33,12 @@ pub fn place_take_order<'c: 'info, 'info>(
     };
 
     let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut market_stats = ctx
+        .accounts
+        .market_stats
+        .as_ref()
+        .map(|stats| stats.load_mut())
+        .transpose()?;
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -40,22 +46,18 @@ pub fn place_take_order<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        now_ts,
         clock.slot,
     )?;
 
     let side = order.side;
 
-    let OrderWithAmounts {
-        total_base_taken_native,
-        total_quote_taken_native,
-        referrer_amount,
-        taker_fees,
-        ..
-    } = book.new_order(
+    let order_amounts = book.new_order(
         &order,
         &mut market,
         &ctx.accounts.market.key(),
         &mut event_heap,
+        market_stats.as_deref_mut(),
         oracle_price_lots,
         None,
         &ctx.accounts.signer.key(),
@@ -64,23 +66,8 @@ pub fn place_take_order<'c: 'info, 'info>(
         ctx.remaining_accounts,
     )?;
 
-    // place_take_orders doesnt pay to referrers
-    let makers_rebates = taker_fees - referrer_amount;
-
-    let (deposit_amount, withdraw_amount) = match side {
-        Side::Bid => {
-            let total_quote_including_fees = total_quote_taken_native + makers_rebates;
-            market.base_deposit_total -= total_base_taken_native;
-            market.quote_deposit_total += total_quote_including_fees;
-            (total_quote_including_fees, total_base_taken_native)
-        }
-        Side::Ask => {
-            let total_quote_discounting_fees = total_quote_taken_native - makers_rebates;
-            market.base_deposit_total += total_base_taken_native;
-            market.quote_deposit_total -= total_quote_discounting_fees;
-            (total_base_taken_native, total_quote_discounting_fees)
-        }
-    };
+    let (deposit_amount, withdraw_amount) =
+        take_order_transfer_amounts(&mut market, side, &order_amounts);
 
     let seeds = market_seeds!(market, ctx.accounts.market.key());
 
@@ -111,22 +98,78 @@ pub fn place_take_order<'c: 'info, 'info>(
         ),
     };
 
-    token_transfer(
+    token_or_native_transfer(
         deposit_amount,
         &ctx.accounts.token_program,
-        user_deposit_acc.as_ref(),
+        user_deposit_acc.as_deref(),
         market_deposit_acc,
         &ctx.accounts.signer,
+        Some(&ctx.accounts.signer),
+        Some(&ctx.accounts.system_program),
     )?;
 
-    token_transfer_signed(
+    let native = NativeUnwrap {
+        native_mint: ctx
+            .accounts
+            .native_mint
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        unwrap_account: ctx
+            .accounts
+            .unwrap_account
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        destination: Some(ctx.accounts.signer.to_account_info()),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    token_or_native_transfer_signed(
         withdraw_amount,
         &ctx.accounts.token_program,
         market_withdraw_acc,
-        user_withdraw_acc.as_ref(),
+        user_withdraw_acc.as_deref(),
+        &native,
+        &ctx.accounts.market.key(),
         &ctx.accounts.market_authority,
         seeds,
     )?;
 
     Ok(())
 }
+
+/// Account a filled take order in the market deposit totals and return the native amounts
+/// the taker has to deposit and gets to withdraw.
+pub(crate) fn take_order_transfer_amounts(
+    market: &mut Market,
+    side: Side,
+    order_amounts: &OrderWithAmounts,
+) -> (u64, u64) {
+    let OrderWithAmounts {
+        total_base_taken_native,
+        total_quote_taken_native,
+        referrer_amount,
+        taker_fees,
+        taker_fees_base,
+        ..
+    } = *order_amounts;
+
+    // place_take_orders doesnt pay to referrers
+    let makers_rebates = taker_fees - referrer_amount;
+
+    // Base fees have no referrer share, they are paid in full and stay in the vault
+    match side {
+        Side::Bid => {
+            let total_quote_including_fees = total_quote_taken_native + makers_rebates;
+            let total_base_discounting_fees = total_base_taken_native - taker_fees_base;
+            market.base_deposit_total -= total_base_discounting_fees;
+            market.quote_deposit_total += total_quote_including_fees;
+            (total_quote_including_fees, total_base_discounting_fees)
+        }
+        Side::Ask => {
+            let total_base_including_fees = total_base_taken_native + taker_fees_base;
+            let total_quote_discounting_fees = total_quote_taken_native - makers_rebates;
+            market.base_deposit_total += total_base_including_fees;
+            market.quote_deposit_total -= total_quote_discounting_fees;
+            (total_base_including_fees, total_quote_discounting_fees)
+        }
+    }
+}
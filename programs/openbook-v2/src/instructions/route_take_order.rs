@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use super::place_take_order::take_order_transfer_amounts;
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::AccountInfoRef;
+use crate::error::*;
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn route_take_order<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RouteTakeOrder<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    limit: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = &ctx.accounts;
+
+    let input_mint = accounts.user_input_account.mint;
+    let intermediate_mint = accounts.user_intermediate_account.mint;
+    let output_mint = accounts.user_output_account.mint;
+
+    let first_side = route_side(
+        &accounts.first_market_base_vault,
+        &accounts.first_market_quote_vault,
+        input_mint,
+        intermediate_mint,
+    )?;
+    let second_side = route_side(
+        &accounts.second_market_base_vault,
+        &accounts.second_market_quote_vault,
+        intermediate_mint,
+        output_mint,
+    )?;
+
+    let first_leg = take_leg(
+        &accounts.first_market,
+        &accounts.first_bids,
+        &accounts.first_asks,
+        &accounts.first_event_heap,
+        accounts.first_market_stats.as_ref(),
+        accounts.first_oracle_a.as_ref(),
+        accounts.first_oracle_b.as_ref(),
+        first_side,
+        amount_in,
+        &accounts.signer.key(),
+        &clock,
+        limit,
+    )?;
+
+    let second_leg = take_leg(
+        &accounts.second_market,
+        &accounts.second_bids,
+        &accounts.second_asks,
+        &accounts.second_event_heap,
+        accounts.second_market_stats.as_ref(),
+        accounts.second_oracle_a.as_ref(),
+        accounts.second_oracle_b.as_ref(),
+        second_side,
+        first_leg.withdraw_amount,
+        &accounts.signer.key(),
+        &clock,
+        limit,
+    )?;
+
+    require_gte!(
+        second_leg.withdraw_amount,
+        min_amount_out,
+        OpenBookError::RouteBelowMinimumOutput
+    );
+
+    for (event_heap_grew, market) in [
+        (first_leg.event_heap_grew, &accounts.first_market),
+        (second_leg.event_heap_grew, &accounts.second_market),
+    ] {
+        if event_heap_grew {
+            system_program_transfer(
+                PENALTY_EVENT_HEAP,
+                &accounts.system_program,
+                &accounts.penalty_payer,
+                market,
+            )?;
+        }
+    }
+
+    let (first_deposit_vault, first_withdraw_vault) = match first_side {
+        Side::Bid => (
+            &accounts.first_market_quote_vault,
+            &accounts.first_market_base_vault,
+        ),
+        Side::Ask => (
+            &accounts.first_market_base_vault,
+            &accounts.first_market_quote_vault,
+        ),
+    };
+    let (second_deposit_vault, second_withdraw_vault) = match second_side {
+        Side::Bid => (
+            &accounts.second_market_quote_vault,
+            &accounts.second_market_base_vault,
+        ),
+        Side::Ask => (
+            &accounts.second_market_base_vault,
+            &accounts.second_market_quote_vault,
+        ),
+    };
+
+    let first_market = accounts.first_market.load()?;
+    let first_seeds = market_seeds!(first_market, accounts.first_market.key());
+    drop(first_market);
+
+    let second_market = accounts.second_market.load()?;
+    let second_seeds = market_seeds!(second_market, accounts.second_market.key());
+    drop(second_market);
+
+    token_transfer(
+        first_leg.deposit_amount,
+        &accounts.token_program,
+        accounts.user_input_account.as_ref(),
+        first_deposit_vault,
+        &accounts.signer,
+    )?;
+
+    // The intermediate tokens move between the market vaults, only the leftover
+    // the second leg could not use goes back to the user
+    token_transfer_signed(
+        second_leg.deposit_amount,
+        &accounts.token_program,
+        first_withdraw_vault,
+        second_deposit_vault,
+        &accounts.first_market_authority,
+        first_seeds,
+    )?;
+
+    token_transfer_signed(
+        first_leg.withdraw_amount - second_leg.deposit_amount,
+        &accounts.token_program,
+        first_withdraw_vault,
+        accounts.user_intermediate_account.as_ref(),
+        &accounts.first_market_authority,
+        first_seeds,
+    )?;
+
+    token_transfer_signed(
+        second_leg.withdraw_amount,
+        &accounts.token_program,
+        second_withdraw_vault,
+        accounts.user_output_account.as_ref(),
+        &accounts.second_market_authority,
+        second_seeds,
+    )?;
+
+    Ok(())
+}
+
+struct TakeLeg {
+    deposit_amount: u64,
+    withdraw_amount: u64,
+    event_heap_grew: bool,
+}
+
+/// Side of the take order that swaps `input_mint` for `output_mint` on a market
+fn route_side(
+    market_base_vault: &Account<TokenAccount>,
+    market_quote_vault: &Account<TokenAccount>,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+) -> Result<Side> {
+    if input_mint == market_quote_vault.mint && output_mint == market_base_vault.mint {
+        Ok(Side::Bid)
+    } else if input_mint == market_base_vault.mint && output_mint == market_quote_vault.mint {
+        Ok(Side::Ask)
+    } else {
+        Err(OpenBookError::InvalidInputRouteMints.into())
+    }
+}
+
+/// Take the book of a market with up to `amount_in` native tokens at any price
+#[allow(clippy::too_many_arguments)]
+fn take_leg<'info>(
+    market_loader: &AccountLoader<'info, Market>,
+    bids: &AccountLoader<'info, BookSide>,
+    asks: &AccountLoader<'info, BookSide>,
+    event_heap: &AccountLoader<'info, EventHeap>,
+    market_stats: Option<&AccountLoader<'info, MarketStats>>,
+    oracle_a: Option<&UncheckedAccount<'info>>,
+    oracle_b: Option<&UncheckedAccount<'info>>,
+    side: Side,
+    amount_in: u64,
+    signer: &Pubkey,
+    clock: &Clock,
+    limit: u8,
+) -> Result<TakeLeg> {
+    let mut market = market_loader.load_mut()?;
+    require!(
+        !market.is_expired(clock.unix_timestamp),
+        OpenBookError::MarketHasExpired
+    );
+
+    let mut book = Orderbook {
+        bids: bids.load_mut()?,
+        asks: asks.load_mut()?,
+    };
+
+    let mut event_heap = event_heap.load_mut()?;
+    let mut market_stats = market_stats.map(|stats| stats.load_mut()).transpose()?;
+    let event_heap_size_before = event_heap.len();
+
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(oracle_a)?.as_ref(),
+        AccountInfoRef::borrow_some(oracle_b)?.as_ref(),
+        now_ts,
+        clock.slot,
+    )?;
+
+    let (max_base_lots, max_quote_lots_including_fees) = match side {
+        Side::Bid => (
+            market.max_base_lots(),
+            (amount_in / market.quote_lot_size as u64).min(market.max_quote_lots() as u64) as i64,
+        ),
+        Side::Ask => (
+            (amount_in / market.base_lot_size as u64).min(market.max_base_lots() as u64) as i64,
+            market.max_quote_lots(),
+        ),
+    };
+
+    let order = Order {
+        side,
+        max_base_lots,
+        max_quote_lots_including_fees,
+        client_order_id: 0,
+        time_in_force: 0,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        reduce_only: false,
+        params: OrderParams::Market,
+    };
+
+    let order_amounts = book.new_order(
+        &order,
+        &mut market,
+        &market_loader.key(),
+        &mut event_heap,
+        market_stats.as_deref_mut(),
+        oracle_price_lots,
+        None,
+        signer,
+        now_ts,
+        limit,
+        &[],
+    )?;
+
+    let (deposit_amount, withdraw_amount) =
+        take_order_transfer_amounts(&mut market, side, &order_amounts);
+
+    Ok(TakeLeg {
+        deposit_amount,
+        withdraw_amount,
+        event_heap_grew: event_heap.len() > event_heap_size_before,
+    })
+}
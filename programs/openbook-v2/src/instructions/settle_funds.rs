@@ -17,6 +17,7 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
         open_orders_account.position.locked_maker_fees = 0;
     }
 
+    let owner_is_payer = ctx.accounts.penalty_payer.key() == open_orders_account.owner;
     let pa = &mut open_orders_account.position;
     let referrer_rebate = pa.referrer_rebates_available + roundoff_maker_fees;
 
@@ -56,20 +57,40 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
         )?;
     }
 
-    token_transfer_signed(
+    // Native SOL is only unwrapped to the owner, who also pays for the temporary account
+    let native = NativeUnwrap {
+        native_mint: ctx
+            .accounts
+            .native_mint
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        unwrap_account: ctx
+            .accounts
+            .unwrap_account
+            .as_ref()
+            .map(|a| a.to_account_info()),
+        destination: owner_is_payer.then(|| ctx.accounts.penalty_payer.to_account_info()),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+
+    token_or_native_transfer_signed(
         pa.base_free_native,
         &ctx.accounts.token_program,
         &ctx.accounts.market_base_vault,
-        &ctx.accounts.user_base_account,
+        ctx.accounts.user_base_account.as_ref(),
+        &native,
+        &ctx.accounts.market.key(),
         &ctx.accounts.market_authority,
         seeds,
     )?;
 
-    token_transfer_signed(
+    token_or_native_transfer_signed(
         pa.quote_free_native,
         &ctx.accounts.token_program,
         &ctx.accounts.market_quote_vault,
-        &ctx.accounts.user_quote_account,
+        ctx.accounts.user_quote_account.as_ref(),
+        &native,
+        &ctx.accounts.market.key(),
         &ctx.accounts.market_authority,
         seeds,
     )?;
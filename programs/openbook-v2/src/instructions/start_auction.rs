@@ -0,0 +1,18 @@
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use anchor_lang::prelude::*;
+
+pub fn start_auction(ctx: Context<StartAuction>, auction_end_ts: i64) -> Result<()> {
+    let now_ts = Clock::get()?.unix_timestamp;
+    let mut market = ctx.accounts.market.load_mut()?;
+    require!(!market.is_expired(now_ts), OpenBookError::MarketHasExpired);
+    require!(
+        auction_end_ts > now_ts && market.maker_fee >= 0,
+        OpenBookError::InvalidInputAuction
+    );
+
+    market.auction_end_ts = auction_end_ts;
+    market.auction_price_lots = 0;
+
+    Ok(())
+}
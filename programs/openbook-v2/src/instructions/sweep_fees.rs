@@ -12,6 +12,20 @@ pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
     market.fees_available = 0;
     market.quote_deposit_total -= amount;
 
+    // Base fees stay available until swept with the base accounts
+    let base_accounts = ctx
+        .accounts
+        .market_base_vault
+        .as_ref()
+        .zip(ctx.accounts.base_token_receiver_account.as_ref());
+    let base_amount = if base_accounts.is_some() {
+        market.base_fees_available
+    } else {
+        0
+    };
+    market.base_fees_available -= base_amount;
+    market.base_deposit_total -= base_amount;
+
     let seeds = market_seeds!(market, ctx.accounts.market.key());
     drop(market);
 
@@ -30,5 +44,24 @@ pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
         receiver: ctx.accounts.token_receiver_account.key(),
     });
 
+    if let Some((market_base_vault, base_token_receiver_account)) = base_accounts {
+        if base_amount > 0 {
+            token_transfer_signed(
+                base_amount,
+                &ctx.accounts.token_program,
+                market_base_vault,
+                base_token_receiver_account,
+                &ctx.accounts.market_authority,
+                seeds,
+            )?;
+
+            emit_stack(SweepFeesLog {
+                market: ctx.accounts.market.key(),
+                amount: base_amount,
+                receiver: base_token_receiver_account.key(),
+            });
+        }
+    }
+
     Ok(())
 }
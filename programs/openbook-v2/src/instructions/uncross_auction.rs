@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::AccountInfoRef;
+use crate::error::*;
+use crate::state::*;
+
+pub fn uncross_auction(ctx: Context<UncrossAuction>, limit: u8) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let mut market = ctx.accounts.market.load_mut()?;
+    require!(
+        !market.is_expired(clock.unix_timestamp),
+        OpenBookError::MarketHasExpired
+    );
+    require!(market.is_in_auction(), OpenBookError::MarketNotInAuction);
+    require_gte!(
+        clock.unix_timestamp,
+        market.auction_end_ts,
+        OpenBookError::AuctionNotEnded
+    );
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_mut()?,
+        asks: ctx.accounts.asks.load_mut()?,
+    };
+    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut market_stats = ctx
+        .accounts
+        .market_stats
+        .as_ref()
+        .map(|stats| stats.load_mut())
+        .transpose()?;
+
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        now_ts,
+        clock.slot,
+    )?;
+
+    // The clearing price is fixed by the first call, later calls keep matching at it
+    let price_lots = if market.auction_price_lots != 0 {
+        Some(market.auction_price_lots)
+    } else {
+        book.auction_clearing_price(now_ts, oracle_price_lots)
+    };
+
+    let uncrossed = match price_lots {
+        Some(price_lots) => {
+            market.auction_price_lots = price_lots;
+            book.uncross_auction(
+                &mut market,
+                &mut event_heap,
+                market_stats.as_deref_mut(),
+                price_lots,
+                now_ts,
+                oracle_price_lots,
+                limit,
+            )?
+        }
+        None => true,
+    };
+
+    if uncrossed {
+        msg!("Auction uncrossed, continuous trading starts");
+        market.auction_end_ts = 0;
+        market.auction_price_lots = 0;
+    }
+
+    Ok(())
+}
@@ -38,6 +38,17 @@ pub mod openbook_v2 {
     use super::*;
 
     /// Create a [`Market`](crate::state::Market) for a given token pair.
+    ///
+    /// A non-zero `auction_end_ts` opens the market with a call auction, see
+    /// `uncross_auction`.
+    ///
+    /// With `fees_in_base` taker fees and maker rebates are paid in base: asks pay
+    /// their taker fee on top of the base they sell and bids receive that much less
+    /// base. The maker fee can't be positive then.
+    ///
+    /// Book side oracles need their market, and Raydium CPMM vault oracles their
+    /// pool state, as the first remaining account. Pool oracles and OpenBook market
+    /// and book side oracles need a `max_twap_deviation`.
     #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
@@ -48,6 +59,11 @@ pub mod openbook_v2 {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        min_base_lots: i64,
+        min_quote_lots: i64,
+        tick_size_lots: i64,
+        auction_end_ts: i64,
+        fees_in_base: bool,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::create_market(
@@ -59,18 +75,38 @@ pub mod openbook_v2 {
             maker_fee,
             taker_fee,
             time_expiry,
+            min_base_lots,
+            min_quote_lots,
+            tick_size_lots,
+            auction_end_ts,
+            fees_in_base,
         )?;
         Ok(())
     }
 
     /// Close a [`Market`](crate::state::Market) (only
     /// [`close_market_admin`](crate::state::Market::close_market_admin)).
+    ///
+    /// The market must be expired and its book and event heap empty. The market,
+    /// bids, asks, event heap and market stats accounts are closed and their rent
+    /// goes to `sol_destination`.
     pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::close_market(ctx)?;
         Ok(())
     }
 
+    /// Create the [`MarketStats`](crate::state::MarketStats) account of a market,
+    /// keeping the high, low and volume of its fills of the last 24 hours.
+    ///
+    /// Anyone can create it. From then on the instructions matching orders of the
+    /// market need it.
+    pub fn create_market_stats(ctx: Context<CreateMarketStats>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::create_market_stats(ctx)?;
+        Ok(())
+    }
+
     /// Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.
     pub fn create_open_orders_indexer(ctx: Context<CreateOpenOrdersIndexer>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
@@ -138,6 +174,7 @@ pub mod openbook_v2 {
             client_order_id: args.client_order_id,
             time_in_force,
             self_trade_behavior: args.self_trade_behavior,
+            reduce_only: args.reduce_only,
             params: match args.order_type {
                 PlaceOrderType::Market => OrderParams::Market,
                 PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
@@ -146,6 +183,9 @@ pub mod openbook_v2 {
                 PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
                     price_lots: args.price_lots,
                 },
+                PlaceOrderType::ExactOut => OrderParams::ExactOut {
+                    price_lots: args.price_lots,
+                },
                 _ => OrderParams::Fixed {
                     price_lots: args.price_lots,
                     order_type: args.order_type.to_post_order_type()?,
@@ -186,6 +226,7 @@ pub mod openbook_v2 {
             client_order_id: place_order.client_order_id,
             time_in_force,
             self_trade_behavior: place_order.self_trade_behavior,
+            reduce_only: place_order.reduce_only,
             params: match place_order.order_type {
                 PlaceOrderType::Market => OrderParams::Market,
                 PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
@@ -194,6 +235,9 @@ pub mod openbook_v2 {
                 PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
                     price_lots: place_order.price_lots,
                 },
+                PlaceOrderType::ExactOut => OrderParams::ExactOut {
+                    price_lots: place_order.price_lots,
+                },
                 _ => OrderParams::Fixed {
                     price_lots: place_order.price_lots,
                     order_type: place_order.order_type.to_post_order_type()?,
@@ -246,11 +290,8 @@ pub mod openbook_v2 {
             client_order_id: place_order.client_order_id,
             time_in_force,
             self_trade_behavior: place_order.self_trade_behavior,
-            params: OrderParams::OraclePegged {
-                price_offset_lots: place_order.price_offset_lots,
-                order_type: place_order.order_type.to_post_order_type()?,
-                peg_limit: place_order.peg_limit,
-            },
+            reduce_only: false,
+            params: place_order.order_params()?,
         };
         #[cfg(feature = "enable-gpl")]
         return instructions::edit_order(
@@ -293,6 +334,7 @@ pub mod openbook_v2 {
                 client_order_id: i as u64,
                 time_in_force,
                 self_trade_behavior: SelfTradeBehavior::CancelProvide,
+                reduce_only: order.reduce_only,
                 params: match orders_type {
                     PlaceOrderType::Market => OrderParams::Market,
                     PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
@@ -301,6 +343,9 @@ pub mod openbook_v2 {
                     PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
                         price_lots: order.price_lots,
                     },
+                    PlaceOrderType::ExactOut => OrderParams::ExactOut {
+                        price_lots: order.price_lots,
+                    },
                     _ => OrderParams::Fixed {
                         price_lots: order.price_lots,
                         order_type: orders_type.to_post_order_type()?,
@@ -344,6 +389,7 @@ pub mod openbook_v2 {
                 client_order_id: i as u64,
                 time_in_force,
                 self_trade_behavior: SelfTradeBehavior::CancelProvide,
+                reduce_only: order.reduce_only,
                 params: match orders_type {
                     PlaceOrderType::Market => OrderParams::Market,
                     PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
@@ -352,6 +398,9 @@ pub mod openbook_v2 {
                     PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
                         price_lots: order.price_lots,
                     },
+                    PlaceOrderType::ExactOut => OrderParams::ExactOut {
+                        price_lots: order.price_lots,
+                    },
                     _ => OrderParams::Fixed {
                         price_lots: order.price_lots,
                         order_type: orders_type.to_post_order_type()?,
@@ -394,11 +443,8 @@ pub mod openbook_v2 {
             client_order_id: args.client_order_id,
             time_in_force,
             self_trade_behavior: args.self_trade_behavior,
-            params: OrderParams::OraclePegged {
-                price_offset_lots: args.price_offset_lots,
-                order_type: args.order_type.to_post_order_type()?,
-                peg_limit: args.peg_limit,
-            },
+            reduce_only: false,
+            params: args.order_params()?,
         };
         #[cfg(feature = "enable-gpl")]
         return instructions::place_order(ctx, order, args.limit);
@@ -411,6 +457,15 @@ pub mod openbook_v2 {
     /// add a new order off the book.
     ///
     /// This type of order allows for instant token settlement for the taker.
+    ///
+    /// With `PlaceOrderType::ExactOut` a bid receives `max_base_lots` paying at
+    /// most `max_quote_lots_including_fees`, and an ask receives at least
+    /// `max_quote_lots_including_fees` selling at most `max_base_lots`, both net
+    /// of taker fees. Asks may receive up to a base lot's worth more, as fills
+    /// are in whole base lots. It fails if the output can't be fully delivered.
+    ///
+    /// On a native mint side the user token account can be left out to pay
+    /// with and receive lamports of the signer directly.
     pub fn place_take_order<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, PlaceTakeOrder<'info>>,
         args: PlaceTakeOrderArgs,
@@ -424,6 +479,7 @@ pub mod openbook_v2 {
             client_order_id: 0,
             time_in_force: 0,
             self_trade_behavior: SelfTradeBehavior::default(),
+            reduce_only: false,
             params: match args.order_type {
                 PlaceOrderType::Market => OrderParams::Market,
                 PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
@@ -432,6 +488,9 @@ pub mod openbook_v2 {
                 PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
                     price_lots: args.price_lots,
                 },
+                PlaceOrderType::ExactOut => OrderParams::ExactOut {
+                    price_lots: args.price_lots,
+                },
                 _ => return Err(OpenBookError::InvalidInputOrderType.into()),
             },
         };
@@ -441,6 +500,78 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Swap through two markets that share a mint, e.g. SOL -> USDC -> BONK,
+    /// taking liquidity off both books.
+    ///
+    /// Up to `amount_in` of the input token is sold on the first market at any
+    /// price and its whole output is sold on the second market. The instruction
+    /// fails if less than `min_amount_out` of the output token is received.
+    ///
+    /// The intermediate token moves directly between the market vaults, only
+    /// the part of it the second market could not take (lot size rounding or
+    /// missing liquidity) is sent to `user_intermediate_account`.
+    ///
+    /// `limit` caps the number of orders to fill on each of the markets.
+    pub fn route_take_order<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RouteTakeOrder<'info>>,
+        args: RouteTakeOrderArgs,
+    ) -> Result<()> {
+        require_gt!(args.amount_in, 0, OpenBookError::InvalidInputLots);
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::route_take_order(ctx, args.amount_in, args.min_amount_out, args.limit)?;
+        Ok(())
+    }
+
+    /// Create a TWAP order for an open orders account, selling or buying
+    /// `total_base_lots` in slices of `slice_base_lots` every `interval_seconds`.
+    ///
+    /// Slices are ImmediateOrCancel orders at `limit_price_lots` placed by keepers
+    /// with `execute_twap_slice`, using the free balances of the open orders account.
+    /// The payer funds `keeper_fee_lamports` for each slice up front.
+    ///
+    /// Slices need to meet the market's minimum order size at the limit price, a
+    /// remainder below it is executed with the slice before it.
+    pub fn create_twap_order(
+        ctx: Context<CreateTwapOrder>,
+        args: CreateTwapOrderArgs,
+    ) -> Result<()> {
+        require_gte!(
+            args.limit_price_lots,
+            1,
+            OpenBookError::InvalidInputPriceLots
+        );
+        require!(
+            args.total_base_lots > 0 && args.slice_base_lots > 0 && args.interval_seconds > 0,
+            OpenBookError::InvalidInputTwap
+        );
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::create_twap_order(ctx, args)?;
+        Ok(())
+    }
+
+    /// Execute the current slice of a TWAP order, permissionless.
+    ///
+    /// Slices whose window passed without being executed are skipped, as are slices
+    /// the free balance can't cover at the market's minimum order size. The keeper
+    /// receives the keeper fee of the slice, even if nothing could be filled.
+    pub fn execute_twap_slice<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExecuteTwapSlice<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::execute_twap_slice(ctx, limit)?;
+        Ok(())
+    }
+
+    /// Close a TWAP order, returning its rent and the unused keeper fees.
+    pub fn cancel_twap_order(ctx: Context<CancelTwapOrder>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::cancel_twap_order(ctx)?;
+        Ok(())
+    }
+
     /// Process up to `limit` [events](crate::state::AnyEvent).
     ///
     /// When a user places a 'take' order, they do not know beforehand which
@@ -530,6 +661,9 @@ pub mod openbook_v2 {
     ///
     /// Makers might wish to `deposit`, rather than have actual tokens moved for
     /// each trade, in order to reduce CUs.
+    ///
+    /// Without a user token account on a native mint side, lamports of the
+    /// `native_payer` are wrapped instead. The same goes for `place_order`.
     pub fn deposit(ctx: Context<Deposit>, base_amount: u64, quote_amount: u64) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::deposit(ctx, base_amount, quote_amount)?;
@@ -556,6 +690,9 @@ pub mod openbook_v2 {
     }
 
     /// Withdraw any available tokens.
+    ///
+    /// Leaving out the user token account of a native mint side unwraps it to
+    /// the owner, who has to be the `penalty_payer` then.
     pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::settle_funds(ctx)?;
@@ -573,6 +710,9 @@ pub mod openbook_v2 {
     }
 
     /// Sweep fees, as a [`Market`](crate::state::Market)'s admin.
+    ///
+    /// Base fees of markets with fees in base are swept when the base vault and a
+    /// base token receiver account are passed.
     pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::sweep_fees(ctx)?;
@@ -602,6 +742,30 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Put the market in a call auction until `auction_end_ts`, e.g. before trading
+    /// resumes (only [`close_market_admin`](crate::state::Market::close_market_admin)).
+    pub fn start_auction(ctx: Context<StartAuction>, auction_end_ts: i64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::start_auction(ctx, auction_end_ts)?;
+        Ok(())
+    }
+
+    /// End the call auction of a market.
+    ///
+    /// While in auction, orders are posted to the book without matching. Once
+    /// `auction_end_ts` has passed, anyone can call this to match the crossing orders
+    /// at the single price that maximizes the matched volume. Both sides of each match
+    /// are settled as makers through [`FillEvent`](crate::state::FillEvent)s, without
+    /// taker fees. Each match logs a single `FillLog`, from the bid's event.
+    ///
+    /// Matches up to `limit` pairs of orders per call. Continuous trading starts once
+    /// no more orders cross at the clearing price.
+    pub fn uncross_auction(ctx: Context<UncrossAuction>, limit: u8) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::uncross_auction(ctx, limit)?;
+        Ok(())
+    }
+
     pub fn stub_oracle_create(ctx: Context<StubOracleCreate>, price: f64) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::stub_oracle_create(ctx, price)?;
@@ -637,6 +801,9 @@ pub struct PlaceOrderArgs {
     // Use this to limit compute used during order matching.
     // When the limit is reached, processing stops and the instruction succeeds.
     pub limit: u8,
+    // Only use the free balance on the open orders account, never pulling tokens
+    // from the wallet: base_free_native for asks and quote_free_native for bids.
+    pub reduce_only: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
@@ -645,6 +812,9 @@ pub struct PlaceMultipleOrdersArgs {
     pub price_lots: i64,
     pub max_quote_lots_including_fees: i64,
     pub expiry_timestamp: u64,
+    // See PlaceOrderArgs::reduce_only. Free balance locked by earlier orders
+    // of the batch is not available anymore.
+    pub reduce_only: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
@@ -679,6 +849,38 @@ pub struct PlaceOrderPeggedArgs {
     // Use this to limit compute used during order matching.
     // When the limit is reached, processing stops and the instruction succeeds.
     pub limit: u8,
+
+    // Alternative adjustment from the oracle price, in basis points of the oracle price.
+    // Orders on the book may be filled at oracle * (1 + bps / 10000) (depends on order type).
+    //
+    // If set, price_offset_lots must be 0. Kept last to leave the layout of the fields
+    // before it unchanged.
+    pub price_offset_bps: Option<i32>,
+}
+
+impl PlaceOrderPeggedArgs {
+    fn order_params(&self) -> Result<OrderParams> {
+        let order_type = self.order_type.to_post_order_type()?;
+        Ok(match self.price_offset_bps {
+            Some(price_offset_bps) => {
+                require_eq!(
+                    self.price_offset_lots,
+                    0,
+                    OpenBookError::InvalidInputPegOffset
+                );
+                OrderParams::OraclePeggedBps {
+                    price_offset_bps,
+                    order_type,
+                    peg_limit: self.peg_limit,
+                }
+            }
+            None => OrderParams::OraclePegged {
+                price_offset_lots: self.price_offset_lots,
+                order_type,
+                peg_limit: self.peg_limit,
+            },
+        })
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
@@ -696,6 +898,32 @@ pub struct PlaceTakeOrderArgs {
     pub limit: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RouteTakeOrderArgs {
+    /// Native amount of the input token to sell on the first market.
+    pub amount_in: u64,
+    /// Minimum native amount of the output token to receive from the second market.
+    pub min_amount_out: u64,
+    /// Maximum number of orders to fill on each of the two markets.
+    pub limit: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CreateTwapOrderArgs {
+    pub side: Side,
+    /// Worst price the slices are executed at.
+    pub limit_price_lots: i64,
+    pub total_base_lots: i64,
+    pub slice_base_lots: i64,
+    pub interval_seconds: u64,
+    /// Timestamp of the first slice, it starts right away if in the past.
+    pub start_ts: u64,
+    /// Lamports paid to the keeper executing a slice.
+    pub keeper_fee_lamports: u64,
+}
+
 // Add security details to explorer.solana.com
 #[cfg(not(feature = "no-entrypoint"))]
 use {default_env::default_env, solana_security_txt::security_txt};
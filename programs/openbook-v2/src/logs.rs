@@ -43,7 +43,7 @@ pub struct FillLog {
 
     pub taker: Pubkey,
     pub taker_client_order_id: u64,
-    pub taker_fee_ceil: u64, // native quote
+    pub taker_fee_ceil: u64, // native quote, native base on markets with fees in base
 
     pub price: i64,
     pub quantity: i64, // number of base lots
@@ -118,3 +118,46 @@ pub struct OpenOrdersPositionLog {
     /// Cumulative taker volume in quote native units (display only)
     pub taker_volume: u128,
 }
+
+#[event]
+pub struct OrderPlacedLog {
+    pub market: Pubkey,
+    pub owner: Pubkey, // open orders account
+    pub order_id: u128,
+    pub client_order_id: u64,
+    pub side: u8,
+    pub order_tree: u8, // 0 fixed, 1 oracle pegged
+    pub price_data: u64,
+    pub peg_limit: i64,
+    pub price: i64,    // price in lots at the time of placement
+    pub quantity: i64, // number of base lots posted
+    pub time_in_force: u16,
+    pub timestamp: u64,
+}
+
+#[event]
+pub struct OrderCancelledLog {
+    pub market: Pubkey,
+    pub owner: Pubkey, // open orders account
+    pub order_id: u128,
+    pub client_order_id: u64,
+    pub side: u8,
+    pub order_tree: u8, // 0 fixed, 1 oracle pegged
+    pub price_data: u64,
+    pub peg_limit: i64,
+    pub quantity: i64, // number of base lots removed from the book
+}
+
+#[event]
+pub struct OrderExpiredLog {
+    pub market: Pubkey,
+    pub owner: Pubkey, // open orders account
+    pub order_id: u128,
+    pub client_order_id: u64,
+    pub side: u8,
+    pub order_tree: u8, // 0 fixed, 1 oracle pegged
+    pub price_data: u64,
+    pub peg_limit: i64,
+    pub quantity: i64, // number of base lots removed from the book
+    pub expiry: u64,
+}
@@ -9,7 +9,7 @@ use crate::pubkey_option::NonZeroPubkeyOption;
 use crate::state::oracle;
 use crate::{accounts_zerocopy::KeyedAccountReader, state::orderbook::Side};
 
-use super::{orderbook, OracleConfig};
+use super::{orderbook, MarketStats, OracleConfig};
 
 // For a 1bps taker fee, set taker_fee to 100, so taker_fee/FEES_SCALE_FACTOR = 10e-4
 pub const FEES_SCALE_FACTOR: i128 = 1_000_000;
@@ -28,7 +28,15 @@ pub struct Market {
     pub base_decimals: u8,
     pub quote_decimals: u8,
 
-    pub padding1: [u8; 5],
+    /// 1 if taker fees and maker rebates are paid in base instead of quote, see
+    /// `base_fees_available`
+    pub fees_in_base: u8,
+
+    /// 1 once the market has a `MarketStats` account, which the instructions matching
+    /// orders need then
+    pub has_market_stats: u8,
+
+    pub padding1: [u8; 3],
 
     // Pda for signing vault txs
     pub market_authority: Pubkey,
@@ -60,6 +68,10 @@ pub struct Market {
     pub oracle_b: NonZeroPubkeyOption,
     /// Oracle configuration
     pub oracle_config: OracleConfig,
+    /// Time weighted average of the accepted native oracle prices, zero until the first
+    pub oracle_twap_price: f64,
+    /// Timestamp in seconds of the last oracle TWAP update
+    pub oracle_twap_last_update_ts: u64,
 
     /// Number of quote native in a quote lot. Must be a power of 10.
     ///
@@ -116,7 +128,42 @@ pub struct Market {
     pub market_quote_vault: Pubkey,
     pub quote_deposit_total: u64,
 
-    pub reserved: [u8; 128],
+    /// Minimum number of base lots an order needs to have to be accepted.
+    ///
+    /// Remainders of partially matched orders below it are not posted.
+    pub min_base_lots: i64,
+
+    /// Minimum notional value in quote lots an order needs to have to be accepted.
+    ///
+    /// Remainders of partially matched orders below it are not posted.
+    pub min_quote_lots: i64,
+
+    /// Prices of orders posted at a fixed price need to be a multiple of this, in
+    /// price lots. Values of 0 and 1 mean any price lot is fine.
+    pub tick_size_lots: i64,
+
+    /// While non-zero the market is in a call auction: orders are posted without
+    /// matching until `uncross_auction` is called after this timestamp.
+    pub auction_end_ts: i64,
+
+    /// Clearing price of the auction in price lots, set once the uncross started.
+    /// Zero while orders are still being collected.
+    pub auction_price_lots: i64,
+
+    /// Price in lots of the last fill. Zero if nothing traded yet.
+    pub last_price_lots: i64,
+    /// Timestamp in seconds of the last fill
+    pub last_trade_ts: u64,
+
+    /// Time weighted average of the fill prices in lots over `ORACLE_TWAP_SECONDS`, as
+    /// of `last_trade_ts`. Read by markets using this one as their oracle.
+    pub fill_twap_price_lots: f64,
+
+    /// Base fees generated on markets with fees in base, available to withdraw via
+    /// sweep_fees. Referrers don't get a share of them.
+    pub base_fees_available: u64,
+
+    pub reserved: [u8; 56],
 }
 
 const_assert_eq!(
@@ -129,13 +176,17 @@ const_assert_eq!(
     1 +                         // bump
     1 +                         // base_decimals
     1 +                         // quote_decimals
-    5 +                         // padding1
+    1 +                         // fees_in_base
+    1 +                         // has_market_stats
+    3 +                         // padding1
     8 +                         // time_expiry
     16 +                        // name
     3 * 32 +                    // bids, asks, and event_heap
     32 +                        // oracle_a
     32 +                        // oracle_b
     size_of::<OracleConfig>() + // oracle_config
+    8 +                         // oracle_twap_price
+    8 +                         // oracle_twap_last_update_ts
     8 +                         // quote_lot_size
     8 +                         // base_lot_size
     8 +                         // seq_num
@@ -151,7 +202,16 @@ const_assert_eq!(
     8 +                         // quote_deposit_total
     8 +                         // base_fees_accrued
     8 +                         // referrer_rebates_accrued
-    128 // reserved
+    8 +                         // min_base_lots
+    8 +                         // min_quote_lots
+    8 +                         // tick_size_lots
+    8 +                         // auction_end_ts
+    8 +                         // auction_price_lots
+    8 +                         // last_price_lots
+    8 +                         // last_trade_ts
+    8 +                         // fill_twap_price_lots
+    8 +                         // base_fees_available
+    56 // reserved
 );
 const_assert_eq!(size_of::<Market>(), 840);
 const_assert_eq!(size_of::<Market>() % 8, 0);
@@ -167,10 +227,54 @@ impl Market {
         self.time_expiry != 0 && self.time_expiry < timestamp
     }
 
+    pub fn is_in_auction(&self) -> bool {
+        self.auction_end_ts != 0
+    }
+
+    pub fn has_fees_in_base(&self) -> bool {
+        self.fees_in_base == 1
+    }
+
+    pub fn has_market_stats(&self) -> bool {
+        self.has_market_stats == 1
+    }
+
+    /// Record a fill at `price_lots` as the last price and in the fill TWAP, and in the
+    /// market stats if the market has them
+    pub fn record_trade(
+        &mut self,
+        market_stats: Option<&mut MarketStats>,
+        now_ts: u64,
+        price_lots: i64,
+        base_lots: i64,
+    ) {
+        if let Some(market_stats) = market_stats {
+            market_stats.record(now_ts, price_lots, base_lots);
+        }
+
+        self.fill_twap_price_lots = if self.last_trade_ts == 0 {
+            price_lots as f64
+        } else {
+            self.fill_twap(now_ts)
+        };
+        self.last_price_lots = price_lots;
+        self.last_trade_ts = now_ts;
+    }
+
+    /// The fill TWAP in price lots at `now_ts`, the last fill price being held since
+    /// `last_trade_ts`. A fill only moves the TWAP as time passes at its price.
+    pub fn fill_twap(&self, now_ts: u64) -> f64 {
+        let elapsed = now_ts.saturating_sub(self.last_trade_ts);
+        let weight = (elapsed as f64 / oracle::ORACLE_TWAP_SECONDS as f64).min(1f64);
+        let twap = self.fill_twap_price_lots;
+        twap + weight * (self.last_price_lots as f64 - twap)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.base_deposit_total == 0
             && self.quote_deposit_total == 0
             && self.fees_available == 0
+            && self.base_fees_available == 0
             && self.referrer_rebates_accrued == 0
     }
 
@@ -212,6 +316,24 @@ impl Market {
             .unwrap()
     }
 
+    /// Is `price_lots` a multiple of the market's tick size?
+    pub fn is_valid_tick(&self, price_lots: i64) -> bool {
+        self.tick_size_lots <= 1 || price_lots % self.tick_size_lots == 0
+    }
+
+    /// Does an order of `base_lots` at `price_lots` meet the market's minimum order size?
+    pub fn is_above_min_size(&self, base_lots: i64, price_lots: i64) -> bool {
+        base_lots >= self.min_base_lots
+            && (base_lots as i128) * (price_lots as i128) >= self.min_quote_lots as i128
+    }
+
+    /// Smallest number of base lots meeting the market's minimum order size at `price_lots`
+    pub fn min_base_lots_at(&self, price_lots: i64) -> i64 {
+        let price_lots = price_lots.max(1) as i128;
+        let min_base_lots_by_quote = (self.min_quote_lots as i128 + price_lots - 1) / price_lots;
+        self.min_base_lots.max(min_base_lots_by_quote as i64)
+    }
+
     /// Convert from the price stored on the book to the price used in value calculations
     pub fn lot_to_native_price(&self, price: i64) -> I80F48 {
         I80F48::from_num(price) * I80F48::from_num(self.quote_lot_size)
@@ -227,48 +349,97 @@ impl Market {
     }
 
     pub fn oracle_price_lots(
-        &self,
+        &mut self,
         oracle_a_acc: Option<&impl KeyedAccountReader>,
         oracle_b_acc: Option<&impl KeyedAccountReader>,
+        now_ts: u64,
         slot: u64,
     ) -> Result<Option<i64>> {
-        let oracle_price = self.oracle_price(oracle_a_acc, oracle_b_acc, slot)?;
+        let oracle_price = self.oracle_price(oracle_a_acc, oracle_b_acc, now_ts, slot)?;
         match oracle_price {
             Some(p) => Ok(Some(self.native_price_to_lot(p)?)),
             None => Ok(None),
         }
     }
 
+    /// The native/native oracle price, None if the oracles are stale, not confident
+    /// enough or too far from the oracle TWAP.
+    ///
+    /// Prices passing the staleness and confidence checks are folded into the TWAP.
     pub fn oracle_price(
-        &self,
+        &mut self,
         oracle_a_acc: Option<&impl KeyedAccountReader>,
         oracle_b_acc: Option<&impl KeyedAccountReader>,
+        now_ts: u64,
         slot: u64,
     ) -> Result<Option<I80F48>> {
-        if self.oracle_a.is_some() && self.oracle_b.is_some() {
-            self.oracle_price_from_a_and_b(oracle_a_acc.unwrap(), oracle_b_acc.unwrap(), slot)
+        let oracle_price = if self.oracle_a.is_some() && self.oracle_b.is_some() {
+            self.oracle_price_from_a_and_b(
+                oracle_a_acc.unwrap(),
+                oracle_b_acc.unwrap(),
+                now_ts,
+                slot,
+            )?
         } else if self.oracle_a.is_some() {
-            self.oracle_price_from_a(oracle_a_acc.unwrap(), slot)
+            self.oracle_price_from_a(oracle_a_acc.unwrap(), now_ts, slot)?
         } else {
-            Ok(None)
-        }
+            None
+        };
+
+        Ok(oracle_price.filter(|price| self.update_oracle_twap(price.to_num(), now_ts)))
+    }
+
+    /// Fold `price` into the oracle TWAP, returns false if it deviates too far from
+    /// the TWAP before the update.
+    ///
+    /// A rejected price is folded in clamped to the accepted range, so a lasting price
+    /// change gets accepted again once the TWAP caught up with it, while a single
+    /// outlier can't move the TWAP further than the accepted range.
+    fn update_oracle_twap(&mut self, price: f64, now_ts: u64) -> bool {
+        let max_deviation = self.oracle_config.max_twap_deviation;
+        let twap = self.oracle_twap_price;
+
+        let is_valid =
+            max_deviation <= 0f64 || twap <= 0f64 || (price - twap).abs() <= max_deviation * twap;
+        let folded_price = if is_valid {
+            price
+        } else {
+            msg!(
+                "Oracle price too far from its twap; price: {}, twap: {}, max_twap_deviation: {}",
+                price,
+                twap,
+                max_deviation,
+            );
+            price.clamp(twap * (1f64 - max_deviation), twap * (1f64 + max_deviation))
+        };
+
+        self.oracle_twap_price = if twap > 0f64 {
+            let elapsed = now_ts.saturating_sub(self.oracle_twap_last_update_ts);
+            let weight = (elapsed as f64 / oracle::ORACLE_TWAP_SECONDS as f64).min(1f64);
+            twap + weight * (folded_price - twap)
+        } else {
+            price
+        };
+        self.oracle_twap_last_update_ts = now_ts;
+
+        is_valid
     }
 
     fn oracle_price_from_a(
         &self,
         oracle_acc: &impl KeyedAccountReader,
+        now_ts: u64,
         now_slot: u64,
     ) -> Result<Option<I80F48>> {
         assert_eq!(self.oracle_a, *oracle_acc.key());
-        let oracle = oracle::oracle_state_unchecked(oracle_acc)?;
+        let oracle = oracle::oracle_state_unchecked(oracle_acc, now_ts)?;
 
-        if oracle.is_stale(oracle_acc.key(), &self.oracle_config, now_slot)
+        if oracle.is_stale(oracle_acc.key(), &self.oracle_config, now_ts, now_slot)
             || !oracle.has_valid_confidence(oracle_acc.key(), &self.oracle_config)
         {
             Ok(None)
         } else {
-            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
-            let decimal_adj = oracle::power_of_ten_float(decimals);
+            let decimal_adj = self.oracle_decimal_adj(oracle.oracle_type);
             Ok(I80F48::checked_from_num(oracle.price * decimal_adj))
         }
     }
@@ -277,33 +448,110 @@ impl Market {
         &self,
         oracle_a_acc: &impl KeyedAccountReader,
         oracle_b_acc: &impl KeyedAccountReader,
+        now_ts: u64,
         now_slot: u64,
     ) -> Result<Option<I80F48>> {
         assert_eq!(self.oracle_a, *oracle_a_acc.key());
         assert_eq!(self.oracle_b, *oracle_b_acc.key());
 
-        let oracle_a = oracle::oracle_state_unchecked(oracle_a_acc)?;
-        let oracle_b = oracle::oracle_state_unchecked(oracle_b_acc)?;
+        let oracle_a = oracle::oracle_state_unchecked(oracle_a_acc, now_ts)?;
+        let oracle_b = oracle::oracle_state_unchecked(oracle_b_acc, now_ts)?;
+
+        if oracle_a.oracle_type == oracle::OracleType::OpenBookBookSide {
+            return Ok(self.oracle_price_from_book(
+                oracle_a_acc.key(),
+                &oracle_a,
+                &oracle_b,
+                now_ts,
+                now_slot,
+            ));
+        }
 
-        if oracle_a.is_stale(oracle_a_acc.key(), &self.oracle_config, now_slot)
-            || oracle_b.is_stale(oracle_b_acc.key(), &self.oracle_config, now_slot)
+        if oracle_a.is_stale(oracle_a_acc.key(), &self.oracle_config, now_ts, now_slot)
+            || oracle_b.is_stale(oracle_b_acc.key(), &self.oracle_config, now_ts, now_slot)
             || !oracle_a.has_valid_combined_confidence(&oracle_b, &self.oracle_config)
         {
             Ok(None)
         } else {
             let price = oracle_a.price / oracle_b.price;
-            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
-            let decimal_adj = oracle::power_of_ten_float(decimals);
+            let decimal_adj = self.oracle_decimal_adj(oracle_a.oracle_type);
             Ok(I80F48::checked_from_num(price * decimal_adj))
         }
     }
 
+    /// Mid of the best bid and ask of another market's book, `bids` and `asks` being
+    /// their oracle states. Both orders count for the staleness and the spread for the
+    /// confidence.
+    fn oracle_price_from_book(
+        &self,
+        bids_pk: &Pubkey,
+        bids: &oracle::OracleState,
+        asks: &oracle::OracleState,
+        now_ts: u64,
+        now_slot: u64,
+    ) -> Option<I80F48> {
+        if bids.price <= 0f64 || asks.price <= 0f64 {
+            msg!("Oracle book is one sided; bids: {}", bids_pk);
+            return None;
+        }
+        if asks.price < bids.price {
+            msg!("Oracle book is crossed; bids: {}", bids_pk);
+            return None;
+        }
+
+        let book = oracle::OracleState {
+            price: (bids.price + asks.price) / 2f64,
+            deviation: (asks.price - bids.price) / 2f64,
+            last_update_slot: u64::MAX,
+            last_update_ts: bids.last_update_ts.min(asks.last_update_ts),
+            oracle_type: oracle::OracleType::OpenBookBookSide,
+        };
+        if book.is_stale(bids_pk, &self.oracle_config, now_ts, now_slot)
+            || !book.has_valid_confidence(bids_pk, &self.oracle_config)
+        {
+            return None;
+        }
+
+        // The book is in the price lots of this market
+        I80F48::checked_from_num(book.price)?
+            .checked_mul(I80F48::from_num(self.quote_lot_size))?
+            .checked_div(I80F48::from_num(self.base_lot_size))
+    }
+
+    /// Factor from an oracle's price to the native/native price
+    fn oracle_decimal_adj(&self, oracle_type: oracle::OracleType) -> f64 {
+        if oracle_type.is_native_price() {
+            1f64
+        } else {
+            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
+            oracle::power_of_ten_float(decimals)
+        }
+    }
+
     pub fn subtract_taker_fees(&self, quote: i64) -> i64 {
         ((quote as i128) * FEES_SCALE_FACTOR / (FEES_SCALE_FACTOR + (self.taker_fee as i128)))
             .try_into()
             .unwrap()
     }
 
+    /// Smallest number of lots of `lot_size` still worth `lots` once the taker fees are
+    /// taken out of them. None if the taker fees take everything.
+    pub fn add_taker_fees_ceil(&self, lots: i64, lot_size: i64) -> Option<i64> {
+        let kept_fee_scale = FEES_SCALE_FACTOR - self.taker_fee as i128;
+        if kept_fee_scale <= 0 {
+            return None;
+        }
+        let target_native = lots as i128 * lot_size as i128;
+        let lot_size = lot_size as i128;
+        let mut gross_lots = (target_native * FEES_SCALE_FACTOR + kept_fee_scale * lot_size - 1)
+            / (kept_fee_scale * lot_size);
+        // The fees are rounded up to the next native unit
+        while gross_lots * lot_size - self.taker_fees_ceil(gross_lots * lot_size) < target_native {
+            gross_lots += 1;
+        }
+        gross_lots.try_into().ok()
+    }
+
     pub fn maker_fees_floor(self, amount: u64) -> u64 {
         if self.maker_fee.is_positive() {
             self.unsigned_maker_fees_floor(amount)
@@ -362,3 +610,57 @@ macro_rules! market_seeds {
     };
 }
 pub(crate) use market_seeds;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn test_oracle_twap_outlier() {
+        let mut market = Market::zeroed();
+        market.oracle_config.max_twap_deviation = 0.1;
+        let twap_seconds = oracle::ORACLE_TWAP_SECONDS;
+
+        // the first price starts the twap
+        assert!(market.update_oracle_twap(1.0, 1_000));
+        assert_eq!(market.oracle_twap_price, 1.0);
+
+        // a single outlier after a quiet period is rejected and only moves the twap
+        // to the edge of the accepted range
+        let now_ts = 1_000 + 2 * twap_seconds;
+        assert!(!market.update_oracle_twap(2.0, now_ts));
+        assert!((market.oracle_twap_price - 1.1).abs() < 1e-12);
+        assert!(!market.update_oracle_twap(2.0, now_ts));
+
+        // prices within range of the twap are accepted again
+        assert!(market.update_oracle_twap(1.05, now_ts + 1));
+
+        // a lasting price change gets accepted once the twap caught up with it
+        let mut now_ts = now_ts + 1;
+        let mut updates = 0;
+        while !market.update_oracle_twap(2.0, now_ts) {
+            now_ts += twap_seconds;
+            updates += 1;
+        }
+        assert_eq!(updates, 7);
+    }
+
+    #[test]
+    fn test_add_taker_fees_ceil() {
+        let mut market = Market::zeroed();
+        assert_eq!(market.add_taker_fees_ceil(15_000, 10), Some(15_000));
+
+        market.taker_fee = 400;
+        for (lots, lot_size) in [(15_000, 10), (10_000, 10), (1, 1), (7, 100), (123_457, 1)] {
+            let gross_lots = market.add_taker_fees_ceil(lots, lot_size).unwrap();
+            let net = |lots: i64| lots * lot_size - market.taker_fees_ceil(lots * lot_size);
+            assert!(net(gross_lots) >= lots * lot_size);
+            assert!(net(gross_lots - 1) < lots * lot_size);
+        }
+        assert_eq!(market.add_taker_fees_ceil(15_000, 10), Some(15_007));
+
+        market.taker_fee = FEES_SCALE_FACTOR as i64;
+        assert_eq!(market.add_taker_fees_ceil(1, 1), None);
+    }
+}
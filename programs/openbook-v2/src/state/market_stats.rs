@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+// length of the buckets the market stats are kept in
+pub const MARKET_STATS_BUCKET_SECONDS: u64 = 60 * 60;
+// number of buckets, spanning a day
+pub const MARKET_STATS_BUCKETS: usize = 24;
+
+/// Rolling 24 hour trade statistics of a market, in hourly buckets
+///
+/// Created for a market with `create_market_stats`. From then on all the instructions
+/// matching orders of the market need it.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct MarketStats {
+    pub market: Pubkey,
+
+    pub bump: u8,
+    pub padding: [u8; 7],
+
+    /// Timestamp in seconds of the last recorded fill
+    pub last_trade_ts: u64,
+    /// Fills by hour, hour `h` since the epoch being kept in `buckets[h % 24]`
+    pub buckets: [TradeStats; MARKET_STATS_BUCKETS],
+
+    pub reserved: [u8; 64],
+}
+
+const_assert_eq!(
+    size_of::<MarketStats>(),
+    32 + 1 + 7 + 8 + MARKET_STATS_BUCKETS * size_of::<TradeStats>() + 64
+);
+const_assert_eq!(size_of::<MarketStats>(), 688);
+const_assert_eq!(size_of::<MarketStats>() % 8, 0);
+
+impl MarketStats {
+    /// Record a fill of `base_lots` at `price_lots`
+    pub fn record(&mut self, now_ts: u64, price_lots: i64, base_lots: i64) {
+        let hour = now_ts / MARKET_STATS_BUCKET_SECONDS;
+        let last_hour = self.last_trade_ts / MARKET_STATS_BUCKET_SECONDS;
+
+        // The buckets of the hours without fills since the last one still hold older fills
+        let cleared_until = hour.min(last_hour + MARKET_STATS_BUCKETS as u64);
+        for h in (last_hour + 1)..=cleared_until {
+            self.buckets[Self::bucket_index(h)] = TradeStats::default();
+        }
+
+        self.buckets[Self::bucket_index(hour)].record(price_lots, base_lots);
+        self.last_trade_ts = self.last_trade_ts.max(now_ts);
+    }
+
+    /// High, low and volume of the fills of the last 24 hours, in whole hours: the fills
+    /// of the hour `now_ts` is in and of the 23 hours before it.
+    pub fn last_24h(&self, now_ts: u64) -> TradeStats {
+        let hour = now_ts / MARKET_STATS_BUCKET_SECONDS;
+        let last_hour = self.last_trade_ts / MARKET_STATS_BUCKET_SECONDS;
+        (0..MARKET_STATS_BUCKETS as u64)
+            .filter_map(|age| hour.checked_sub(age))
+            // buckets of hours after the last fill are stale, they weren't cleared yet
+            .filter(|&h| h <= last_hour && last_hour - h < MARKET_STATS_BUCKETS as u64)
+            .fold(TradeStats::default(), |stats, h| {
+                stats.merge(&self.buckets[Self::bucket_index(h)])
+            })
+    }
+
+    fn bucket_index(hour: u64) -> usize {
+        (hour % MARKET_STATS_BUCKETS as u64) as usize
+    }
+}
+
+#[zero_copy]
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Default, PartialEq, Eq)]
+pub struct TradeStats {
+    /// Highest fill price in lots, only meaningful with a non-zero volume
+    pub high_price_lots: i64,
+    /// Lowest fill price in lots, only meaningful with a non-zero volume
+    pub low_price_lots: i64,
+    /// Filled base lots
+    pub volume_base_lots: i64,
+}
+const_assert_eq!(size_of::<TradeStats>(), 3 * 8);
+
+impl TradeStats {
+    pub fn record(&mut self, price_lots: i64, base_lots: i64) {
+        if self.volume_base_lots == 0 {
+            self.high_price_lots = price_lots;
+            self.low_price_lots = price_lots;
+        } else {
+            self.high_price_lots = self.high_price_lots.max(price_lots);
+            self.low_price_lots = self.low_price_lots.min(price_lots);
+        }
+        self.volume_base_lots = self.volume_base_lots.saturating_add(base_lots);
+    }
+
+    pub fn merge(&self, other: &TradeStats) -> TradeStats {
+        if self.volume_base_lots == 0 {
+            *other
+        } else if other.volume_base_lots == 0 {
+            *self
+        } else {
+            TradeStats {
+                high_price_lots: self.high_price_lots.max(other.high_price_lots),
+                low_price_lots: self.low_price_lots.min(other.low_price_lots),
+                volume_base_lots: self.volume_base_lots.saturating_add(other.volume_base_lots),
+            }
+        }
+    }
+}
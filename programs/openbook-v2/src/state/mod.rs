@@ -1,13 +1,20 @@
 pub use market::*;
+pub use market_stats::*;
 pub use open_orders_account::*;
 pub use open_orders_indexer::*;
 pub use oracle::*;
 pub use orderbook::*;
+pub use twap_order::*;
 
 mod market;
+mod market_stats;
 mod open_orders_account;
 mod open_orders_indexer;
+mod orca_internal;
 mod orderbook;
 
 pub mod oracle;
+mod raydium_cpmm_internal;
 mod raydium_internal;
+mod switchboard_on_demand_internal;
+mod twap_order;
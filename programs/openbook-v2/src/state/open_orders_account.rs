@@ -135,14 +135,20 @@ impl OpenOrdersAccount {
         let is_self_trade = fill.maker == fill.taker;
 
         let side = fill.taker_side().invert_side();
+        let base_native = (fill.quantity * market.base_lot_size) as u64;
         let quote_native = (fill.quantity * fill.price * market.quote_lot_size) as u64;
+        let fees_in_base = market.has_fees_in_base();
 
-        let (maker_fees, maker_rebate) = if is_self_trade {
-            (0, 0)
+        // Markets with fees in base have no maker fees, their rebates are paid in base
+        let (maker_fees, maker_rebate, maker_rebate_base) = if is_self_trade {
+            (0, 0, 0)
+        } else if fees_in_base {
+            (0, 0, market.maker_rebate_floor(base_native))
         } else {
             (
                 market.maker_fees_floor(quote_native),
                 market.maker_rebate_floor(quote_native),
+                0,
             )
         };
 
@@ -170,11 +176,12 @@ impl OpenOrdersAccount {
 
             match side {
                 Side::Bid => {
-                    pa.base_free_native += (fill.quantity * market.base_lot_size) as u64;
+                    pa.base_free_native += base_native + maker_rebate_base;
                     pa.quote_free_native += maker_rebate + locked_amount_above_fill_price;
                     pa.locked_maker_fees -= locked_maker_fees;
                 }
                 Side::Ask => {
+                    pa.base_free_native += maker_rebate_base;
                     pa.quote_free_native += quote_native + maker_rebate - maker_fees;
                 }
             };
@@ -198,30 +205,37 @@ impl OpenOrdersAccount {
             }
         }
 
-        // Calculate taker fee, ignoring self trades
-        let taker_fee_ceil = if quote_native > 0 && fill.maker != fill.taker {
+        // Calculate taker fee, ignoring self trades and auction matches, which have no taker
+        let taker_fee_ceil = if fill.is_auction() {
+            0
+        } else if fees_in_base && fill.maker != fill.taker {
+            market.taker_fees_ceil(base_native)
+        } else if quote_native > 0 && fill.maker != fill.taker {
             market.taker_fees_ceil(quote_native)
         } else {
             0
         };
 
-        emit_stack(FillLog {
-            market: self.market,
-            taker_side: fill.taker_side,
-            maker_slot: fill.maker_slot,
-            maker_out: fill.maker_out(),
-            timestamp: fill.timestamp,
-            seq_num: fill.market_seq_num,
-            maker: fill.maker,
-            maker_client_order_id: fill.maker_client_order_id,
-            maker_fee: maker_fees,
-            maker_timestamp: fill.maker_timestamp,
-            taker: fill.taker,
-            taker_client_order_id: fill.taker_client_order_id,
-            taker_fee_ceil,
-            price: fill.price,
-            quantity: fill.quantity,
-        });
+        // The mirror event of an auction match leaves logging the fill to the bid's event
+        if !fill.is_auction_mirror() {
+            emit_stack(FillLog {
+                market: self.market,
+                taker_side: fill.taker_side,
+                maker_slot: fill.maker_slot,
+                maker_out: fill.maker_out(),
+                timestamp: fill.timestamp,
+                seq_num: fill.market_seq_num,
+                maker: fill.maker,
+                maker_client_order_id: fill.maker_client_order_id,
+                maker_fee: maker_fees,
+                maker_timestamp: fill.maker_timestamp,
+                taker: fill.taker,
+                taker_client_order_id: fill.taker_client_order_id,
+                taker_fee_ceil,
+                price: fill.price,
+                quantity: fill.quantity,
+            });
+        }
 
         let pa = &self.position;
         emit_stack(OpenOrdersPositionLog {
@@ -241,6 +255,9 @@ impl OpenOrdersAccount {
     }
 
     /// Release funds and apply taker fees to the taker account. Account fees for referrer
+    ///
+    /// Base taker fees of asks are paid with the base the ask locks, like quote fees of bids.
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_taker(
         &mut self,
         market: &mut Market,
@@ -248,11 +265,12 @@ impl OpenOrdersAccount {
         base_native: u64,
         quote_native: u64,
         taker_fees: u64,
+        taker_fees_base: u64,
         referrer_amount: u64,
     ) {
         let pa = &mut self.position;
         match taker_side {
-            Side::Bid => pa.base_free_native += base_native,
+            Side::Bid => pa.base_free_native += base_native - taker_fees_base,
             Side::Ask => pa.quote_free_native += quote_native - taker_fees,
         };
 
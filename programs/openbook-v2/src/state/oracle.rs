@@ -8,8 +8,14 @@ use switchboard_solana::AggregatorAccountData;
 
 use crate::accounts_zerocopy::*;
 use crate::error::*;
+use crate::state::orca_internal;
+use crate::state::orca_internal::Whirlpool;
+use crate::state::raydium_cpmm_internal;
 use crate::state::raydium_internal;
 use crate::state::raydium_internal::PoolState;
+use crate::state::switchboard_on_demand_internal;
+use crate::state::switchboard_on_demand_internal::PullFeedAccountData;
+use crate::state::{BookSide, Market};
 
 const DECIMAL_CONSTANT_ZERO_INDEX: i8 = 12;
 const DECIMAL_CONSTANTS_F64: [f64; 25] = [
@@ -21,6 +27,10 @@ pub const fn power_of_ten_float(decimals: i8) -> f64 {
     DECIMAL_CONSTANTS_F64[(decimals + DECIMAL_CONSTANT_ZERO_INDEX) as usize]
 }
 
+/// Time the oracle and fill TWAPs average over, see `Market::oracle_price` and
+/// `Market::fill_twap`
+pub const ORACLE_TWAP_SECONDS: u64 = 30 * 60;
+
 pub mod switchboard_v1_devnet_oracle {
     use solana_program::declare_id;
     declare_id!("7azgmy1pFXHikv36q1zZASvFq5vFa39TT9NweVugKKTU");
@@ -35,10 +45,16 @@ pub mod switchboard_v2_mainnet_oracle {
 pub struct OracleConfig {
     pub conf_filter: f64,
     pub max_staleness_slots: i64,
-    pub reserved: [u8; 72],
+    /// Disabled if not positive, so that markets created before it was carved out
+    /// of `reserved` keep it off. Checked against oracles that report their update time.
+    pub max_staleness_seconds: i64,
+    /// Largest accepted deviation of the oracle price from `Market::oracle_twap_price`,
+    /// relative to it. Disabled if zero.
+    pub max_twap_deviation: f64,
+    pub reserved: [u8; 40],
 }
-const_assert_eq!(size_of::<OracleConfig>(), 8 + 8 + 72);
-const_assert_eq!(size_of::<OracleConfig>(), 88);
+const_assert_eq!(size_of::<OracleConfig>(), 8 + 8 + 8 + 8 + 40);
+const_assert_eq!(size_of::<OracleConfig>(), 72);
 const_assert_eq!(size_of::<OracleConfig>() % 8, 0);
 
 #[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
@@ -48,6 +64,10 @@ pub struct OracleConfigParams {
     pub conf_filter: f32,
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub max_staleness_slots: Option<u32>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub max_staleness_seconds: Option<u32>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub max_twap_deviation: Option<f32>,
 }
 
 impl OracleConfigParams {
@@ -55,7 +75,9 @@ impl OracleConfigParams {
         OracleConfig {
             conf_filter: self.conf_filter as f64,
             max_staleness_slots: self.max_staleness_slots.map(|v| v as i64).unwrap_or(-1),
-            reserved: [0; 72],
+            max_staleness_seconds: self.max_staleness_seconds.map(|v| v as i64).unwrap_or(0),
+            max_twap_deviation: self.max_twap_deviation.map(|v| v as f64).unwrap_or(0f64),
+            reserved: [0; 40],
         }
     }
 }
@@ -67,17 +89,53 @@ pub enum OracleType {
     SwitchboardV1,
     SwitchboardV2,
     RaydiumCLMM,
+    /// Another OpenBook market, priced at the time weighted average of its fills
+    OpenBookMarket,
+    /// Priced as token b per token a, so a market using it needs token a as its base
+    ///
+    /// The pool price moves with every swap, so a flash loan can push it anywhere
+    /// within one transaction. Markets using it need a max twap deviation.
+    OrcaWhirlpool,
+    /// A token vault of a pool, only usable as a pair: the base vault as oracle a and
+    /// the quote vault as oracle b give the quote reserve per base reserve
+    ///
+    /// The vault balances include the pool's unclaimed fees, slightly overstating reserves.
+    /// Like pool prices, they can be moved by swaps, flash loans and plain donations
+    /// within one transaction, so markets using them need a max twap deviation.
+    RaydiumCPMM,
+    SwitchboardOnDemand,
+    /// A book side of another OpenBook market, only usable as a pair: its bids as
+    /// oracle a and its asks as oracle b give the mid of their best fixed price orders
+    ///
+    /// The prices are in the price lots of that market, which needs the same mints
+    /// and lot sizes as the market using it.
+    OpenBookBookSide,
+}
+
+impl OracleType {
+    /// Pools that don't store their mints' decimals report native instead of ui prices
+    pub fn is_native_price(&self) -> bool {
+        matches!(self, OracleType::OrcaWhirlpool | OracleType::RaydiumCPMM)
+    }
 }
 
 pub struct OracleState {
     pub price: f64,
     pub deviation: f64,
     pub last_update_slot: u64,
+    /// Timestamp of the last update, for oracles that report it
+    pub last_update_ts: Option<u64>,
     pub oracle_type: OracleType,
 }
 
 impl OracleState {
-    pub fn is_stale(&self, oracle_pk: &Pubkey, config: &OracleConfig, now_slot: u64) -> bool {
+    pub fn is_stale(
+        &self,
+        oracle_pk: &Pubkey,
+        config: &OracleConfig,
+        now_ts: u64,
+        now_slot: u64,
+    ) -> bool {
         if config.max_staleness_slots >= 0
             && self
                 .last_update_slot
@@ -91,6 +149,28 @@ impl OracleState {
                 self.last_update_slot,
                 now_slot,
             );
+            return true;
+        }
+
+        let Some(last_update_ts) = self.last_update_ts else {
+            return false;
+        };
+        let max_staleness_seconds = if config.max_staleness_seconds > 0 {
+            config.max_staleness_seconds
+        } else if self.last_update_slot == u64::MAX && config.max_staleness_slots >= 0 {
+            // Oracles without a slot fall back to the slot staleness, at the 400ms target slot time
+            config.max_staleness_slots * 2 / 5
+        } else {
+            return false;
+        };
+        if last_update_ts.saturating_add(max_staleness_seconds as u64) < now_ts {
+            msg!(
+                "Oracle is stale; pubkey {}, price: {}, last_update_ts: {}, now_ts: {}",
+                oracle_pk,
+                self.price,
+                last_update_ts,
+                now_ts,
+            );
             true
         } else {
             false
@@ -156,6 +236,10 @@ pub fn determine_oracle_type(acc_info: &impl KeyedAccountReader) -> Result<Oracl
         return Ok(OracleType::Pyth);
     } else if data[0..8] == StubOracle::discriminator() {
         return Ok(OracleType::Stub);
+    } else if data[0..8] == Market::discriminator() && acc_info.owner() == &crate::ID {
+        return Ok(OracleType::OpenBookMarket);
+    } else if data[0..8] == BookSide::discriminator() && acc_info.owner() == &crate::ID {
+        return Ok(OracleType::OpenBookBookSide);
     }
     // https://github.com/switchboard-xyz/switchboard-v2/blob/main/libraries/rs/src/aggregator.rs#L114
     // note: disc is not public, hence the copy pasta
@@ -169,11 +253,79 @@ pub fn determine_oracle_type(acc_info: &impl KeyedAccountReader) -> Result<Oracl
         return Ok(OracleType::SwitchboardV1);
     } else if acc_info.owner() == &raydium_internal::ID {
         return Ok(OracleType::RaydiumCLMM);
+    } else if acc_info.owner() == &orca_internal::ID && data[0..8] == Whirlpool::discriminator() {
+        return Ok(OracleType::OrcaWhirlpool);
+    } else if acc_info.owner() == &anchor_spl::token::ID
+        && data.len() >= 72
+        && data[32..64] == raydium_cpmm_internal::vault_authority().to_bytes()
+    {
+        return Ok(OracleType::RaydiumCPMM);
+    } else if acc_info.owner() == &switchboard_on_demand_internal::ID
+        && data[0..8] == PullFeedAccountData::discriminator()
+    {
+        return Ok(OracleType::SwitchboardOnDemand);
     }
 
     Err(OpenBookError::UnknownOracleType.into())
 }
 
+/// Checks that whirlpool oracles price the base mint in the quote mint
+///
+/// A single pool needs the base mint as token a and the quote mint as token b. A pair
+/// needs them as token a of oracle a and of oracle b, with a common token b.
+pub fn validate_whirlpool_oracles<T: KeyedAccountReader>(
+    oracle_a: &T,
+    oracle_b: Option<&T>,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Result<()> {
+    let mints = |acc_info: &T| {
+        let pool =
+            bytemuck::from_bytes::<Whirlpool>(&acc_info.data()[8..8 + size_of::<Whirlpool>()]);
+        (pool.token_mint_a, pool.token_mint_b)
+    };
+    let (mint_a, mint_b) = mints(oracle_a);
+    let valid = match oracle_b {
+        None => mint_a == *base_mint && mint_b == *quote_mint,
+        Some(oracle_b) => {
+            let (other_mint_a, other_mint_b) = mints(oracle_b);
+            mint_a == *base_mint && other_mint_a == *quote_mint && mint_b == other_mint_b
+        }
+    };
+    require!(valid, OpenBookError::InvalidInputPoolOracle);
+    Ok(())
+}
+
+/// Checks that vault oracles are the base and quote vaults of the same Raydium CPMM pool
+pub fn validate_vault_oracles(
+    base_vault: &impl KeyedAccountReader,
+    quote_vault: &impl KeyedAccountReader,
+    pool: &impl KeyedAccountReader,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Result<()> {
+    let data = pool.data();
+    require!(
+        pool.owner() == &raydium_cpmm_internal::ID
+            && data.len() >= 8 + size_of::<raydium_cpmm_internal::PoolState>()
+            && data[0..8] == raydium_cpmm_internal::PoolState::discriminator(),
+        OpenBookError::InvalidInputVaultOracle
+    );
+    let pool = bytemuck::from_bytes::<raydium_cpmm_internal::PoolState>(
+        &data[8..8 + size_of::<raydium_cpmm_internal::PoolState>()],
+    );
+    let vaults = [pool.token_0_vault, pool.token_1_vault];
+    // A token account starts with its mint
+    require!(
+        vaults.contains(base_vault.key())
+            && vaults.contains(quote_vault.key())
+            && base_vault.data()[0..32] == base_mint.to_bytes()
+            && quote_vault.data()[0..32] == quote_mint.to_bytes(),
+        OpenBookError::InvalidInputVaultOracle
+    );
+    Ok(())
+}
+
 /// Get the pyth agg price if it's available, otherwise take the prev price.
 ///
 /// Returns the publish slot in addition to the price info.
@@ -206,29 +358,45 @@ fn pyth_get_price(
     }
 }
 
+/// Confidence proxy for pool prices, which have no deviation of their own
+///
+/// The deviation relative to the price shrinks with the square root of the pool's depth
+/// in native tokens, so a conf_filter of 0.001 asks for a depth of at least 1e6.
+fn pool_deviation(price: f64, reserve: f64) -> f64 {
+    if reserve > 0f64 {
+        price / reserve.sqrt()
+    } else {
+        f64::INFINITY
+    }
+}
+
 /// Returns the price of one native base token, in native quote tokens
 ///
 /// Example: The for SOL at 40 USDC/SOL it would return 0.04 (the unit is USDC-native/SOL-native)
 ///
 /// The staleness and confidence of the oracle is not checked. Use the functions on
 /// OracleState to validate them if needed. That's why this function is called _unchecked.
-pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<OracleState> {
+pub fn oracle_state_unchecked(
+    acc_info: &impl KeyedAccountReader,
+    now_ts: u64,
+) -> Result<OracleState> {
     let data = &acc_info.data();
     let oracle_type = determine_oracle_type(acc_info)?;
 
     Ok(match oracle_type {
         OracleType::Stub => {
             let stub = acc_info.load::<StubOracle>()?;
-            let last_update_slot = if stub.last_update_slot == 0 {
+            let (last_update_slot, last_update_ts) = if stub.last_update_slot == 0 {
                 // ensure staleness checks will never fail
-                u64::MAX
+                (u64::MAX, None)
             } else {
-                stub.last_update_slot
+                (stub.last_update_slot, Some(stub.last_update_ts as u64))
             };
             OracleState {
                 price: stub.price,
                 last_update_slot,
                 deviation: stub.deviation,
+                last_update_ts,
                 oracle_type: OracleType::Stub,
             }
         }
@@ -245,6 +413,7 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
                 price,
                 last_update_slot,
                 deviation,
+                last_update_ts: Some(price_data.publish_time as u64),
                 oracle_type: OracleType::Pyth,
             }
         }
@@ -271,6 +440,7 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
                 price,
                 last_update_slot,
                 deviation,
+                last_update_ts: Some(feed.latest_confirmed_round.round_open_timestamp as u64),
                 oracle_type: OracleType::SwitchboardV2,
             }
         }
@@ -285,6 +455,7 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
                 price,
                 last_update_slot,
                 deviation,
+                last_update_ts: None,
                 oracle_type: OracleType::SwitchboardV1,
             }
         }
@@ -302,9 +473,112 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
                 price,
                 last_update_slot: u64::MAX, // ensure staleness slot will never fail
                 deviation: 0f64,
+                last_update_ts: None,
                 oracle_type: OracleType::RaydiumCLMM,
             }
         }
+        OracleType::OpenBookMarket => {
+            let market = acc_info.load::<Market>()?;
+            let twap_lots = market.fill_twap(now_ts);
+
+            // Like the other oracles, this is the ui price
+            let decimal_adj =
+                power_of_ten_float((market.base_decimals as i8) - (market.quote_decimals as i8));
+            let ui_price = |price_lots: f64| {
+                price_lots * market.quote_lot_size as f64 / market.base_lot_size as f64
+                    * decimal_adj
+            };
+
+            // The last fill moving away from the average makes it less reliable, and
+            // without fills there's no price to speak of
+            let deviation = if twap_lots > 0f64 {
+                ui_price((market.last_price_lots as f64 - twap_lots).abs())
+            } else {
+                f64::INFINITY
+            };
+
+            OracleState {
+                price: ui_price(twap_lots),
+                last_update_slot: u64::MAX, // staleness is checked by the last fill instead
+                deviation,
+                last_update_ts: Some(market.last_trade_ts),
+                oracle_type: OracleType::OpenBookMarket,
+            }
+        }
+        OracleType::OrcaWhirlpool => {
+            let pool = bytemuck::from_bytes::<Whirlpool>(&data[8..8 + size_of::<Whirlpool>()]);
+
+            let sqrt_price = U64F64::from_bits(pool.sqrt_price).to_num::<f64>();
+            let price = sqrt_price * sqrt_price;
+
+            // Virtual reserves of the in range liquidity
+            let liquidity = pool.liquidity as f64;
+            let reserve_a = liquidity / sqrt_price;
+            let reserve_b = liquidity * sqrt_price;
+
+            require_gte!(price, 0f64);
+            OracleState {
+                price,
+                last_update_slot: u64::MAX, // ensure staleness slot will never fail
+                deviation: pool_deviation(price, reserve_a.min(reserve_b)),
+                last_update_ts: None,
+                oracle_type: OracleType::OrcaWhirlpool,
+            }
+        }
+        OracleType::RaydiumCPMM => {
+            let reserve = u64::from_le_bytes(data[64..72].try_into().unwrap()) as f64;
+
+            // Dividing the base vault's price by the quote vault's gives quote per base
+            let price = if reserve > 0f64 { 1f64 / reserve } else { 0f64 };
+
+            OracleState {
+                price,
+                last_update_slot: u64::MAX, // ensure staleness slot will never fail
+                deviation: pool_deviation(price, reserve),
+                last_update_ts: None,
+                oracle_type: OracleType::RaydiumCPMM,
+            }
+        }
+        OracleType::SwitchboardOnDemand => {
+            let feed = bytemuck::from_bytes::<PullFeedAccountData>(
+                &data[8..8 + size_of::<PullFeedAccountData>()],
+            );
+            let result = feed.result;
+
+            // beyond the range of power_of_ten_float
+            let decimal_adj = 10f64.powi(-(switchboard_on_demand_internal::PRECISION as i32));
+            let price = result.value as f64 * decimal_adj;
+            let deviation = result.std_dev as f64 * decimal_adj;
+            let last_update_slot = result.slot;
+
+            require_gte!(price, 0f64);
+            OracleState {
+                price,
+                last_update_slot,
+                deviation,
+                last_update_ts: Some(feed.last_update_timestamp as u64),
+                oracle_type: OracleType::SwitchboardOnDemand,
+            }
+        }
+        OracleType::OpenBookBookSide => {
+            let book_side = acc_info.load::<BookSide>()?;
+
+            // Oracle pegged orders would need the other market's oracle, they are skipped
+            let (price_lots, timestamp) = book_side
+                .iter_valid(now_ts, None)
+                .next()
+                .map_or((0, None), |best| {
+                    (best.price_lots, Some(best.node.timestamp))
+                });
+
+            OracleState {
+                price: price_lots as f64,
+                last_update_slot: u64::MAX, // staleness is checked by the order's timestamp
+                deviation: 0f64,
+                last_update_ts: timestamp,
+                oracle_type: OracleType::OpenBookBookSide,
+            }
+        }
     })
 }
 
@@ -358,6 +632,73 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_switchboard_on_demand_price() -> Result<()> {
+        let mut feed = PullFeedAccountData::default();
+        feed.result.value = 152_500_000_000_000_000_000;
+        feed.result.std_dev = 50_000_000_000_000_000;
+        feed.result.slot = 1234;
+
+        let mut file_data = PullFeedAccountData::discriminator().to_vec();
+        file_data.extend_from_slice(bytemuck::bytes_of(&feed));
+        // rest of the account
+        file_data.extend_from_slice(&[0; 1280]);
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &switchboard_on_demand_internal::ID,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai).unwrap() == OracleType::SwitchboardOnDemand);
+
+        let oracle = oracle_state_unchecked(ai, 0)?;
+        assert!((oracle.price - 152.5).abs() < 1e-10);
+        assert!((oracle.deviation - 0.05).abs() < 1e-12);
+
+        let config = OracleConfigParams {
+            conf_filter: 0.001,
+            max_staleness_slots: Some(10),
+            max_staleness_seconds: None,
+            max_twap_deviation: None,
+        }
+        .to_oracle_config();
+        assert!(oracle.has_valid_confidence(ai.key, &config));
+        assert!(!oracle.is_stale(ai.key, &config, 0, 1244));
+        assert!(oracle.is_stale(ai.key, &config, 0, 1245));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_staleness_seconds_off_for_existing_markets() {
+        // config of a market created before max_staleness_seconds and max_twap_deviation
+        // were taken from the reserved bytes
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0.1f64.to_le_bytes());
+        bytes.extend_from_slice(&100i64.to_le_bytes());
+        bytes.extend_from_slice(&[0; 56]);
+        let config: OracleConfig = bytemuck::pod_read_unaligned(&bytes);
+        assert_eq!(config.max_staleness_slots, 100);
+        assert_eq!(config.max_staleness_seconds, 0);
+        assert_eq!(config.max_twap_deviation, 0f64);
+
+        let oracle = OracleState {
+            price: 1.0,
+            deviation: 0.0,
+            last_update_slot: 1000,
+            last_update_ts: Some(1_700_000_000),
+            oracle_type: OracleType::Pyth,
+        };
+        let pk = Pubkey::default();
+        assert!(!oracle.is_stale(&pk, &config, 1_700_003_600, 1100));
+        assert!(oracle.is_stale(&pk, &config, 1_700_003_600, 1101));
+
+        let mut config = config;
+        config.max_staleness_seconds = 60;
+        assert!(!oracle.is_stale(&pk, &config, 1_700_000_060, 1100));
+        assert!(oracle.is_stale(&pk, &config, 1_700_000_061, 1100));
+    }
+
     #[test]
     pub fn test_raydium_price() -> Result<()> {
         let filename = format!(
@@ -373,7 +714,7 @@ mod tests {
             data: data.borrow(),
         };
 
-        let oracle = oracle_state_unchecked(ai)?;
+        let oracle = oracle_state_unchecked(ai, 0)?;
 
         let price_from_raydium_sdk = 24.470_087_964_273_85f64;
         println!("{:?}", oracle.price);
@@ -382,6 +723,221 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_openbook_market_price() -> Result<()> {
+        let mut market: Market = bytemuck::Zeroable::zeroed();
+        market.base_decimals = 6;
+        market.quote_decimals = 6;
+        market.quote_lot_size = 1;
+        market.base_lot_size = 1;
+
+        let oracle_state = |market: &Market, now_ts: u64| {
+            let mut file_data = Market::discriminator().to_vec();
+            file_data.extend_from_slice(bytemuck::bytes_of(market));
+            let data = RefCell::new(&mut file_data[..]);
+            let ai = &AccountInfoRef {
+                key: &Pubkey::default(),
+                owner: &crate::ID,
+                data: data.borrow(),
+            };
+            assert!(determine_oracle_type(ai).unwrap() == OracleType::OpenBookMarket);
+            oracle_state_unchecked(ai, now_ts)
+        };
+
+        let config = OracleConfigParams {
+            conf_filter: 0.1,
+            max_staleness_slots: Some(100),
+            max_staleness_seconds: None,
+            max_twap_deviation: None,
+        }
+        .to_oracle_config();
+
+        // Without fills there is no price
+        let oracle = oracle_state(&market, 1000)?;
+        assert!(!oracle.has_valid_confidence(&Pubkey::default(), &config));
+
+        market.record_trade(None, 1000, 100, 1);
+        let oracle = oracle_state(&market, 1000)?;
+        assert_eq!(oracle.price, 100.0);
+        assert_eq!(oracle.deviation, 0.0);
+        assert!(oracle.has_valid_confidence(&Pubkey::default(), &config));
+        // 100 slots are 40 seconds after the last fill
+        assert!(!oracle.is_stale(&Pubkey::default(), &config, 1040, 0));
+        assert!(oracle.is_stale(&Pubkey::default(), &config, 1041, 0));
+
+        // A fill far off only moves the average as time passes at its price
+        market.record_trade(None, 1000, 200, 1);
+        let oracle = oracle_state(&market, 1000)?;
+        assert_eq!(oracle.price, 100.0);
+        assert_eq!(oracle.deviation, 100.0);
+        assert!(!oracle.has_valid_confidence(&Pubkey::default(), &config));
+
+        let half_twap = 1000 + ORACLE_TWAP_SECONDS / 2;
+        assert_eq!(oracle_state(&market, half_twap)?.price, 150.0);
+        assert_eq!(
+            oracle_state(&market, 1000 + ORACLE_TWAP_SECONDS)?.price,
+            200.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_pool_prices() -> Result<()> {
+        let whirlpool = Whirlpool {
+            sqrt_price: 2 << 64,
+            liquidity: 1_000_000_000,
+            ..Whirlpool::default()
+        };
+        let mut file_data = Whirlpool::discriminator().to_vec();
+        file_data.extend_from_slice(bytemuck::bytes_of(&whirlpool));
+        // rest of the account
+        file_data.extend_from_slice(&[0; 384]);
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &orca_internal::ID,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai).unwrap() == OracleType::OrcaWhirlpool);
+
+        let oracle = oracle_state_unchecked(ai, 0)?;
+        assert_eq!(oracle.price, 4.0);
+        // token a is the shallower side, with 5e8 in virtual reserves
+        assert!((oracle.deviation / oracle.price - 1.0 / 5e8f64.sqrt()).abs() < 1e-12);
+
+        let vault_oracle = |reserve: u64| {
+            let mut file_data = vec![0u8; 165];
+            file_data[32..64].copy_from_slice(&raydium_cpmm_internal::vault_authority().to_bytes());
+            file_data[64..72].copy_from_slice(&reserve.to_le_bytes());
+            let data = RefCell::new(&mut file_data[..]);
+            let ai = &AccountInfoRef {
+                key: &Pubkey::default(),
+                owner: &anchor_spl::token::ID,
+                data: data.borrow(),
+            };
+            assert!(determine_oracle_type(ai).unwrap() == OracleType::RaydiumCPMM);
+            oracle_state_unchecked(ai, 0)
+        };
+        let base_vault = vault_oracle(1_000_000)?;
+        let quote_vault = vault_oracle(4_000_000)?;
+        assert_eq!(base_vault.price / quote_vault.price, 4.0);
+
+        let config = |conf_filter: f32| {
+            OracleConfigParams {
+                conf_filter,
+                max_staleness_slots: None,
+                max_staleness_seconds: None,
+                max_twap_deviation: None,
+            }
+            .to_oracle_config()
+        };
+        assert!(base_vault.has_valid_combined_confidence(&quote_vault, &config(0.01)));
+        assert!(!base_vault.has_valid_combined_confidence(&quote_vault, &config(0.001)));
+
+        let empty_vault = vault_oracle(0)?;
+        assert!(!empty_vault.has_valid_combined_confidence(&quote_vault, &config(0.01)));
+
+        Ok(())
+    }
+
+    fn account_info<'a, 'info>(
+        key: &'info Pubkey,
+        owner: &'info Pubkey,
+        data: &'a RefCell<&'info mut [u8]>,
+    ) -> AccountInfoRef<'a, 'info> {
+        AccountInfoRef {
+            key,
+            owner,
+            data: data.borrow(),
+        }
+    }
+
+    fn whirlpool_data(token_mint_a: Pubkey, token_mint_b: Pubkey) -> Vec<u8> {
+        let whirlpool = Whirlpool {
+            token_mint_a,
+            token_mint_b,
+            ..Whirlpool::default()
+        };
+        let mut data = Whirlpool::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&whirlpool));
+        data
+    }
+
+    #[test]
+    pub fn test_whirlpool_oracle_validation() {
+        let (base_mint, quote_mint, usdc_mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let key = Pubkey::new_unique();
+
+        let mut pool = whirlpool_data(base_mint, quote_mint);
+        let mut inverted_pool = whirlpool_data(quote_mint, base_mint);
+        let mut base_pool = whirlpool_data(base_mint, usdc_mint);
+        let mut quote_pool = whirlpool_data(quote_mint, usdc_mint);
+        let pool = RefCell::new(&mut pool[..]);
+        let inverted_pool = RefCell::new(&mut inverted_pool[..]);
+        let base_pool = RefCell::new(&mut base_pool[..]);
+        let quote_pool = RefCell::new(&mut quote_pool[..]);
+        let pool = account_info(&key, &orca_internal::ID, &pool);
+        let inverted_pool = account_info(&key, &orca_internal::ID, &inverted_pool);
+        let base_pool = account_info(&key, &orca_internal::ID, &base_pool);
+        let quote_pool = account_info(&key, &orca_internal::ID, &quote_pool);
+
+        let validate = |oracle_a, oracle_b| {
+            validate_whirlpool_oracles(oracle_a, oracle_b, &base_mint, &quote_mint)
+        };
+        assert!(validate(&pool, None).is_ok());
+        assert!(validate(&inverted_pool, None).is_err());
+        assert!(validate(&base_pool, Some(&quote_pool)).is_ok());
+        assert!(validate(&quote_pool, Some(&base_pool)).is_err());
+        assert!(validate(&base_pool, Some(&pool)).is_err());
+    }
+
+    #[test]
+    pub fn test_vault_oracle_validation() {
+        let (base_mint, quote_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (pool_key, base_vault_key, quote_vault_key, foreign_vault_key) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let pool_state = raydium_cpmm_internal::PoolState {
+            token_0_vault: quote_vault_key,
+            token_1_vault: base_vault_key,
+            ..raydium_cpmm_internal::PoolState::default()
+        };
+        let mut pool_data = raydium_cpmm_internal::PoolState::discriminator().to_vec();
+        pool_data.extend_from_slice(bytemuck::bytes_of(&pool_state));
+        let pool_data = RefCell::new(&mut pool_data[..]);
+        let pool = account_info(&pool_key, &raydium_cpmm_internal::ID, &pool_data);
+        let fake_pool = account_info(&pool_key, &anchor_spl::token::ID, &pool_data);
+
+        // token accounts start with their mint
+        let mut base_vault_data = vec![0u8; 165];
+        base_vault_data[0..32].copy_from_slice(&base_mint.to_bytes());
+        let mut quote_vault_data = vec![0u8; 165];
+        quote_vault_data[0..32].copy_from_slice(&quote_mint.to_bytes());
+        let base_vault_data = RefCell::new(&mut base_vault_data[..]);
+        let quote_vault_data = RefCell::new(&mut quote_vault_data[..]);
+        let base_vault = account_info(&base_vault_key, &anchor_spl::token::ID, &base_vault_data);
+        let quote_vault = account_info(&quote_vault_key, &anchor_spl::token::ID, &quote_vault_data);
+        let foreign_vault =
+            account_info(&foreign_vault_key, &anchor_spl::token::ID, &base_vault_data);
+
+        let validate = |base_vault, quote_vault, pool| {
+            validate_vault_oracles(base_vault, quote_vault, pool, &base_mint, &quote_mint)
+        };
+        assert!(validate(&base_vault, &quote_vault, &pool).is_ok());
+        assert!(validate(&quote_vault, &base_vault, &pool).is_err());
+        assert!(validate(&foreign_vault, &quote_vault, &pool).is_err());
+        assert!(validate(&base_vault, &quote_vault, &fake_pool).is_err());
+    }
+
     #[test]
     pub fn lookup_test() {
         for idx in -12..0_i8 {
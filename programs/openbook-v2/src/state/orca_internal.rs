@@ -0,0 +1,40 @@
+use anchor_lang::{account, declare_id};
+use solana_program::pubkey::Pubkey;
+
+declare_id!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// The leading fields of a whirlpool, up to the token vaults
+///
+/// The account is larger: read it from `data[8..8 + size_of::<Whirlpool>()]`.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct Whirlpool {
+    pub whirlpools_config: Pubkey,
+    pub whirlpool_bump: [u8; 1],
+
+    pub tick_spacing: u16,
+    pub tick_spacing_seed: [u8; 2],
+
+    /// Stored as hundredths of a basis point
+    pub fee_rate: u16,
+    /// Portion of fee rate taken, stored as basis points
+    pub protocol_fee_rate: u16,
+
+    /// The currently in range liquidity available to the pool
+    pub liquidity: u128,
+    /// The current price of the pool as a sqrt(token_b/token_a) Q64.64 value
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+
+    pub protocol_fee_owed_a: u64,
+    pub protocol_fee_owed_b: u64,
+
+    pub token_mint_a: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub fee_growth_global_a: u128,
+
+    pub token_mint_b: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub fee_growth_global_b: u128,
+}
@@ -0,0 +1,224 @@
+use crate::state::{EventHeap, Market, MarketStats, MAX_NUM_EVENTS};
+use anchor_lang::prelude::*;
+use bytemuck::cast;
+
+use super::*;
+
+impl<'a> Orderbook<'a> {
+    /// The single price at which the crossing part of the book matches the most base lots.
+    ///
+    /// Ties are broken by the smallest imbalance between bid and ask volume at the price,
+    /// then by the lowest price. Returns None if the book is not crossed.
+    pub fn auction_clearing_price(
+        &self,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+    ) -> Option<i64> {
+        let best_bid_price = self
+            .bids
+            .iter_valid(now_ts, oracle_price_lots)
+            .next()?
+            .price_lots;
+        let best_ask_price = self
+            .asks
+            .iter_valid(now_ts, oracle_price_lots)
+            .next()?
+            .price_lots;
+        if best_bid_price < best_ask_price {
+            return None;
+        }
+
+        // Best first: bids by descending and asks by ascending price
+        let bids: Vec<(i64, i64)> = self
+            .bids
+            .iter_valid(now_ts, oracle_price_lots)
+            .take_while(|o| o.price_lots >= best_ask_price)
+            .map(|o| (o.price_lots, o.node.quantity))
+            .collect();
+        let asks: Vec<(i64, i64)> = self
+            .asks
+            .iter_valid(now_ts, oracle_price_lots)
+            .take_while(|o| o.price_lots <= best_bid_price)
+            .map(|o| (o.price_lots, o.node.quantity))
+            .collect();
+
+        let mut prices: Vec<i64> = bids.iter().chain(asks.iter()).map(|o| o.0).collect();
+        prices.sort_unstable();
+        prices.dedup();
+
+        let total_bid_lots: i64 = bids.iter().map(|o| o.1).sum();
+        let mut bid_lots_below = 0;
+        let mut bid_index = bids.len();
+        let mut ask_lots = 0;
+        let mut ask_index = 0;
+
+        // (volume, imbalance, price)
+        let mut best: Option<(i64, i64, i64)> = None;
+        for price in prices {
+            while ask_index < asks.len() && asks[ask_index].0 <= price {
+                ask_lots += asks[ask_index].1;
+                ask_index += 1;
+            }
+            while bid_index > 0 && bids[bid_index - 1].0 < price {
+                bid_lots_below += bids[bid_index - 1].1;
+                bid_index -= 1;
+            }
+            let bid_lots = total_bid_lots - bid_lots_below;
+
+            let volume = bid_lots.min(ask_lots);
+            let imbalance = (bid_lots - ask_lots).abs();
+            if best.map_or(true, |(best_volume, best_imbalance, _)| {
+                volume > best_volume || (volume == best_volume && imbalance < best_imbalance)
+            }) {
+                best = Some((volume, imbalance, price));
+            }
+        }
+
+        best.filter(|(volume, _, _)| *volume > 0)
+            .map(|(_, _, price)| price)
+    }
+
+    /// Match crossing orders at `price_lots`, best prices first, for up to `limit` pairs.
+    ///
+    /// Both orders of a pair are makers: every pair gets its own seq num and pushes one
+    /// [`FillEvent`] for the bid and one for the ask, settled by `consume_events`. The
+    /// bid's event carries the price its funds were locked at, so the difference to the
+    /// clearing price gets released. Only the bid's event logs the fill, the ask's is its
+    /// mirror, and neither pays a taker fee.
+    ///
+    /// Returns true if no more orders cross at `price_lots`.
+    pub fn uncross_auction(
+        &mut self,
+        market: &mut Market,
+        event_heap: &mut EventHeap,
+        mut market_stats: Option<&mut MarketStats>,
+        price_lots: i64,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+        mut limit: u8,
+    ) -> Result<bool> {
+        let mut bid_changes: Vec<(BookSideOrderHandle, i64)> = vec![];
+        let mut ask_changes: Vec<(BookSideOrderHandle, i64)> = vec![];
+        let mut bid_deletes: Vec<(BookSideOrderTree, u128)> = vec![];
+        let mut ask_deletes: Vec<(BookSideOrderTree, u128)> = vec![];
+
+        let uncrossed = {
+            let mut bids = self
+                .bids
+                .iter_valid(now_ts, oracle_price_lots)
+                .take_while(|o| o.price_lots >= price_lots);
+            let mut asks = self
+                .asks
+                .iter_valid(now_ts, oracle_price_lots)
+                .take_while(|o| o.price_lots <= price_lots);
+
+            let mut bid = bids.next();
+            let mut ask = asks.next();
+            let mut bid_remaining = bid.as_ref().map_or(0, |o| o.node.quantity);
+            let mut ask_remaining = ask.as_ref().map_or(0, |o| o.node.quantity);
+
+            let uncrossed = loop {
+                let (Some(best_bid), Some(best_ask)) = (&bid, &ask) else {
+                    break true;
+                };
+                if limit == 0 || event_heap.len() + 2 > MAX_NUM_EVENTS as usize {
+                    msg!("Auction uncross limit reached");
+                    break false;
+                }
+
+                let quantity = bid_remaining.min(ask_remaining);
+                bid_remaining -= quantity;
+                ask_remaining -= quantity;
+
+                market.seq_num += 1;
+                let bid_locked_price = match best_bid.handle.order_tree {
+                    BookSideOrderTree::Fixed => best_bid.price_lots,
+                    BookSideOrderTree::OraclePegged => best_bid.node.peg_limit,
+                };
+                let mut bid_fill = FillEvent::new(
+                    Side::Ask,
+                    bid_remaining == 0,
+                    best_bid.node.owner_slot,
+                    now_ts,
+                    market.seq_num,
+                    best_bid.node.owner,
+                    best_bid.node.client_order_id,
+                    best_bid.node.timestamp,
+                    best_ask.node.owner,
+                    best_ask.node.client_order_id,
+                    price_lots,
+                    bid_locked_price,
+                    quantity,
+                );
+                let mut ask_fill = FillEvent::new(
+                    Side::Bid,
+                    ask_remaining == 0,
+                    best_ask.node.owner_slot,
+                    now_ts,
+                    market.seq_num,
+                    best_ask.node.owner,
+                    best_ask.node.client_order_id,
+                    best_ask.node.timestamp,
+                    best_bid.node.owner,
+                    best_bid.node.client_order_id,
+                    price_lots,
+                    best_ask.node.peg_limit,
+                    quantity,
+                );
+                bid_fill.auction = 1;
+                ask_fill.auction = 1;
+                ask_fill.auction_mirror = 1;
+                event_heap.push_back(cast(bid_fill));
+                event_heap.push_back(cast(ask_fill));
+                market.record_trade(market_stats.as_deref_mut(), now_ts, price_lots, quantity);
+                limit -= 1;
+
+                if bid_remaining == 0 {
+                    bid_deletes.push((best_bid.handle.order_tree, best_bid.node.key));
+                    bid = bids.next();
+                    bid_remaining = bid.as_ref().map_or(0, |o| o.node.quantity);
+                }
+                if ask_remaining == 0 {
+                    ask_deletes.push((best_ask.handle.order_tree, best_ask.node.key));
+                    ask = asks.next();
+                    ask_remaining = ask.as_ref().map_or(0, |o| o.node.quantity);
+                }
+            };
+
+            // Keep partially matched orders on the book with what is left of them
+            if let Some(best_bid) = bid.filter(|o| o.node.quantity != bid_remaining) {
+                bid_changes.push((best_bid.handle, bid_remaining));
+            }
+            if let Some(best_ask) = ask.filter(|o| o.node.quantity != ask_remaining) {
+                ask_changes.push((best_ask.handle, ask_remaining));
+            }
+
+            uncrossed
+        };
+
+        for (handle, new_quantity) in bid_changes {
+            self.bids
+                .node_mut(handle.node)
+                .unwrap()
+                .as_leaf_mut()
+                .unwrap()
+                .quantity = new_quantity;
+        }
+        for (handle, new_quantity) in ask_changes {
+            self.asks
+                .node_mut(handle.node)
+                .unwrap()
+                .as_leaf_mut()
+                .unwrap()
+                .quantity = new_quantity;
+        }
+        for (order_tree, key) in bid_deletes {
+            self.bids.remove_by_key(order_tree, key).unwrap();
+        }
+        for (order_tree, key) in ask_deletes {
+            self.asks.remove_by_key(order_tree, key).unwrap();
+        }
+
+        Ok(uncrossed)
+    }
+}
@@ -2,7 +2,7 @@ use crate::logs::*;
 use crate::state::MAX_OPEN_ORDERS;
 use crate::{
     error::*,
-    state::{orderbook::bookside::*, EventHeap, Market, OpenOrdersAccount},
+    state::{orderbook::bookside::*, EventHeap, Market, MarketStats, OpenOrdersAccount},
 };
 use anchor_lang::prelude::*;
 use bytemuck::cast;
@@ -29,6 +29,9 @@ pub struct OrderWithAmounts {
     pub total_base_taken_native: u64,
     pub total_quote_taken_native: u64,
     pub taker_fees: u64,
+    /// Taker fees in native base, on markets with fees in base. Added to the base an
+    /// ask pays, or subtracted from the base a bid receives.
+    pub taker_fees_base: u64,
     pub maker_fees: u64,
     pub referrer_amount: u64,
 }
@@ -64,6 +67,7 @@ impl<'a> Orderbook<'a> {
         open_book_market: &mut Market,
         market_pk: &Pubkey,
         event_heap: &mut EventHeap,
+        mut market_stats: Option<&mut MarketStats>,
         oracle_price_lots: Option<i64>,
         mut open_orders_account: Option<&mut OpenOrdersAccount>,
         owner: &Pubkey,
@@ -78,8 +82,24 @@ impl<'a> Orderbook<'a> {
         let other_side = side.invert_side();
         let post_only = order.is_post_only();
         let fill_or_kill = order.is_fill_or_kill();
+        let exact_out = order.is_exact_out();
+        let in_auction = market.is_in_auction();
         let mut post_target = order.post_target();
-        let (price_lots, price_data) = order.price(now_ts, oracle_price_lots, self)?;
+
+        // Orders are only collected during an auction, they get matched when it's uncrossed
+        if in_auction {
+            require!(post_target.is_some(), OpenBookError::MarketInAuction);
+        }
+        let (price_lots, price_data) =
+            order.price(now_ts, oracle_price_lots, market.tick_size_lots, self)?;
+
+        // Only orders resting at a fixed price need to be on the tick grid
+        if post_target == Some(BookSideOrderTree::Fixed) {
+            require!(
+                market.is_valid_tick(price_lots),
+                OpenBookError::InvalidInputTickSize
+            );
+        }
 
         // generate new order id
         let order_id = market.gen_order_id(side, price_data);
@@ -89,9 +109,26 @@ impl<'a> Orderbook<'a> {
         // Any changes to matching orders on the other side of the book are collected in
         // matched_changes/matched_deletes and then applied after this loop.
 
-        let order_max_base_lots = order.max_base_lots;
-        let order_max_quote_lots = if side == Side::Bid && !post_only {
+        // Taker fees come out of the amount the taker pays: quote for bids, or base for
+        // asks on markets with fees in base
+        let fees_in_base = market.has_fees_in_base();
+        let order_max_base_lots = if side == Side::Ask && fees_in_base && !post_only {
+            market.subtract_taker_fees(order.max_base_lots)
+        } else if side == Side::Bid && fees_in_base && exact_out {
+            // The received base is net of the taker fees
+            market
+                .add_taker_fees_ceil(order.max_base_lots, market.base_lot_size)
+                .ok_or(OpenBookError::WouldNotFillExactOut)?
+        } else {
+            order.max_base_lots
+        };
+        let order_max_quote_lots = if side == Side::Bid && !fees_in_base && !post_only {
             market.subtract_taker_fees(order.max_quote_lots_including_fees)
+        } else if side == Side::Ask && !fees_in_base && exact_out {
+            // The received quote is net of the taker fees
+            market
+                .add_taker_fees_ceil(order.max_quote_lots_including_fees, market.quote_lot_size)
+                .ok_or(OpenBookError::WouldNotFillExactOut)?
         } else {
             order.max_quote_lots_including_fees
         };
@@ -108,9 +145,16 @@ impl<'a> Orderbook<'a> {
             OpenBookError::InvalidInputLotsSize
         );
 
+        // The quote budget is only an upper bound, the order's size is judged at its limit price
+        require!(
+            market.is_above_min_size(order.max_base_lots, price_lots),
+            OpenBookError::OrderBelowMinimumSize
+        );
+
         let mut remaining_base_lots = order_max_base_lots;
         let mut remaining_quote_lots = order_max_quote_lots;
         let mut decremented_quote_lots = 0_i64;
+        let mut decremented_base_lots = 0_i64;
 
         let mut referrer_amount = 0_u64;
         let mut maker_rebates_acc = 0_u64;
@@ -120,10 +164,27 @@ impl<'a> Orderbook<'a> {
         let mut number_of_dropped_expired_orders = 0;
         let mut number_of_processed_fill_events = 0;
 
+        // A post-only cancel-replace order must not cancel its owner's crossing orders
+        // when another owner's order would still cross it
+        let crossed_by_other_owner = order.is_post_only_cancel_replace()
+            && self
+                .bookside(other_side)
+                .iter_valid(now_ts, oracle_price_lots)
+                .take_while(|o| side.is_price_within_limit(o.price_lots, price_lots))
+                .any(|o| open_orders_account.is_none() || owner != &o.node.owner);
+        if crossed_by_other_owner {
+            msg!("Order could not be placed due to PostOnly");
+            post_target = None;
+        }
+
         let opposing_bookside = self.bookside_mut(other_side);
         for best_opposing in opposing_bookside.iter_all_including_invalid(now_ts, oracle_price_lots)
         {
-            if remaining_base_lots == 0 || remaining_quote_lots == 0 {
+            if remaining_base_lots == 0
+                || remaining_quote_lots <= 0
+                || in_auction
+                || crossed_by_other_owner
+            {
                 break;
             }
 
@@ -131,6 +192,22 @@ impl<'a> Orderbook<'a> {
                 // Remove the order from the book unless we've done that enough
                 if number_of_dropped_expired_orders < DROP_EXPIRED_ORDER_LIMIT {
                     number_of_dropped_expired_orders += 1;
+                    if best_opposing.node.is_expired(now_ts) {
+                        emit_order_expired(
+                            *market_pk,
+                            other_side,
+                            best_opposing.handle.order_tree,
+                            best_opposing.node,
+                        );
+                    } else {
+                        // exceeded its peg_limit
+                        emit_order_cancelled(
+                            *market_pk,
+                            other_side,
+                            best_opposing.handle.order_tree,
+                            best_opposing.node,
+                        );
+                    }
                     let event = OutEvent::new(
                         other_side,
                         best_opposing.node.owner_slot,
@@ -159,7 +236,8 @@ impl<'a> Orderbook<'a> {
             if !side.is_price_within_limit(best_opposing_price, price_lots) {
                 break;
             }
-            if post_only {
+            let is_own_order = open_orders_account.is_some() && owner == &best_opposing.node.owner;
+            if post_only && !(is_own_order && order.is_post_only_cancel_replace()) {
                 msg!("Order could not be placed due to PostOnly");
                 post_target = None;
                 break; // return silently to not fail other instructions in tx
@@ -170,7 +248,13 @@ impl<'a> Orderbook<'a> {
                 break;
             }
 
-            let max_match_by_quote = remaining_quote_lots / best_opposing_price;
+            // Exact-out asks take a whole base lot more rather than fall short of the
+            // quote they need to receive
+            let max_match_by_quote = if exact_out && side == Side::Ask {
+                (remaining_quote_lots + best_opposing_price - 1) / best_opposing_price
+            } else {
+                remaining_quote_lots / best_opposing_price
+            };
             // Do not post orders in the book due to bad pricing and negative spread
             if max_match_by_quote == 0 {
                 post_target = None;
@@ -183,11 +267,18 @@ impl<'a> Orderbook<'a> {
             let match_quote_lots = match_base_lots * best_opposing_price;
 
             // Self-trade behaviour
-            if open_orders_account.is_some() && owner == &best_opposing.node.owner {
-                match order.self_trade_behavior {
+            if is_own_order {
+                // Post-only orders only get here to cancel their owner's crossing orders
+                let self_trade_behavior = if post_only {
+                    SelfTradeBehavior::CancelProvide
+                } else {
+                    order.self_trade_behavior
+                };
+                match self_trade_behavior {
                     SelfTradeBehavior::DecrementTake => {
                         // remember all decremented quote lots to only charge fees on not-self-trades
                         decremented_quote_lots += match_quote_lots;
+                        decremented_base_lots += match_base_lots;
                     }
                     SelfTradeBehavior::CancelProvide => {
                         // The open orders acc is always present in this case, no need event_heap
@@ -196,6 +287,12 @@ impl<'a> Orderbook<'a> {
                             best_opposing.node.quantity,
                             *market,
                         );
+                        emit_order_cancelled(
+                            *market_pk,
+                            other_side,
+                            best_opposing.handle.order_tree,
+                            best_opposing.node,
+                        );
                         matched_order_deletes
                             .push((best_opposing.handle.order_tree, best_opposing.node.key));
 
@@ -206,7 +303,10 @@ impl<'a> Orderbook<'a> {
                         return err!(OpenBookError::WouldSelfTrade)
                     }
                 }
-                assert!(order.self_trade_behavior == SelfTradeBehavior::DecrementTake);
+                assert!(self_trade_behavior == SelfTradeBehavior::DecrementTake);
+            } else if fees_in_base {
+                maker_rebates_acc +=
+                    market.maker_rebate_floor((match_base_lots * market.base_lot_size) as u64);
             } else {
                 maker_rebates_acc +=
                     market.maker_rebate_floor((match_quote_lots * market.quote_lot_size) as u64);
@@ -214,7 +314,7 @@ impl<'a> Orderbook<'a> {
 
             remaining_base_lots -= match_base_lots;
             remaining_quote_lots -= match_quote_lots;
-            assert!(remaining_quote_lots >= 0);
+            assert!(remaining_quote_lots >= 0 || exact_out);
 
             let new_best_opposing_quantity = best_opposing.node.quantity - match_base_lots;
             let maker_out = new_best_opposing_quantity == 0;
@@ -245,6 +345,15 @@ impl<'a> Orderbook<'a> {
                 market: *market_pk,
                 seq_num: market.seq_num,
             });
+            // Self-trades pay no fees, they would move the stats and the fill TWAP for free
+            if !is_own_order {
+                market.record_trade(
+                    market_stats.as_deref_mut(),
+                    now_ts,
+                    best_opposing_price,
+                    match_base_lots,
+                );
+            }
 
             process_fill_event(
                 fill,
@@ -258,7 +367,7 @@ impl<'a> Orderbook<'a> {
         }
 
         let total_quote_lots_taken = order_max_quote_lots - remaining_quote_lots;
-        let total_base_lots_taken = order.max_base_lots - remaining_base_lots;
+        let total_base_lots_taken = order_max_base_lots - remaining_base_lots;
         assert!(total_quote_lots_taken >= 0);
         assert!(total_base_lots_taken >= 0);
 
@@ -268,11 +377,18 @@ impl<'a> Orderbook<'a> {
         // Record the taker trade in the account already, even though it will only be
         // realized when the fill event gets executed
         let mut taker_fees_native = 0_u64;
+        let mut taker_fees_base_native = 0_u64;
         if total_quote_lots_taken > 0 || total_base_lots_taken > 0 {
             let total_quote_taken_native_wo_self =
                 ((total_quote_lots_taken - decremented_quote_lots) * market.quote_lot_size) as u64;
-
-            if total_quote_taken_native_wo_self > 0 {
+            let total_base_taken_native_wo_self =
+                ((total_base_lots_taken - decremented_base_lots) * market.base_lot_size) as u64;
+
+            if fees_in_base {
+                // No referrer share: what's left after the maker rebates can be swept
+                taker_fees_base_native = market.taker_fees_ceil(total_base_taken_native_wo_self);
+                market.base_fees_available += taker_fees_base_native - maker_rebates_acc;
+            } else if total_quote_taken_native_wo_self > 0 {
                 taker_fees_native = market.taker_fees_ceil(total_quote_taken_native_wo_self);
 
                 // Only account taker fees now. Maker fees accounted once processing the event
@@ -287,6 +403,7 @@ impl<'a> Orderbook<'a> {
                     total_base_taken_native,
                     total_quote_taken_native,
                     taker_fees_native,
+                    taker_fees_base_native,
                     referrer_amount,
                 );
             } else {
@@ -296,10 +413,10 @@ impl<'a> Orderbook<'a> {
             let (total_quantity_paid, total_quantity_received) = match side {
                 Side::Bid => (
                     total_quote_taken_native + taker_fees_native,
-                    total_base_taken_native,
+                    total_base_taken_native - taker_fees_base_native,
                 ),
                 Side::Ask => (
-                    total_base_taken_native,
+                    total_base_taken_native + taker_fees_base_native,
                     total_quote_taken_native - taker_fees_native,
                 ),
             };
@@ -309,7 +426,7 @@ impl<'a> Orderbook<'a> {
                 taker: *owner,
                 total_quantity_paid,
                 total_quantity_received,
-                fees: taker_fees_native,
+                fees: taker_fees_native + taker_fees_base_native,
             });
         }
 
@@ -325,6 +442,15 @@ impl<'a> Orderbook<'a> {
         remaining_quote_lots =
             order.max_quote_lots_including_fees - total_quote_lots_taken - taker_fees_lots;
 
+        // Same for the base of asks paying their fees in base
+        let remaining_base_lots_to_post = if side == Side::Ask && fees_in_base {
+            let taker_fees_base_lots =
+                (taker_fees_base_native as i64 + market.base_lot_size - 1) / market.base_lot_size;
+            order.max_base_lots - total_base_lots_taken - taker_fees_base_lots
+        } else {
+            remaining_base_lots
+        };
+
         // Apply changes to matched asks (handles invalidate on delete!)
         for (handle, new_quantity) in matched_order_changes {
             opposing_bookside
@@ -354,7 +480,7 @@ impl<'a> Orderbook<'a> {
         // If there are still quantity unmatched, place on the book
         let book_base_quantity_lots = {
             remaining_quote_lots -= market.maker_fees_ceil(remaining_quote_lots);
-            remaining_base_lots.min(remaining_quote_lots / price)
+            remaining_base_lots_to_post.min(remaining_quote_lots / price)
         };
 
         if book_base_quantity_lots <= 0 {
@@ -370,11 +496,41 @@ impl<'a> Orderbook<'a> {
             post_target = None;
         }
 
+        // Do not post dust remainders. Fail if the order could neither take nor post anything.
+        if post_target.is_some() && !market.is_above_min_size(book_base_quantity_lots, price_lots) {
+            require_gt!(
+                total_base_lots_taken,
+                0,
+                OpenBookError::OrderBelowMinimumSize
+            );
+            msg!(
+                "Remainder of {} base lots is below the market minimum size, not posting",
+                book_base_quantity_lots
+            );
+            post_target = None;
+        }
+
         // There is still quantity, but it's a fill or kill order -> kill
         if fill_or_kill && remaining_base_lots > 0 {
             return err!(OpenBookError::WouldExecutePartially);
         }
 
+        // The full output amount net of taker fees could not be delivered within the
+        // limits -> kill
+        let output_remaining = match side {
+            Side::Bid => {
+                total_base_taken_native - taker_fees_base_native
+                    < (order.max_base_lots * market.base_lot_size) as u64
+            }
+            Side::Ask => {
+                total_quote_taken_native - taker_fees_native
+                    < (order.max_quote_lots_including_fees * market.quote_lot_size) as u64
+            }
+        };
+        if exact_out && output_remaining {
+            return err!(OpenBookError::WouldNotFillExactOut);
+        }
+
         let mut maker_fees_native = 0;
         let mut posted_base_native = 0;
         let mut posted_quote_native = 0;
@@ -404,7 +560,10 @@ impl<'a> Orderbook<'a> {
 
             let bookside = self.bookside_mut(side);
             // Drop an expired order if possible
-            if let Some(expired_order) = bookside.remove_one_expired(order_tree_target, now_ts) {
+            if let Some((expired_order, expired_order_tree)) =
+                bookside.remove_one_expired(order_tree_target, now_ts)
+            {
+                emit_order_expired(*market_pk, side, expired_order_tree, &expired_order);
                 let event = OutEvent::new(
                     side,
                     expired_order.owner_slot,
@@ -425,13 +584,14 @@ impl<'a> Orderbook<'a> {
 
             if bookside.is_full() {
                 // If this bid is higher than lowest bid, boot that bid and insert this one
-                let (worst_order, worst_price) =
+                let (worst_order, worst_order_tree, worst_price) =
                     bookside.remove_worst(now_ts, oracle_price_lots).unwrap();
                 // OpenBookErrorCode::OutOfSpace
                 require!(
                     side.is_price_better(price_lots, worst_price),
                     OpenBookError::SomeError
                 );
+                emit_order_cancelled(*market_pk, side, worst_order_tree, &worst_order);
                 let event = OutEvent::new(
                     side,
                     worst_order.owner_slot,
@@ -463,6 +623,21 @@ impl<'a> Orderbook<'a> {
             );
             let _result = bookside.insert_leaf(order_tree_target, &new_order)?;
 
+            emit_stack(OrderPlacedLog {
+                market: *market_pk,
+                owner: *owner,
+                order_id,
+                client_order_id: order.client_order_id,
+                side: side.into(),
+                order_tree: order_tree_target.into(),
+                price_data,
+                peg_limit: order.peg_limit(),
+                price: price_lots,
+                quantity: book_base_quantity_lots,
+                time_in_force: order.time_in_force,
+                timestamp: now_ts,
+            });
+
             open_orders.add_order(
                 side,
                 order_tree_target,
@@ -486,6 +661,7 @@ impl<'a> Orderbook<'a> {
             total_quote_taken_native,
             referrer_amount,
             taker_fees: taker_fees_native,
+            taker_fees_base: taker_fees_base_native,
             maker_fees: maker_fees_native,
         })
     }
@@ -572,6 +748,7 @@ impl<'a> Orderbook<'a> {
             require_keys_eq!(leaf_node.owner, owner);
         }
         open_orders_account.cancel_order(leaf_node.owner_slot as usize, leaf_node.quantity, market);
+        emit_order_cancelled(open_orders_account.market, side, book_component, &leaf_node);
 
         Ok(leaf_node)
     }
@@ -627,3 +804,42 @@ pub fn process_fill_event<'c: 'info, 'info>(
 
     Ok(())
 }
+
+fn emit_order_cancelled(
+    market: Pubkey,
+    side: Side,
+    order_tree: BookSideOrderTree,
+    leaf_node: &LeafNode,
+) {
+    emit_stack(OrderCancelledLog {
+        market,
+        owner: leaf_node.owner,
+        order_id: leaf_node.key,
+        client_order_id: leaf_node.client_order_id,
+        side: side.into(),
+        order_tree: order_tree.into(),
+        price_data: leaf_node.price_data(),
+        peg_limit: leaf_node.peg_limit,
+        quantity: leaf_node.quantity,
+    });
+}
+
+fn emit_order_expired(
+    market: Pubkey,
+    side: Side,
+    order_tree: BookSideOrderTree,
+    leaf_node: &LeafNode,
+) {
+    emit_stack(OrderExpiredLog {
+        market,
+        owner: leaf_node.owner,
+        order_id: leaf_node.key,
+        client_order_id: leaf_node.client_order_id,
+        side: side.into(),
+        order_tree: order_tree.into(),
+        price_data: leaf_node.price_data(),
+        peg_limit: leaf_node.peg_limit,
+        quantity: leaf_node.quantity,
+        expiry: leaf_node.expiry(),
+    });
+}
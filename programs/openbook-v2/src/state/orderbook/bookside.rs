@@ -79,6 +79,16 @@ impl BookSide {
         &mut self.roots[component as usize]
     }
 
+    /// Handle of the subtree of the oracle pegged OrderTree that holds all orders
+    /// with offsets in basis points, see ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX
+    pub fn oracle_pegged_bps_subtree(&self) -> Option<NodeHandle> {
+        self.nodes.find_prefix_subtree(
+            self.root(BookSideOrderTree::OraclePegged),
+            (ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX as u128) << 64,
+            ORACLE_PEGGED_BPS_PREFIX_LEN,
+        )
+    }
+
     pub fn is_full(&self) -> bool {
         self.nodes.is_full()
     }
@@ -98,15 +108,23 @@ impl BookSide {
         self.nodes.insert_leaf(root, new_leaf)
     }
 
-    /// Remove the overall worst-price order.
+    /// Remove the overall worst-price order, returning it with its component and price.
     pub fn remove_worst(
         &mut self,
         now_ts: u64,
         oracle_price_lots: Option<i64>,
-    ) -> Option<(LeafNode, i64)> {
-        let worst_fixed = self.nodes.find_worst(&self.roots[0]);
-        let worst_pegged = self.nodes.find_worst(&self.roots[1]);
+    ) -> Option<(LeafNode, BookSideOrderTree, i64)> {
         let side = self.nodes.order_tree_type().side();
+        let worst_fixed = self.nodes.find_worst(&self.roots[0]);
+        let bps_subtree = self.oracle_pegged_bps_subtree();
+        let worst_pegged = rank_oracle_pegged_orders(
+            side,
+            self.nodes
+                .find_worst_in_subtree(self.roots[1].node(), bps_subtree),
+            self.nodes.find_worst_in_subtree(bps_subtree, None),
+            true,
+            oracle_price_lots,
+        );
         let worse = rank_orders(
             side,
             worst_fixed,
@@ -119,19 +137,20 @@ impl BookSide {
         let key = worse.node.key;
         let order_tree = worse.handle.order_tree;
         let n = self.remove_by_key(order_tree, key)?;
-        Some((n, price))
+        Some((n, order_tree, price))
     }
 
     /// Remove the order with the lowest expiry timestamp in the component, if that's < now_ts.
     /// If there is none, try to remove the lowest expiry one from the other component.
+    /// Returns the removed order together with the component it was removed from.
     pub fn remove_one_expired(
         &mut self,
         component: BookSideOrderTree,
         now_ts: u64,
-    ) -> Option<LeafNode> {
+    ) -> Option<(LeafNode, BookSideOrderTree)> {
         let root = &mut self.roots[component as usize];
         if let Some(n) = self.nodes.remove_one_expired(root, now_ts) {
-            return Some(n);
+            return Some((n, component));
         }
 
         let other_component = match component {
@@ -139,7 +158,9 @@ impl BookSide {
             BookSideOrderTree::OraclePegged => BookSideOrderTree::Fixed,
         };
         let other_root = &mut self.roots[other_component as usize];
-        self.nodes.remove_one_expired(other_root, now_ts)
+        self.nodes
+            .remove_one_expired(other_root, now_ts)
+            .map(|n| (n, other_component))
     }
 
     pub fn remove_by_key(
@@ -244,6 +265,19 @@ mod tests {
                 .unwrap();
         }
 
+        while root_pegged.leaf_count < 150 {
+            let price_data: u64 = oracle_pegged_bps_price_data(rng.gen_range(-2000..2000));
+            let seq_num: u64 = rng.gen_range(0..1000);
+            let key = new_node_key(side, price_data, seq_num);
+            if keys.contains(&key) {
+                continue;
+            }
+            keys.push(key);
+            order_tree
+                .insert_leaf(&mut root_pegged, &new_leaf(key))
+                .unwrap();
+        }
+
         while root_fixed.leaf_count < 100 {
             let price_data: u64 = rng.gen_range(1..50);
             let seq_num: u64 = rng.gen_range(0..1000);
@@ -282,7 +316,7 @@ mod tests {
             }
             assert!(total >= 101); // some oracle peg orders could be skipped
             if oracle_price_lots > 20 {
-                assert_eq!(total, 200);
+                assert_eq!(total, 250);
             }
         }
     }
@@ -374,29 +408,89 @@ mod tests {
 
         // remove pegged order
         assert_eq!(order_prices(0, 100), vec![120, 100, 90, 85, 80]);
-        let (_, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
         assert_eq!(p, 80);
         assert_eq!(order_prices(0, 100), vec![120, 100, 90, 85]);
 
         // remove fixed order (order at 190=200-10 hits the peg limit)
         assert_eq!(order_prices(0, 200), vec![185, 120, 100]);
-        let (_, p) = bookside.borrow_mut().remove_worst(0, Some(200)).unwrap();
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(200)).unwrap();
         assert_eq!(p, 100);
         assert_eq!(order_prices(0, 200), vec![185, 120]);
 
         // remove until end
 
         assert_eq!(order_prices(0, 100), vec![120, 90, 85]);
-        let (_, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
         assert_eq!(p, 85);
         assert_eq!(order_prices(0, 100), vec![120, 90]);
-        let (_, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
         assert_eq!(p, 90);
         assert_eq!(order_prices(0, 100), vec![120]);
-        let (_, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
         assert_eq!(p, 120);
         assert_eq!(order_prices(0, 100), Vec::<i64>::new());
     }
 
+    #[test]
+    fn bookside_bps_pegged() {
+        use std::cell::RefCell;
+
+        let side = Side::Bid;
+        let mut order_tree = new_order_tree(OrderTreeType::Bids);
+        let mut root_fixed = OrderTreeRoot::zeroed();
+        let mut root_pegged = OrderTreeRoot::zeroed();
+        let new_leaf = |key: u128| LeafNode::new(0, key, Pubkey::default(), 0, 1000, 0, -1, 0);
+
+        let key = new_node_key(side, fixed_price_data(100).unwrap(), 0);
+        order_tree
+            .insert_leaf(&mut root_fixed, &new_leaf(key))
+            .unwrap();
+        let key = new_node_key(side, oracle_pegged_price_data(-10), 1);
+        order_tree
+            .insert_leaf(&mut root_pegged, &new_leaf(key))
+            .unwrap();
+        for (seq_num, price_offset_bps) in [(2, -500), (3, -2000)] {
+            let key = new_node_key(
+                side,
+                oracle_pegged_bps_price_data(price_offset_bps),
+                seq_num,
+            );
+            order_tree
+                .insert_leaf(&mut root_pegged, &new_leaf(key))
+                .unwrap();
+        }
+
+        let bookside = RefCell::new(BookSide {
+            roots: [root_fixed, root_pegged],
+            reserved_roots: [OrderTreeRoot::zeroed(); 4],
+            reserved: [0; 256],
+            nodes: order_tree,
+        });
+        let order_prices = |oracle: i64| -> Vec<i64> {
+            bookside
+                .borrow()
+                .iter_valid(0, Some(oracle))
+                .map(|it| it.price_lots)
+                .collect()
+        };
+
+        // the relative order of lot and bps offsets depends on the oracle price
+        assert_eq!(order_prices(100), vec![100, 95, 90, 80]);
+        assert_eq!(order_prices(1000), vec![990, 950, 800, 100]);
+        assert_eq!(order_prices(110), vec![104, 100, 100, 88]);
+
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(1000)).unwrap();
+        assert_eq!(p, 100);
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        assert_eq!(p, 80);
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        assert_eq!(p, 90);
+        assert_eq!(order_prices(100), vec![95]);
+        let (_, _, p) = bookside.borrow_mut().remove_worst(0, Some(100)).unwrap();
+        assert_eq!(p, 95);
+        assert!(bookside.borrow().is_empty());
+    }
+
     // add test for oracle expired
 }
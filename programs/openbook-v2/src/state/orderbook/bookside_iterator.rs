@@ -16,6 +16,9 @@ impl<'a> BookSideIterItem<'a> {
 /// Iterates the fixed and oracle_pegged OrderTrees simultaneously, allowing users to
 /// walk the orderbook without caring about where an order came from.
 ///
+/// Oracle pegged orders with lot and bps offsets are iterated separately, since their
+/// relative order depends on the oracle price.
+///
 /// This will skip over orders that are not currently matchable, but might be valid
 /// in the future.
 ///
@@ -24,19 +27,29 @@ impl<'a> BookSideIterItem<'a> {
 pub struct BookSideIter<'a> {
     fixed_iter: OrderTreeIter<'a>,
     oracle_pegged_iter: OrderTreeIter<'a>,
+    oracle_pegged_bps_iter: OrderTreeIter<'a>,
     now_ts: u64,
     oracle_price_lots: Option<i64>,
 }
 
 impl<'a> BookSideIter<'a> {
     pub fn new(book_side: &'a BookSide, now_ts: u64, oracle_price_lots: Option<i64>) -> Self {
+        let oracle_pegged_root = book_side.root(BookSideOrderTree::OraclePegged);
+        let bps_subtree = book_side.oracle_pegged_bps_subtree();
         Self {
             fixed_iter: book_side
                 .nodes
                 .iter(book_side.root(BookSideOrderTree::Fixed)),
-            oracle_pegged_iter: book_side
-                .nodes
-                .iter(book_side.root(BookSideOrderTree::OraclePegged)),
+            oracle_pegged_iter: OrderTreeIter::new_in_subtree(
+                &book_side.nodes,
+                oracle_pegged_root.node(),
+                bps_subtree,
+            ),
+            oracle_pegged_bps_iter: OrderTreeIter::new_in_subtree(
+                &book_side.nodes,
+                bps_subtree,
+                None,
+            ),
             now_ts,
             oracle_price_lots,
         }
@@ -62,8 +75,13 @@ pub fn oracle_pegged_price(
     side: Side,
 ) -> (OrderState, i64) {
     let price_data = node.price_data();
-    let price_offset = oracle_pegged_price_offset(price_data);
-    let price = oracle_price_lots.saturating_add(price_offset);
+    let price = if is_oracle_pegged_bps_price_data(price_data) {
+        let price_offset_bps = oracle_pegged_price_offset_bps(price_data);
+        oracle_pegged_bps_price_lots(side, oracle_price_lots, price_offset_bps)
+    } else {
+        let price_offset = oracle_pegged_price_offset(price_data);
+        oracle_price_lots.saturating_add(price_offset)
+    };
     if (1..i64::MAX).contains(&price) {
         if node.peg_limit != -1 && side.is_price_better(price, node.peg_limit) {
             return (OrderState::Invalid, price);
@@ -123,6 +141,41 @@ fn oracle_pegged_to_result(
     }
 }
 
+/// Compares two oracle pegged orders and returns the one that would match first.
+///
+/// (or the worse one, if `return_worse` is set)
+///
+/// Without an oracle price pegged orders can't be ranked and are ignored by
+/// rank_orders() anyway, so any of them is returned.
+pub fn rank_oracle_pegged_orders<'a>(
+    side: Side,
+    lhs: Option<(NodeHandle, &'a LeafNode)>,
+    rhs: Option<(NodeHandle, &'a LeafNode)>,
+    return_worse: bool,
+    oracle_price_lots: Option<i64>,
+) -> Option<(NodeHandle, &'a LeafNode)> {
+    match (lhs, rhs, oracle_price_lots) {
+        (Some(l), Some(r), Some(oracle_price_lots)) => {
+            let fixed_key = |node: &LeafNode| {
+                let (_, price_lots) = oracle_pegged_price(oracle_price_lots, node, side);
+                key_for_fixed_price(node.key, price_lots)
+            };
+            let is_better = if side == Side::Bid {
+                fixed_key(l.1) > fixed_key(r.1)
+            } else {
+                fixed_key(l.1) < fixed_key(r.1)
+            };
+
+            if is_better ^ return_worse {
+                Some(l)
+            } else {
+                Some(r)
+            }
+        }
+        _ => lhs.or(rhs),
+    }
+}
+
 /// Compares the `fixed` and `oracle_pegged` order and returns the one that would match first.
 ///
 /// (or the worse one, if `return_worse` is set)
@@ -173,18 +226,25 @@ impl<'a> Iterator for BookSideIter<'a> {
         // Skip all the oracle pegged orders that aren't representable with the current oracle
         // price. Example: iterating asks, but the best ask is at offset -100 with the oracle at 50.
         // We need to skip asks until we find the first that has a price >= 1.
-        let o_peek = if let Some(oracle_price_lots) = self.oracle_price_lots {
-            let mut o_peek = self.oracle_pegged_iter.peek();
-            while let Some((_, o_node)) = o_peek {
-                if oracle_pegged_price(oracle_price_lots, o_node, side).0 != OrderState::Skipped {
-                    break;
+        let (o_peek, b_peek) = if let Some(oracle_price_lots) = self.oracle_price_lots {
+            let skip_unrepresentable = |iter: &mut OrderTreeIter<'a>| {
+                let mut peek = iter.peek();
+                while let Some((_, node)) = peek {
+                    if oracle_pegged_price(oracle_price_lots, node, side).0 != OrderState::Skipped {
+                        break;
+                    }
+                    peek = iter.next()
                 }
-                o_peek = self.oracle_pegged_iter.next()
-            }
-            o_peek
+                peek
+            };
+            (
+                skip_unrepresentable(&mut self.oracle_pegged_iter),
+                skip_unrepresentable(&mut self.oracle_pegged_bps_iter),
+            )
         } else {
-            None
+            (None, None)
         };
+        let o_peek = rank_oracle_pegged_orders(side, o_peek, b_peek, false, self.oracle_price_lots);
 
         let f_peek = self.fixed_iter.peek();
 
@@ -198,7 +258,13 @@ impl<'a> Iterator for BookSideIter<'a> {
         )?;
         match better.handle.order_tree {
             BookSideOrderTree::Fixed => self.fixed_iter.next(),
-            BookSideOrderTree::OraclePegged => self.oracle_pegged_iter.next(),
+            BookSideOrderTree::OraclePegged => {
+                if b_peek.map(|(handle, _)| handle) == Some(better.handle.node) {
+                    self.oracle_pegged_bps_iter.next()
+                } else {
+                    self.oracle_pegged_iter.next()
+                }
+            }
         };
 
         Some(better)
@@ -7,6 +7,8 @@ use std::mem::size_of;
 use super::Side;
 
 pub const MAX_NUM_EVENTS: u16 = 600;
+// Max events to consume per ix.
+pub const MAX_EVENTS_CONSUME: usize = 8;
 pub const NO_NODE: u16 = u16::MAX;
 
 /// Container for the different EventTypes.
@@ -241,7 +243,9 @@ pub struct FillEvent {
     pub taker_side: u8, // Side, from the taker's POV
     pub maker_out: u8,  // 1 if maker order quantity == 0
     pub maker_slot: u8,
-    pub padding: [u8; 4],
+    pub auction: u8,        // 1 if matched by an auction uncross, no taker fees then
+    pub auction_mirror: u8, // 1 for the ask's fill of an auction match, which logs no fill
+    pub padding: [u8; 2],
     pub timestamp: u64,
     pub market_seq_num: u64,
 
@@ -294,6 +298,8 @@ impl FillEvent {
             price,
             peg_limit,
             quantity,
+            auction: 0,
+            auction_mirror: 0,
             padding: Default::default(),
             reserved: [0; 8],
         }
@@ -305,6 +311,12 @@ impl FillEvent {
     pub fn maker_out(&self) -> bool {
         self.maker_out == 1
     }
+    pub fn is_auction(&self) -> bool {
+        self.auction == 1
+    }
+    pub fn is_auction_mirror(&self) -> bool {
+        self.auction_mirror == 1
+    }
 }
 
 #[derive(
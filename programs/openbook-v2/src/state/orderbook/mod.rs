@@ -8,6 +8,7 @@ pub use order_type::*;
 pub use ordertree::*;
 pub use ordertree_iterator::*;
 
+mod auction;
 mod book;
 mod bookside;
 mod bookside_iterator;
@@ -21,7 +22,13 @@ mod ordertree_iterator;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::{Market, OpenOrdersAccount, FEES_SCALE_FACTOR};
+    use crate::accounts_zerocopy::AccountInfoRef;
+    use crate::error::OpenBookError;
+    use crate::state::{
+        Market, MarketStats, OpenOrdersAccount, OracleConfigParams, TradeStats, FEES_SCALE_FACTOR,
+        MARKET_STATS_BUCKET_SECONDS,
+    };
+    use anchor_lang::Discriminator;
     use bytemuck::Zeroable;
     use fixed::types::I80F48;
     use solana_program::pubkey::Pubkey;
@@ -120,10 +127,12 @@ mod tests {
                             order_type: PostOrderType::Limit,
                         },
                         self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                        reduce_only: false,
                     },
                     &mut openbook_market,
                     &market_pk,
                     event_heap,
+                    None,
                     oracle_price_lots,
                     Some(&mut account),
                     &Pubkey::new_unique(),
@@ -263,10 +272,12 @@ mod tests {
                     order_type: PostOrderType::Limit,
                 },
                 self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                reduce_only: false,
             },
             &mut market,
             &market_pk,
             &mut event_heap,
+            None,
             oracle_price_lots,
             Some(&mut maker),
             &maker_pk,
@@ -310,10 +321,12 @@ mod tests {
                     order_type: PostOrderType::Limit,
                 },
                 self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                reduce_only: false,
             },
             &mut market,
             &market_pk,
             &mut event_heap,
+            None,
             oracle_price_lots,
             Some(&mut taker),
             &taker_pk,
@@ -354,7 +367,7 @@ mod tests {
 
         // simulate event heap processing
         maker.execute_maker(&mut market, fill);
-        taker.execute_taker(&mut market, Side::Ask, 0, 0, 0, 0);
+        taker.execute_taker(&mut market, Side::Ask, 0, 0, 0, 0, 0);
 
         assert_eq!(maker.position.bids_base_lots, bid_quantity - match_quantity);
         assert_eq!(maker.position.asks_base_lots, 0);
@@ -399,10 +412,12 @@ mod tests {
                         order_type: PostOrderType::Limit,
                     },
                     self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    reduce_only: false,
                 },
                 &mut market,
                 &market_pk,
                 event_heap,
+                None,
                 oracle_price_lots,
                 Some(&mut account),
                 &Pubkey::default(),
@@ -450,4 +465,504 @@ mod tests {
         new_order(&mut book, &mut event_heap, Side::Bid, 5005, 30, 1);
         assert_eq!(event_heap.len(), 1);
     }
+
+    #[test]
+    fn book_min_size_and_tick_size() {
+        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(1000.0);
+        let mut book = book_accs.orderbook();
+        let market_pk = Pubkey::new_unique();
+
+        market.min_base_lots = 2;
+        market.min_quote_lots = 2_500;
+        market.tick_size_lots = 10;
+
+        let mut new_order = |book: &mut Orderbook,
+                             event_heap: &mut EventHeap,
+                             side,
+                             price_lots,
+                             max_base_lots: i64,
+                             order_type|
+         -> anchor_lang::Result<u128> {
+            let mut account = OpenOrdersAccount::default_for_tests();
+
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots,
+                    max_quote_lots_including_fees: i64::MAX / market.quote_lot_size,
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    reduce_only: false,
+                },
+                &mut market,
+                &market_pk,
+                event_heap,
+                None,
+                oracle_price_lots,
+                Some(&mut account),
+                &Pubkey::new_unique(),
+                0, // now_ts
+                u8::MAX,
+                &[],
+            )?;
+            Ok(account.open_order_by_raw_index(0).id)
+        };
+
+        // Prices off the tick grid are rejected
+        assert!(new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Ask,
+            1015,
+            3,
+            PostOrderType::Limit
+        )
+        .is_err());
+
+        // Below the minimum base lots or the minimum notional
+        assert!(new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Ask,
+            1010,
+            1,
+            PostOrderType::Limit
+        )
+        .is_err());
+        assert!(new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Ask,
+            1010,
+            2,
+            PostOrderType::Limit
+        )
+        .is_err());
+
+        let ask_id = new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Ask,
+            1010,
+            3,
+            PostOrderType::Limit,
+        )
+        .unwrap();
+        assert!(order_tree_contains_key(&book.asks, ask_id));
+
+        // Takes the ask fully, the dust remainder is not posted
+        let bid_id = new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Bid,
+            1010,
+            4,
+            PostOrderType::Limit,
+        )
+        .unwrap();
+        assert_eq!(bid_id, 0);
+        assert_eq!(event_heap.len(), 1);
+        assert!(book.is_empty());
+
+        // PostOnlySlide slides onto the tick grid
+        new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Bid,
+            1000,
+            3,
+            PostOrderType::Limit,
+        )
+        .unwrap();
+        new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Ask,
+            990,
+            3,
+            PostOrderType::PostOnlySlide,
+        )
+        .unwrap();
+        assert!(order_tree_contains_price(&book.asks, 1010));
+
+        // Taking below the minimum notional is rejected, however large the quote budget
+        assert!(new_order(
+            &mut book,
+            &mut event_heap,
+            Side::Bid,
+            1010,
+            2,
+            PostOrderType::Limit
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn book_trade_stats() {
+        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(1000.0);
+        let mut book = book_accs.orderbook();
+        let market_pk = Pubkey::new_unique();
+        let mut stats = MarketStats::zeroed();
+
+        let new_order = |book: &mut Orderbook,
+                         event_heap: &mut EventHeap,
+                         market: &mut Market,
+                         stats: &mut MarketStats,
+                         side,
+                         price_lots,
+                         max_base_lots,
+                         now_ts| {
+            let mut account = OpenOrdersAccount::default_for_tests();
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots,
+                    max_quote_lots_including_fees: i64::MAX / market.quote_lot_size,
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type: PostOrderType::Limit,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    reduce_only: false,
+                },
+                market,
+                &market_pk,
+                event_heap,
+                Some(stats),
+                oracle_price_lots,
+                Some(&mut account),
+                &Pubkey::new_unique(),
+                now_ts,
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+        };
+
+        let hour = MARKET_STATS_BUCKET_SECONDS;
+        let now_ts = 10 * hour;
+        for price_lots in [1000, 1010] {
+            new_order(
+                &mut book,
+                &mut event_heap,
+                &mut market,
+                &mut stats,
+                Side::Ask,
+                price_lots,
+                2,
+                now_ts,
+            );
+        }
+        assert_eq!(market.last_price_lots, 0);
+        assert_eq!(stats.last_24h(now_ts), TradeStats::default());
+
+        // Takes both asks, the last fill is the worse price
+        new_order(
+            &mut book,
+            &mut event_heap,
+            &mut market,
+            &mut stats,
+            Side::Bid,
+            1010,
+            3,
+            now_ts,
+        );
+        assert_eq!(market.last_price_lots, 1010);
+        assert_eq!(market.last_trade_ts, now_ts);
+        assert_eq!(
+            stats.last_24h(now_ts),
+            TradeStats {
+                high_price_lots: 1010,
+                low_price_lots: 1000,
+                volume_base_lots: 3,
+            }
+        );
+
+        // A fill 23 hours later still sees the first ones
+        let next_ts = now_ts + 23 * hour;
+        for side in [Side::Ask, Side::Bid] {
+            new_order(
+                &mut book,
+                &mut event_heap,
+                &mut market,
+                &mut stats,
+                side,
+                990,
+                1,
+                next_ts,
+            );
+        }
+        assert_eq!(market.last_price_lots, 990);
+        assert_eq!(
+            stats.last_24h(next_ts),
+            TradeStats {
+                high_price_lots: 1010,
+                low_price_lots: 990,
+                volume_base_lots: 4,
+            }
+        );
+
+        // Hours drop out of the window as time passes, with or without fills
+        assert_eq!(
+            stats.last_24h(next_ts + hour),
+            TradeStats {
+                high_price_lots: 990,
+                low_price_lots: 990,
+                volume_base_lots: 1,
+            }
+        );
+        assert_eq!(stats.last_24h(next_ts + 24 * hour), TradeStats::default());
+
+        // The bucket of the first fills is reused for the hour a day later
+        let day_later_ts = now_ts + 24 * hour;
+        for side in [Side::Ask, Side::Bid] {
+            new_order(
+                &mut book,
+                &mut event_heap,
+                &mut market,
+                &mut stats,
+                side,
+                995,
+                1,
+                day_later_ts,
+            );
+        }
+        assert_eq!(
+            stats.last_24h(day_later_ts),
+            TradeStats {
+                high_price_lots: 995,
+                low_price_lots: 990,
+                volume_base_lots: 2,
+            }
+        );
+    }
+
+    // Self-trades pay no fees, so they must not move the last price, stats and fill TWAP
+    #[test]
+    fn book_self_trades_leave_trade_stats() {
+        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(1000.0);
+        let mut book = book_accs.orderbook();
+        let market_pk = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut account = OpenOrdersAccount::default_for_tests();
+        let now_ts = 1000;
+
+        let new_order = |book: &mut Orderbook,
+                         event_heap: &mut EventHeap,
+                         market: &mut Market,
+                         side,
+                         owner: &Pubkey,
+                         account| {
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots: 1,
+                    max_quote_lots_including_fees: i64::MAX,
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots: 1000,
+                        order_type: PostOrderType::Limit,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    reduce_only: false,
+                },
+                market,
+                &market_pk,
+                event_heap,
+                None,
+                oracle_price_lots,
+                Some(account),
+                owner,
+                now_ts,
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+        };
+
+        let (book, heap, market) = (&mut book, &mut event_heap, &mut market);
+        new_order(book, heap, market, Side::Ask, &owner, &mut account);
+        new_order(book, heap, market, Side::Ask, &owner, &mut account);
+        new_order(book, heap, market, Side::Bid, &owner, &mut account);
+        assert_eq!(heap.len(), 1);
+        assert_eq!(market.last_price_lots, 0);
+        assert_eq!(market.last_trade_ts, 0);
+        assert_eq!(market.fill_twap_price_lots, 0f64);
+
+        let mut other_account = OpenOrdersAccount::default_for_tests();
+        let other_owner = Pubkey::new_unique();
+        new_order(
+            book,
+            heap,
+            market,
+            Side::Bid,
+            &other_owner,
+            &mut other_account,
+        );
+        assert_eq!(heap.len(), 2);
+        assert_eq!(market.last_price_lots, 1000);
+        assert_eq!(market.last_trade_ts, now_ts);
+        assert_eq!(market.fill_twap_price_lots, 1000f64);
+    }
+
+    // A market using this book as its oracle sees orders leave the book as they are
+    // cancelled, a quote placed and pulled again leaves nothing behind
+    #[test]
+    fn book_oracle_pegged_bps_zero_oracle_price() {
+        let (_, _, _, book_accs) = test_setup(1000.0);
+        let book = book_accs.orderbook();
+
+        let order = Order {
+            side: Side::Bid,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 1_000,
+            client_order_id: 0,
+            time_in_force: 0,
+            params: OrderParams::OraclePeggedBps {
+                price_offset_bps: -100,
+                order_type: PostOrderType::PostOnlySlide,
+                peg_limit: -1,
+            },
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            reduce_only: false,
+        };
+
+        // A bps offset of a zero oracle price means nothing
+        assert!(
+            order.price(0, Some(0), 1, &book).unwrap_err()
+                == OpenBookError::OraclePegInvalidOracleState.into()
+        );
+        assert_eq!(order.price(0, Some(1_000), 1, &book).unwrap().0, 990);
+    }
+
+    #[test]
+    fn book_oracle_place_then_cancel() {
+        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(1000.0);
+        let mut book = book_accs.orderbook();
+        let market_pk = Pubkey::new_unique();
+        let bids_pk = Pubkey::new_unique();
+        let asks_pk = Pubkey::new_unique();
+        let now_ts = 1000;
+
+        let mut oracle_market = Market::zeroed();
+        oracle_market.quote_lot_size = 1;
+        oracle_market.base_lot_size = 1;
+        oracle_market.oracle_a = Some(bids_pk).into();
+        oracle_market.oracle_b = Some(asks_pk).into();
+        oracle_market.oracle_config = OracleConfigParams {
+            conf_filter: 0.1,
+            max_staleness_slots: None,
+            max_staleness_seconds: Some(60),
+            max_twap_deviation: None,
+        }
+        .to_oracle_config();
+
+        let oracle_price = |oracle_market: &mut Market, book: &Orderbook, now_ts: u64| {
+            let account_data = |book_side: &BookSide| {
+                let mut data = BookSide::discriminator().to_vec();
+                data.extend_from_slice(bytemuck::bytes_of(book_side));
+                data
+            };
+            let mut bids_data = account_data(&book.bids);
+            let mut asks_data = account_data(&book.asks);
+            let bids_data = RefCell::new(&mut bids_data[..]);
+            let asks_data = RefCell::new(&mut asks_data[..]);
+            let bids = AccountInfoRef {
+                key: &bids_pk,
+                owner: &crate::ID,
+                data: bids_data.borrow(),
+            };
+            let asks = AccountInfoRef {
+                key: &asks_pk,
+                owner: &crate::ID,
+                data: asks_data.borrow(),
+            };
+            oracle_market
+                .oracle_price(Some(&bids), Some(&asks), now_ts, 0)
+                .unwrap()
+        };
+
+        let new_order = |book: &mut Orderbook,
+                         event_heap: &mut EventHeap,
+                         market: &mut Market,
+                         side,
+                         price_lots| {
+            let mut account = OpenOrdersAccount::default_for_tests();
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots: 1,
+                    max_quote_lots_including_fees: i64::MAX / market.quote_lot_size,
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type: PostOrderType::Limit,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                    reduce_only: false,
+                },
+                market,
+                &market_pk,
+                event_heap,
+                None,
+                oracle_price_lots,
+                Some(&mut account),
+                &Pubkey::new_unique(),
+                now_ts,
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+            account
+        };
+
+        let cancel = |book: &mut Orderbook, account: &mut OpenOrdersAccount, market: Market| {
+            let (order_id, side_and_tree) = {
+                let oo = account.open_order_by_raw_index(0);
+                (oo.id, oo.side_and_tree())
+            };
+            book.cancel_order(account, order_id, side_and_tree, market, None)
+                .unwrap();
+        };
+
+        assert_eq!(oracle_price(&mut oracle_market, &book, now_ts), None);
+        new_order(&mut book, &mut event_heap, &mut market, Side::Bid, 990);
+        // A one sided book has no mid
+        assert_eq!(oracle_price(&mut oracle_market, &book, now_ts), None);
+        new_order(&mut book, &mut event_heap, &mut market, Side::Ask, 1010);
+        assert_eq!(
+            oracle_price(&mut oracle_market, &book, now_ts),
+            Some(I80F48::from_num(1000))
+        );
+
+        // Tight quotes move the mid only while they are on the book
+        let mut bid = new_order(&mut book, &mut event_heap, &mut market, Side::Bid, 1004);
+        let mut ask = new_order(&mut book, &mut event_heap, &mut market, Side::Ask, 1006);
+        assert_eq!(
+            oracle_price(&mut oracle_market, &book, now_ts),
+            Some(I80F48::from_num(1005))
+        );
+        cancel(&mut book, &mut bid, market);
+        cancel(&mut book, &mut ask, market);
+        assert_eq!(
+            oracle_price(&mut oracle_market, &book, now_ts),
+            Some(I80F48::from_num(1000))
+        );
+
+        // The book is stale once its best orders are older than the max staleness
+        assert_eq!(
+            oracle_price(&mut oracle_market, &book, now_ts + 60),
+            Some(I80F48::from_num(1000))
+        );
+        assert_eq!(oracle_price(&mut oracle_market, &book, now_ts + 61), None);
+    }
 }
@@ -54,6 +54,81 @@ pub fn oracle_pegged_price_offset(price_data: u64) -> i64 {
     price_data.wrapping_sub(u64::MAX / 2 + 1) as i64
 }
 
+/// Largest price offset (in lots) an oracle pegged order may have.
+///
+/// Lot offsets above this would produce price data that collides with the
+/// basis point offset range, see ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX.
+pub const MAX_ORACLE_PEGGED_PRICE_OFFSET_LOTS: i64 = (1 << 62) - 1;
+
+/// Top bits of the price data of oracle pegged orders whose offset is expressed
+/// in basis points of the oracle price instead of in lots.
+///
+/// Since all these keys share a prefix, they form a single subtree of the oracle
+/// pegged OrderTree. Within it, a larger bps offset always means a larger price,
+/// so the tree ordering stays correct for any oracle price.
+pub const ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX: u64 = 0b11 << 62;
+
+/// Number of key bits covered by ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX
+pub const ORACLE_PEGGED_BPS_PREFIX_LEN: u32 = 2;
+
+/// Creates price data for an oracle pegged order from a price offset in basis points
+///
+/// Reverse of oracle_pegged_price_offset_bps()
+pub fn oracle_pegged_bps_price_data(price_offset_bps: i32) -> u64 {
+    // Map i32::MIN to 0 and i32::MAX to u32::MAX below the prefix, keeping the order
+    let offset = (price_offset_bps as i64 - i32::MIN as i64) as u64;
+    ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX | offset
+}
+
+/// Retrieves the price offset (in bps) from an oracle pegged order's price data
+///
+/// Reverse of oracle_pegged_bps_price_data()
+pub fn oracle_pegged_price_offset_bps(price_data: u64) -> i32 {
+    let offset = price_data & !ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX;
+    (offset as i64 + i32::MIN as i64) as i32
+}
+
+/// Does this oracle pegged order's price data store an offset in basis points?
+pub fn is_oracle_pegged_bps_price_data(price_data: u64) -> bool {
+    price_data & ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX == ORACLE_PEGGED_BPS_PRICE_DATA_PREFIX
+}
+
+/// Computes the price (in lots) of an order pegged `price_offset_bps` basis points
+/// away from the oracle price.
+///
+/// Bids are rounded down and asks up, so the order is never more aggressive than
+/// requested. The result saturates at the i64 range.
+pub fn oracle_pegged_bps_price_lots(
+    side: Side,
+    oracle_price_lots: i64,
+    price_offset_bps: i32,
+) -> i64 {
+    let numerator = oracle_price_lots as i128 * (10_000 + price_offset_bps as i128);
+    let price = match side {
+        Side::Bid => numerator.div_euclid(10_000),
+        Side::Ask => -(-numerator).div_euclid(10_000),
+    };
+    price.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
+/// Computes the bps offset at which an order pegged to `oracle_price_lots` is not
+/// more aggressive than `price_lots`.
+///
+/// Used when an order type (PostOnlySlide) moves the price of a pegged order.
+pub fn oracle_pegged_price_offset_bps_for_price(
+    side: Side,
+    oracle_price_lots: i64,
+    price_lots: i64,
+) -> i32 {
+    let numerator = (price_lots as i128 - oracle_price_lots as i128) * 10_000;
+    let denominator = oracle_price_lots as i128;
+    let offset = match side {
+        Side::Bid => numerator.div_euclid(denominator),
+        Side::Ask => -(-numerator).div_euclid(denominator),
+    };
+    offset.clamp(i32::MIN as i128, i32::MAX as i128) as i32
+}
+
 /// Creates price data for a fixed order's price
 ///
 /// Reverse of fixed_price_lots()
@@ -387,6 +462,69 @@ mod tests {
         assert_eq!(oracle_pegged_price_data(i64::MIN), 0);
         assert_eq!(oracle_pegged_price_data(i64::MAX), u64::MAX);
         assert_eq!(oracle_pegged_price_data(0), -(i64::MIN as i128) as u64); // remember -i64::MIN is not a valid i64
+
+        let bps_seq = [i32::MIN, -9_999, -1, 0, 1, 50, i32::MAX];
+        for price_offset_bps in bps_seq {
+            let price_data = oracle_pegged_bps_price_data(price_offset_bps);
+            assert!(is_oracle_pegged_bps_price_data(price_data));
+            assert_eq!(price_offset_bps, oracle_pegged_price_offset_bps(price_data));
+        }
+        for (lhs, rhs) in bps_seq.iter().tuple_windows() {
+            let l_price_data = oracle_pegged_bps_price_data(*lhs);
+            let r_price_data = oracle_pegged_bps_price_data(*rhs);
+            assert!(l_price_data < r_price_data);
+        }
+
+        // lot offsets never reach into the bps range
+        for price_offset in [i64::MIN, 0, MAX_ORACLE_PEGGED_PRICE_OFFSET_LOTS] {
+            assert!(!is_oracle_pegged_bps_price_data(oracle_pegged_price_data(
+                price_offset
+            )));
+        }
+        assert!(is_oracle_pegged_bps_price_data(oracle_pegged_price_data(
+            MAX_ORACLE_PEGGED_PRICE_OFFSET_LOTS + 1
+        )));
+    }
+
+    #[test]
+    fn oracle_pegged_bps_price() {
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Bid, 10_000, 25), 10_025);
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Ask, 10_000, -25), 9_975);
+
+        // rounding is never in favor of the order
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Bid, 999, 5), 999);
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Ask, 999, 5), 1000);
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Bid, 999, -5), 998);
+        assert_eq!(oracle_pegged_bps_price_lots(Side::Ask, 999, -5), 999);
+
+        assert_eq!(
+            oracle_pegged_bps_price_lots(Side::Bid, i64::MAX, i32::MAX),
+            i64::MAX
+        );
+        assert!(oracle_pegged_bps_price_lots(Side::Ask, 100, -10_000) < 1);
+
+        for oracle_price_lots in [1, 999, 10_000, 123_456_789] {
+            for price_lots in [1, 500, 1_000, 10_001, 200_000] {
+                let bid_offset = oracle_pegged_price_offset_bps_for_price(
+                    Side::Bid,
+                    oracle_price_lots,
+                    price_lots,
+                );
+                assert!(
+                    oracle_pegged_bps_price_lots(Side::Bid, oracle_price_lots, bid_offset)
+                        <= price_lots
+                );
+                let ask_offset = oracle_pegged_price_offset_bps_for_price(
+                    Side::Ask,
+                    oracle_price_lots,
+                    price_lots,
+                );
+                assert!(
+                    oracle_pegged_bps_price_lots(Side::Ask, oracle_price_lots, ask_offset)
+                        >= price_lots
+                );
+            }
+        }
     }
 
     #[test]
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use super::*;
 use crate::error::*;
+use crate::state::Market;
 
 ///  order parameters
 pub struct Order {
@@ -22,6 +23,9 @@ pub struct Order {
     /// Configure how matches with order of the same owner are handled
     pub self_trade_behavior: SelfTradeBehavior,
 
+    /// Only use the free balance on the open orders account
+    pub reduce_only: bool,
+
     /// Order type specific params
     pub params: OrderParams,
 }
@@ -40,9 +44,17 @@ pub enum OrderParams {
         order_type: PostOrderType,
         peg_limit: i64,
     },
+    OraclePeggedBps {
+        price_offset_bps: i32,
+        order_type: PostOrderType,
+        peg_limit: i64,
+    },
     FillOrKill {
         price_lots: i64,
     },
+    ExactOut {
+        price_lots: i64,
+    },
 }
 
 impl Order {
@@ -68,9 +80,40 @@ impl Order {
         let order_type = match self.params {
             OrderParams::Fixed { order_type, .. } => order_type,
             OrderParams::OraclePegged { order_type, .. } => order_type,
+            OrderParams::OraclePeggedBps { order_type, .. } => order_type,
             _ => return false,
         };
-        order_type == PostOrderType::PostOnly || order_type == PostOrderType::PostOnlySlide
+        order_type == PostOrderType::PostOnly
+            || order_type == PostOrderType::PostOnlySlide
+            || order_type == PostOrderType::PostOnlyCancelReplace
+    }
+
+    /// Does this post-only order cancel its owner's crossing orders instead of not posting?
+    pub fn is_post_only_cancel_replace(&self) -> bool {
+        match self.params {
+            OrderParams::Fixed { order_type, .. }
+            | OrderParams::OraclePegged { order_type, .. }
+            | OrderParams::OraclePeggedBps { order_type, .. } => {
+                order_type == PostOrderType::PostOnlyCancelReplace
+            }
+            _ => false,
+        }
+    }
+
+    /// Cap a reduce-only order to the free balance it may use: base for asks, quote for bids
+    pub fn cap_to_free_balance(&mut self, market: &Market, base_free: u64, quote_free: u64) {
+        match self.side {
+            Side::Ask => {
+                self.max_base_lots = self
+                    .max_base_lots
+                    .min(market.max_base_lots_from_lamports(base_free));
+            }
+            Side::Bid => {
+                self.max_quote_lots_including_fees = self
+                    .max_quote_lots_including_fees
+                    .min(market.max_quote_lots_from_lamports(quote_free));
+            }
+        }
     }
 
     /// Is this order required to be executed completely? It will fail if it would do a partial execution.
@@ -78,11 +121,19 @@ impl Order {
         matches!(self.params, OrderParams::FillOrKill { .. })
     }
 
+    /// Is this order required to deliver its full output amount net of taker fees? That is
+    /// max_base_lots for bids and max_quote_lots_including_fees for asks.
+    pub fn is_exact_out(&self) -> bool {
+        matches!(self.params, OrderParams::ExactOut { .. })
+    }
+
     /// Order tree that this order should be added to
     pub fn post_target(&self) -> Option<BookSideOrderTree> {
         match self.params {
             OrderParams::Fixed { .. } => Some(BookSideOrderTree::Fixed),
-            OrderParams::OraclePegged { .. } => Some(BookSideOrderTree::OraclePegged),
+            OrderParams::OraclePegged { .. } | OrderParams::OraclePeggedBps { .. } => {
+                Some(BookSideOrderTree::OraclePegged)
+            }
             _ => None,
         }
     }
@@ -95,6 +146,7 @@ impl Order {
         oracle_price_lots: Option<i64>,
         price_lots: i64,
         order_type: PostOrderType,
+        tick_size_lots: i64,
         order_book: &Orderbook,
     ) -> i64 {
         if order_type == PostOrderType::PostOnlySlide {
@@ -102,7 +154,7 @@ impl Order {
                 .bookside(self.side.invert_side())
                 .best_price(now_ts, oracle_price_lots)
             {
-                post_only_slide_limit(self.side, best_other_price, price_lots)
+                post_only_slide_limit(self.side, best_other_price, price_lots, tick_size_lots)
             } else {
                 price_lots
             }
@@ -114,16 +166,22 @@ impl Order {
     /// Compute the price_lots this order is currently at, as well as the price_data that
     /// would be stored in its OrderTree node if the order is posted to the orderbook.
     /// Will fail for oracle peg if there is no oracle price passed.
+    ///
+    /// PostOnlySlide orders slide to a multiple of `tick_size_lots`.
     pub fn price(
         &self,
         now_ts: u64,
         oracle_price_lots: Option<i64>,
+        tick_size_lots: i64,
         order_book: &Orderbook,
     ) -> Result<(i64, u64)> {
+        // bps offset the order is stored at, if it's pegged in bps
+        let mut price_offset_bps = None;
         let price_lots = match self.params {
             OrderParams::Market => market_order_limit_for_side(self.side),
             OrderParams::ImmediateOrCancel { price_lots } => price_lots,
             OrderParams::FillOrKill { price_lots } => price_lots,
+            OrderParams::ExactOut { price_lots } => price_lots,
             OrderParams::Fixed {
                 price_lots,
                 order_type,
@@ -132,6 +190,7 @@ impl Order {
                 oracle_price_lots,
                 price_lots,
                 order_type,
+                tick_size_lots,
                 order_book,
             ),
             OrderParams::OraclePegged {
@@ -149,15 +208,63 @@ impl Order {
                     oracle_price_lots,
                     price_lots,
                     order_type,
+                    tick_size_lots,
                     order_book,
                 )
             }
+            OrderParams::OraclePeggedBps {
+                price_offset_bps: requested_offset_bps,
+                order_type,
+                ..
+            } => {
+                // The offset is relative to the oracle price, which needs to be positive
+                let oracle_price_lots = oracle_price_lots
+                    .filter(|&price_lots| price_lots >= 1)
+                    .ok_or(OpenBookError::OraclePegInvalidOracleState)?;
+                let price_lots = oracle_pegged_bps_price_lots(
+                    self.side,
+                    oracle_price_lots,
+                    requested_offset_bps,
+                );
+
+                let adjusted_price_lots = self.price_for_order_type(
+                    now_ts,
+                    Some(oracle_price_lots),
+                    price_lots,
+                    order_type,
+                    tick_size_lots,
+                    order_book,
+                );
+                if adjusted_price_lots == price_lots {
+                    price_offset_bps = Some(requested_offset_bps);
+                    price_lots
+                } else {
+                    // Store the offset that is not more aggressive than the adjusted price
+                    let offset_bps = oracle_pegged_price_offset_bps_for_price(
+                        self.side,
+                        oracle_price_lots,
+                        adjusted_price_lots,
+                    );
+                    price_offset_bps = Some(offset_bps);
+                    oracle_pegged_bps_price_lots(self.side, oracle_price_lots, offset_bps)
+                }
+            }
         };
         require_gte!(price_lots, 1, OpenBookError::InvalidPriceLots);
         let price_data = match self.params {
             OrderParams::OraclePegged { .. } => {
                 // unwrap cannot fail (already handled above)
-                oracle_pegged_price_data(price_lots - oracle_price_lots.unwrap())
+                let price_offset_lots = price_lots - oracle_price_lots.unwrap();
+                require_gte!(
+                    MAX_ORACLE_PEGGED_PRICE_OFFSET_LOTS,
+                    price_offset_lots,
+                    OpenBookError::InvalidPriceLots
+                );
+                oracle_pegged_price_data(price_offset_lots)
+            }
+            // unwrap cannot fail (always set above)
+            OrderParams::OraclePeggedBps { .. } => {
+                oracle_pegged_bps_price_data(price_offset_bps.unwrap())
             }
             _ => fixed_price_data(price_lots)?,
         };
@@ -168,6 +275,7 @@ impl Order {
     pub fn peg_limit(&self) -> i64 {
         match self.params {
             OrderParams::OraclePegged { peg_limit, .. } => peg_limit,
+            OrderParams::OraclePeggedBps { peg_limit, .. } => peg_limit,
             _ => -1,
         }
     }
@@ -182,10 +290,11 @@ fn market_order_limit_for_side(side: Side) -> i64 {
 }
 
 /// The limit to use for PostOnlySlide orders: the tinyest bit better than
-/// the best opposing order
-fn post_only_slide_limit(side: Side, best_other_side: i64, limit: i64) -> i64 {
+/// the best opposing order, rounded away from it onto the tick grid
+fn post_only_slide_limit(side: Side, best_other_side: i64, limit: i64, tick_size_lots: i64) -> i64 {
+    let tick = tick_size_lots.max(1);
     match side {
-        Side::Bid => limit.min(best_other_side - 1),
-        Side::Ask => limit.max(best_other_side + 1),
+        Side::Bid => limit.min((best_other_side - 1) / tick * tick),
+        Side::Ask => limit.max((best_other_side + tick) / tick * tick),
     }
 }
@@ -43,6 +43,16 @@ pub enum PlaceOrderType {
     /// Take existing orders up to price, max_base_quantity and max_quote_quantity.
     /// Abort if partially executed, never place an order on the book.
     FillOrKill = 5,
+
+    /// Take existing orders up to price until the full output amount net of taker fees
+    /// is reached: max_base_quantity for bids, max_quote_quantity for asks, which may
+    /// overshoot it by less than a base lot.
+    /// Abort if the output cannot be fully delivered, never place an order on the book.
+    ExactOut = 6,
+
+    /// Like PostOnly, but the owner's own orders that would match are cancelled
+    /// first. Does nothing, and cancels nothing, if other owners' orders would match.
+    PostOnlyCancelReplace = 7,
 }
 
 impl PlaceOrderType {
@@ -51,9 +61,11 @@ impl PlaceOrderType {
             Self::Market => Err(OpenBookError::InvalidOrderPostMarket.into()),
             Self::ImmediateOrCancel => Err(OpenBookError::InvalidOrderPostIOC.into()),
             Self::FillOrKill => Err(OpenBookError::InvalidOrderPostIOC.into()),
+            Self::ExactOut => Err(OpenBookError::InvalidOrderPostIOC.into()),
             Self::Limit => Ok(PostOrderType::Limit),
             Self::PostOnly => Ok(PostOrderType::PostOnly),
             Self::PostOnlySlide => Ok(PostOrderType::PostOnlySlide),
+            Self::PostOnlyCancelReplace => Ok(PostOrderType::PostOnlyCancelReplace),
         }
     }
 }
@@ -82,6 +94,10 @@ pub enum PostOrderType {
     /// If existing orders match with this order, adjust the price to just barely
     /// not match. Always places an order on the book.
     PostOnlySlide = 4,
+
+    /// Like PostOnly, but the owner's own orders that would match are cancelled
+    /// first. Does nothing, and cancels nothing, if other owners' orders would match.
+    PostOnlyCancelReplace = 7,
 }
 
 #[derive(
@@ -108,9 +108,46 @@ impl OrderTreeNodes {
     }
 
     pub fn find_worst(&self, root: &OrderTreeRoot) -> Option<(NodeHandle, &LeafNode)> {
-        match self.order_tree_type() {
-            OrderTreeType::Bids => self.min_leaf(root),
-            OrderTreeType::Asks => self.max_leaf(root),
+        self.find_worst_in_subtree(root.node(), None)
+    }
+
+    /// Like find_worst(), but only considers leaves below `start` that are
+    /// not part of the `excluded` subtree.
+    pub fn find_worst_in_subtree(
+        &self,
+        start: Option<NodeHandle>,
+        excluded: Option<NodeHandle>,
+    ) -> Option<(NodeHandle, &LeafNode)> {
+        let find_max = self.order_tree_type() == OrderTreeType::Asks;
+        self.subtree_leaf_min_max(find_max, start, excluded)
+    }
+
+    /// Returns the handle of the subtree that holds exactly the leaves whose keys
+    /// share the top `prefix_len` bits with `prefix`, if there are any.
+    pub fn find_prefix_subtree(
+        &self,
+        root: &OrderTreeRoot,
+        prefix: u128,
+        prefix_len: u32,
+    ) -> Option<NodeHandle> {
+        let shares_prefix = |key: u128, len: u32| len == 0 || (key ^ prefix) >> (128 - len) == 0;
+
+        let mut node_handle = root.node()?;
+        loop {
+            match self.node(node_handle)?.case()? {
+                NodeRef::Inner(inner) => {
+                    if !shares_prefix(inner.key, inner.prefix_len.min(prefix_len)) {
+                        return None;
+                    }
+                    if inner.prefix_len >= prefix_len {
+                        return Some(node_handle);
+                    }
+                    node_handle = inner.walk_down(prefix).0;
+                }
+                NodeRef::Leaf(leaf) => {
+                    return shares_prefix(leaf.key, prefix_len).then_some(node_handle);
+                }
+            }
         }
     }
 
@@ -175,14 +212,27 @@ impl OrderTreeNodes {
         find_max: bool,
         root: &OrderTreeRoot,
     ) -> Option<(NodeHandle, &LeafNode)> {
-        let mut node_handle: NodeHandle = root.node()?;
+        self.subtree_leaf_min_max(find_max, root.node(), None)
+    }
+
+    fn subtree_leaf_min_max(
+        &self,
+        find_max: bool,
+        start: Option<NodeHandle>,
+        excluded: Option<NodeHandle>,
+    ) -> Option<(NodeHandle, &LeafNode)> {
+        let mut node_handle: NodeHandle = start.filter(|&s| Some(s) != excluded)?;
 
         let i = usize::from(find_max);
         loop {
             let node_contents = self.node(node_handle)?;
             match node_contents.case()? {
                 NodeRef::Inner(inner) => {
-                    node_handle = inner.children[i];
+                    node_handle = if Some(inner.children[i]) == excluded {
+                        inner.children[1 - i]
+                    } else {
+                        inner.children[i]
+                    };
                 }
                 NodeRef::Leaf(leaf) => {
                     return Some((node_handle, leaf));
@@ -11,10 +11,22 @@ pub struct OrderTreeIter<'a> {
     /// either 0, 1 to iterate low-to-high, or 1, 0 to iterate high-to-low
     left: usize,
     right: usize,
+
+    /// Subtree that is skipped entirely
+    excluded: Option<NodeHandle>,
 }
 
 impl<'a> OrderTreeIter<'a> {
     pub fn new(order_tree: &'a OrderTreeNodes, root: &OrderTreeRoot) -> Self {
+        Self::new_in_subtree(order_tree, root.node(), None)
+    }
+
+    /// Iterate over the leaves below `start`, skipping the subtree at `excluded`
+    pub fn new_in_subtree(
+        order_tree: &'a OrderTreeNodes,
+        start: Option<NodeHandle>,
+        excluded: Option<NodeHandle>,
+    ) -> Self {
         let (left, right) = if order_tree.order_tree_type() == OrderTreeType::Bids {
             (1, 0)
         } else {
@@ -28,8 +40,9 @@ impl<'a> OrderTreeIter<'a> {
             next_leaf: None,
             left,
             right,
+            excluded,
         };
-        if let Some(r) = root.node() {
+        if let Some(r) = start.filter(|&r| Some(r) != excluded) {
             iter.next_leaf = iter.find_leftmost_leaf(r);
         }
         iter
@@ -52,8 +65,14 @@ impl<'a> OrderTreeIter<'a> {
         loop {
             match self.order_tree.node(current).unwrap().case().unwrap() {
                 NodeRef::Inner(inner) => {
-                    self.stack.push(inner);
-                    current = inner.children[self.left];
+                    let left = inner.children[self.left];
+                    if Some(left) == self.excluded {
+                        // both children can't be excluded, no need to come back here
+                        current = inner.children[self.right];
+                    } else {
+                        self.stack.push(inner);
+                        current = left;
+                    }
                 }
                 NodeRef::Leaf(leaf) => {
                     return Some((current, leaf));
@@ -72,12 +91,17 @@ impl<'a> Iterator for OrderTreeIter<'a> {
 
         // start popping from stack and get the other child
         let current_leaf = self.next_leaf;
-        self.next_leaf = match self.stack.pop() {
-            None => None,
-            Some(inner) => {
-                let start = inner.children[self.right];
-                // go down the left branch as much as possible until reaching a leaf
-                self.find_leftmost_leaf(start)
+        self.next_leaf = loop {
+            match self.stack.pop() {
+                None => break None,
+                Some(inner) => {
+                    let start = inner.children[self.right];
+                    if Some(start) == self.excluded {
+                        continue;
+                    }
+                    // go down the left branch as much as possible until reaching a leaf
+                    break self.find_leftmost_leaf(start);
+                }
             }
         };
 
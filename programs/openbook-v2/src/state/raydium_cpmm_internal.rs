@@ -0,0 +1,38 @@
+use anchor_lang::{account, declare_id};
+use solana_program::pubkey::Pubkey;
+
+#[cfg(feature = "devnet")]
+declare_id!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+#[cfg(not(feature = "devnet"))]
+declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+pub const AUTH_SEED: &[u8] = b"vault_and_lp_mint_auth_seed";
+
+/// The authority owning the token vaults of all pools
+///
+/// The pool state doesn't store its reserves, they are the balances of these vaults.
+#[cfg(not(feature = "devnet"))]
+pub fn vault_authority() -> Pubkey {
+    solana_program::pubkey!("GpMZbSM2GgvTKHJirzeGfMFoaZ8UR2X7F4v8vHTvxFbL")
+}
+
+#[cfg(feature = "devnet")]
+pub fn vault_authority() -> Pubkey {
+    Pubkey::find_program_address(&[AUTH_SEED], &ID).0
+}
+
+/// The leading fields of a pool state, up to its mints
+///
+/// The account is larger: read it from `data[8..8 + size_of::<PoolState>()]`.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct PoolState {
+    pub amm_config: Pubkey,
+    pub pool_creator: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::{account, declare_id, zero_copy};
+use solana_program::pubkey::Pubkey;
+
+#[cfg(feature = "devnet")]
+declare_id!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+#[cfg(not(feature = "devnet"))]
+declare_id!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Results and submissions are fixed point numbers with this many decimals
+pub const PRECISION: u32 = 18;
+
+/// The leading fields of a pull feed, up to its current result
+///
+/// The account is larger: read it from `data[8..8 + size_of::<PullFeedAccountData>()]`.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct PullFeedAccountData {
+    pub submissions: [OracleSubmission; 32],
+    pub authority: Pubkey,
+    pub queue: Pubkey,
+    pub feed_hash: [u8; 32],
+    pub initialized_at: i64,
+    pub permissions: u64,
+    pub max_variance: u64,
+    pub min_responses: u32,
+    pub name: [u8; 32],
+    pub padding1: [u8; 2],
+    pub historical_result_idx: u8,
+    pub min_sample_size: u8,
+    pub last_update_timestamp: i64,
+    pub lut_slot: u64,
+    pub reserved1: [u8; 32],
+    pub result: CurrentResult,
+}
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct OracleSubmission {
+    pub oracle: Pubkey,
+    pub slot: u64,
+    pub landed_at: u64,
+    pub value: i128,
+}
+
+/// Aggregate of the submissions the feed was last updated with
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct CurrentResult {
+    pub value: i128,
+    pub std_dev: i128,
+    pub mean: i128,
+    pub range: i128,
+    pub min_value: i128,
+    pub max_value: i128,
+    pub num_samples: u8,
+    pub submission_idx: u8,
+    pub padding1: [u8; 6],
+    /// Slot of the most recent submission used
+    pub slot: u64,
+    pub min_slot: u64,
+    pub max_slot: u64,
+}
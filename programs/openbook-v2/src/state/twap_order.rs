@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use super::Side;
+
+/// Sells or buys `total_base_lots` over time, in slices of `slice_base_lots`
+///
+/// Each slice is an ImmediateOrCancel order at `limit_price_lots`, placed by any
+/// keeper through `execute_twap_slice` and paid from the free balances of the
+/// open orders account. Slice `i` can be executed between `start_ts + i * interval_seconds`
+/// and the start of the next slice, slices missing their window are skipped.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct TwapOrder {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+
+    pub bump: u8,
+    /// Side, as a `Side` value
+    pub side: u8,
+    pub padding: [u8; 6],
+
+    pub limit_price_lots: i64,
+    pub total_base_lots: i64,
+    pub slice_base_lots: i64,
+    /// Base lots of the slices not executed or skipped yet
+    pub remaining_base_lots: i64,
+    /// Base lots actually filled by the executed slices
+    pub filled_base_lots: i64,
+
+    pub start_ts: u64,
+    pub interval_seconds: u64,
+    /// Index of the first slice that wasn't executed or skipped yet
+    pub next_slice: u64,
+
+    /// Lamports paid to the keeper of each executed slice, out of the account balance
+    pub keeper_fee_lamports: u64,
+
+    pub reserved: [u8; 64],
+}
+
+const_assert_eq!(
+    size_of::<TwapOrder>(),
+    32 * 2 + 1 + 1 + 6 + 8 * 5 + 8 * 3 + 8 + 64
+);
+const_assert_eq!(size_of::<TwapOrder>(), 208);
+const_assert_eq!(size_of::<TwapOrder>() % 8, 0);
+
+impl TwapOrder {
+    pub fn side(&self) -> Side {
+        Side::try_from(self.side).unwrap()
+    }
+
+    pub fn num_slices(&self) -> u64 {
+        let total = self.total_base_lots as u64;
+        let slice = self.slice_base_lots as u64;
+        (total + slice - 1) / slice
+    }
+
+    /// Index of the slice whose window contains `now_ts`, if the order has started
+    pub fn current_slice(&self, now_ts: u64) -> Option<u64> {
+        now_ts
+            .checked_sub(self.start_ts)
+            .map(|elapsed| elapsed / self.interval_seconds)
+    }
+
+    /// Skips the slices whose window has passed and returns the size of the
+    /// slice `slice` to execute now
+    ///
+    /// A remainder below `min_base_lots` couldn't be placed on its own, it is merged
+    /// into the slice.
+    pub fn take_slice(&mut self, slice: u64, min_base_lots: i64) -> i64 {
+        let skipped = slice.saturating_sub(self.next_slice);
+        let skipped_base_lots = (skipped as i64).saturating_mul(self.slice_base_lots);
+        self.remaining_base_lots -= skipped_base_lots.min(self.remaining_base_lots);
+
+        let mut slice_base_lots = self.slice_base_lots.min(self.remaining_base_lots);
+        if self.remaining_base_lots - slice_base_lots < min_base_lots {
+            slice_base_lots = self.remaining_base_lots;
+        }
+        self.remaining_base_lots -= slice_base_lots;
+        self.next_slice = slice + 1;
+        slice_base_lots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn twap(total_base_lots: i64, slice_base_lots: i64) -> TwapOrder {
+        TwapOrder {
+            open_orders_account: Pubkey::default(),
+            market: Pubkey::default(),
+            bump: 0,
+            side: Side::Ask.into(),
+            padding: [0; 6],
+            limit_price_lots: 1,
+            total_base_lots,
+            slice_base_lots,
+            remaining_base_lots: total_base_lots,
+            filled_base_lots: 0,
+            start_ts: 1000,
+            interval_seconds: 60,
+            next_slice: 0,
+            keeper_fee_lamports: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_twap_slices() {
+        let mut t = twap(25, 10);
+        assert_eq!(t.num_slices(), 3);
+        assert_eq!(t.current_slice(999), None);
+        assert_eq!(t.current_slice(1000), Some(0));
+        assert_eq!(t.current_slice(1119), Some(1));
+
+        assert_eq!(t.take_slice(0, 0), 10);
+        assert_eq!(t.remaining_base_lots, 15);
+        assert_eq!(t.next_slice, 1);
+
+        // slice 1 missed its window
+        assert_eq!(t.take_slice(2, 0), 5);
+        assert_eq!(t.remaining_base_lots, 0);
+        assert_eq!(t.next_slice, 3);
+
+        let mut t = twap(25, 10);
+        assert_eq!(t.take_slice(5, 0), 0);
+        assert_eq!(t.remaining_base_lots, 0);
+
+        // a remainder below the minimum is merged into the slice before it
+        let mut t = twap(25, 10);
+        assert_eq!(t.take_slice(0, 6), 10);
+        assert_eq!(t.take_slice(1, 6), 15);
+        assert_eq!(t.remaining_base_lots, 0);
+
+        let mut t = twap(25, 10);
+        assert_eq!(t.take_slice(1, 5), 10);
+        assert_eq!(t.take_slice(2, 5), 5);
+    }
+}
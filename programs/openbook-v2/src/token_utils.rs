@@ -1,6 +1,6 @@
 use super::*;
 use anchor_lang::system_program;
-use anchor_spl::token;
+use anchor_spl::token::{self, Token, TokenAccount};
 
 pub fn token_transfer<
     'info,
@@ -88,3 +88,157 @@ pub fn system_program_transfer<
         Ok(())
     }
 }
+
+/// Move `amount` from `user_account` into `vault` or, when no user token account is
+/// passed on a native mint vault, wrap that many lamports of `native_payer` into it.
+pub fn token_or_native_transfer<'info>(
+    amount: u64,
+    token_program: &Program<'info, Token>,
+    user_account: Option<&Account<'info, TokenAccount>>,
+    vault: &Account<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    native_payer: Option<&Signer<'info>>,
+    system_program: Option<&Program<'info, System>>,
+) -> Result<()> {
+    if let Some(user_account) = user_account {
+        return token_transfer(amount, token_program, user_account, vault, authority);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let (Some(native_payer), Some(system_program)) = (native_payer, system_program) else {
+        return err!(OpenBookError::InvalidInputNativeTransfer);
+    };
+    require_keys_eq!(
+        vault.mint,
+        token::spl_token::native_mint::ID,
+        OpenBookError::InvalidInputNativeTransfer
+    );
+
+    system_program_transfer(amount, system_program, native_payer, vault)?;
+    token::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        token::SyncNative {
+            account: vault.to_account_info(),
+        },
+    ))
+}
+
+/// Accounts to pay out of a native mint vault without a user token account.
+pub struct NativeUnwrap<'info> {
+    pub native_mint: Option<AccountInfo<'info>>,
+    /// Temporary token account at the `[b"Unwrap", market]` PDA
+    pub unwrap_account: Option<AccountInfo<'info>>,
+    /// Pays the rent of the temporary token account and receives the lamports
+    pub destination: Option<AccountInfo<'info>>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Move `amount` out of `vault` into `user_account` or, when no user token account is
+/// passed on a native mint vault, unwrap it as lamports into the native destination.
+///
+/// Unwrapping goes through a temporary token account owned by the market authority that
+/// is closed into the destination right away, so it can only happen once per market and
+/// instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn token_or_native_transfer_signed<'info>(
+    amount: u64,
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    user_account: Option<&Account<'info, TokenAccount>>,
+    native: &NativeUnwrap<'info>,
+    market: &Pubkey,
+    market_authority: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    if let Some(user_account) = user_account {
+        return token_transfer_signed(
+            amount,
+            token_program,
+            vault,
+            user_account,
+            market_authority,
+            seeds,
+        );
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let (Some(native_mint), Some(unwrap_account), Some(destination)) = (
+        &native.native_mint,
+        &native.unwrap_account,
+        &native.destination,
+    ) else {
+        return err!(OpenBookError::InvalidInputNativeTransfer);
+    };
+    require_keys_eq!(
+        vault.mint,
+        token::spl_token::native_mint::ID,
+        OpenBookError::InvalidInputNativeTransfer
+    );
+    let (unwrap_address, bump) =
+        Pubkey::find_program_address(&[b"Unwrap".as_ref(), market.as_ref()], &crate::id());
+    require_keys_eq!(
+        unwrap_account.key(),
+        unwrap_address,
+        OpenBookError::InvalidInputNativeTransfer
+    );
+    let unwrap_seeds: &[&[u8]] = &[b"Unwrap".as_ref(), market.as_ref(), &[bump]];
+
+    // Anyone can send lamports to the PDA, so don't rely on create_account
+    let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+    system_program_transfer(
+        rent.saturating_sub(unwrap_account.lamports()),
+        &native.system_program,
+        destination,
+        unwrap_account,
+    )?;
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            native.system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: unwrap_account.clone(),
+            },
+            &[unwrap_seeds],
+        ),
+        TokenAccount::LEN as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            native.system_program.clone(),
+            system_program::Assign {
+                account_to_assign: unwrap_account.clone(),
+            },
+            &[unwrap_seeds],
+        ),
+        &token::ID,
+    )?;
+    token::initialize_account3(CpiContext::new(
+        token_program.to_account_info(),
+        token::InitializeAccount3 {
+            account: unwrap_account.clone(),
+            mint: native_mint.clone(),
+            authority: market_authority.clone(),
+        },
+    ))?;
+
+    token_transfer_signed(
+        amount,
+        token_program,
+        &vault.to_account_info(),
+        unwrap_account,
+        market_authority,
+        seeds,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: unwrap_account.clone(),
+            destination: destination.clone(),
+            authority: market_authority.clone(),
+        },
+        &[seeds],
+    ))
+}
@@ -13,6 +13,7 @@ pub use super::program_test;
 pub use utils::assert_equal_fixed_f64 as assert_equal;
 
 mod test;
+mod test_auction;
 mod test_crank;
 mod test_create_market;
 mod test_edit_order;
@@ -20,9 +21,12 @@ mod test_fees;
 mod test_fill_or_kill_order;
 mod test_indexer;
 mod test_multiple_orders;
+mod test_native_sol;
 mod test_oracle_peg;
 mod test_order_types;
 mod test_permissioned;
 mod test_place_order_remaining;
+mod test_route_take_order;
 mod test_self_trade;
 mod test_take_order;
+mod test_twap;
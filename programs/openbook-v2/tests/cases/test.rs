@@ -149,8 +149,8 @@ async fn test_simple_settle() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -175,8 +175,8 @@ async fn test_simple_settle() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -294,8 +294,8 @@ async fn test_delegate_settle() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: payer_token_0,
-            user_quote_account: payer_token_1,
+            user_base_account: Some(payer_token_0),
+            user_quote_account: Some(payer_token_1),
             referrer_account: None,
         },
     )
@@ -311,8 +311,8 @@ async fn test_delegate_settle() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -338,8 +338,8 @@ async fn test_delegate_settle() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: payer_token_0,
-            user_quote_account: payer_token_1,
+            user_base_account: Some(payer_token_0),
+            user_quote_account: Some(payer_token_1),
             referrer_account: None,
         },
     )
@@ -476,8 +476,8 @@ async fn test_cancel_orders() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             base_amount: 10000,
             quote_amount: 0,
         },
@@ -541,8 +541,8 @@ async fn test_cancel_orders() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -781,3 +781,132 @@ async fn test_expired_orders() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_order_lifecycle_logs() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 1,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    let placed = solana.program_log_events::<openbook_v2::logs::OrderPlacedLog>();
+    assert_eq!(placed.len(), 1);
+    let order_id = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .open_orders[0]
+        .id;
+    assert_eq!(placed[0].market, market);
+    assert_eq!(placed[0].owner, account_1);
+    assert_eq!(placed[0].order_id, order_id);
+    assert_eq!(placed[0].client_order_id, 1);
+    assert_eq!(placed[0].side, u8::from(Side::Bid));
+    assert_eq!(placed[0].order_tree, u8::from(BookSideOrderTree::Fixed));
+    assert_eq!(placed[0].price, price_lots);
+    assert_eq!(placed[0].quantity, 1);
+
+    send_tx(
+        solana,
+        CancelOrderInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_1,
+            order_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    let cancelled = solana.program_log_events::<openbook_v2::logs::OrderCancelledLog>();
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0].market, market);
+    assert_eq!(cancelled[0].owner, account_1);
+    assert_eq!(cancelled[0].order_id, order_id);
+    assert_eq!(cancelled[0].client_order_id, 1);
+    assert_eq!(cancelled[0].price_data, placed[0].price_data);
+    assert_eq!(cancelled[0].quantity, 1);
+
+    // A bid expiring in 2s is dropped by the next matching order
+    let now_ts: u64 = solana.get_clock().await.unix_timestamp as u64;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 2,
+            expiry_timestamp: now_ts + 2,
+            ..place_bid_ix
+        },
+    )
+    .await
+    .unwrap();
+    let order_id = solana.program_log_events::<openbook_v2::logs::OrderPlacedLog>()[0].order_id;
+
+    solana.advance_clock(2).await;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 3,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let expired = solana.program_log_events::<openbook_v2::logs::OrderExpiredLog>();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].owner, account_1);
+    assert_eq!(expired[0].order_id, order_id);
+    assert_eq!(expired[0].client_order_id, 2);
+    assert_eq!(expired[0].side, u8::from(Side::Bid));
+    assert_eq!(expired[0].quantity, 1);
+
+    // The ask rests on the book instead
+    let placed = solana.program_log_events::<openbook_v2::logs::OrderPlacedLog>();
+    assert_eq!(placed.len(), 1);
+    assert_eq!(placed[0].owner, account_2);
+    assert_eq!(placed[0].side, u8::from(Side::Ask));
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use super::*;
+
+#[tokio::test]
+async fn test_auction_uncross() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        close_market_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        close_market_admin_bool: true,
+        maker_fee: 0,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    let now_ts = solana.get_clock().await.unix_timestamp;
+
+    let result = send_tx(
+        solana,
+        StartAuctionInstruction {
+            close_market_admin,
+            market,
+            auction_end_ts: now_ts - 1,
+        },
+    )
+    .await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::InvalidInputAuction.error_code(),
+        "Auction end must be in the future".into(),
+    );
+
+    send_tx(
+        solana,
+        StartAuctionInstruction {
+            close_market_admin,
+            market,
+            auction_end_ts: now_ts + 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots: price_lots + 10,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots: price_lots - 10,
+        max_base_lots: 1,
+        ..place_bid_ix.clone()
+    };
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots + 5,
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Crossing orders rest on the book without matching
+    {
+        let market_acc = solana.get_account::<Market>(market).await;
+        let event_heap = solana
+            .get_account_boxed::<EventHeap>(market_acc.event_heap)
+            .await;
+        assert_eq!(event_heap.header.count(), 0);
+
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 2);
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 2);
+    }
+
+    // Take-only orders can't execute
+    let result = send_tx(
+        solana,
+        PlaceOrderInstruction {
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            ..place_ask_ix.clone()
+        },
+    )
+    .await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::MarketInAuction.error_code(),
+        "Market is in auction".into(),
+    );
+
+    let uncross_ix = UncrossAuctionInstruction { market, limit: 10 };
+
+    let result = send_tx(solana, uncross_ix.clone()).await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::AuctionNotEnded.error_code(),
+        "Auction has not ended".into(),
+    );
+
+    solana.advance_clock(10).await;
+    send_tx(solana, uncross_ix.clone()).await.unwrap();
+
+    // Most volume is matched at the higher ask, both asks fill against the bid there
+    {
+        let market_acc = solana.get_account::<Market>(market).await;
+        assert!(!market_acc.is_in_auction());
+        assert_eq!(market_acc.auction_price_lots, 0);
+
+        let event_heap = solana
+            .get_account_boxed::<EventHeap>(market_acc.event_heap)
+            .await;
+        assert_eq!(event_heap.header.count(), 4);
+    }
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    // One fill log per match, each with its own seq num and no taker fee
+    let fills = solana.program_log_events::<openbook_v2::logs::FillLog>();
+    assert_eq!(fills.len(), 2);
+    assert_ne!(fills[0].seq_num, fills[1].seq_num);
+    for fill in &fills {
+        assert_eq!(fill.maker, account_1);
+        assert_eq!(fill.taker, account_2);
+        assert_eq!(fill.taker_fee_ceil, 0);
+        assert_eq!(fill.price, price_lots + 5);
+        assert_eq!(fill.quantity, 1);
+    }
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+
+        // The bid locked 5 quote lots more per base lot than the clearing price
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.bids_quote_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 200);
+        assert_eq!(open_orders_account_1.position.quote_free_native, 100);
+
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+        assert_eq!(
+            open_orders_account_2.position.quote_free_native,
+            2 * (price_lots + 5) as u64 * 10
+        );
+    }
+
+    // Orders match again once the auction is over
+    send_tx(solana, uncross_ix).await.unwrap_err();
+    send_tx(solana, place_bid_ix).await.unwrap();
+    send_tx(solana, place_ask_ix).await.unwrap();
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_market_in_auction() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let market = TestKeypair::new();
+    let auction_end_ts = solana.get_clock().await.unix_timestamp + 60;
+
+    // Both makers of an auction match get rebates, nobody pays taker fees for them
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market,
+                quote_lot_size: 10,
+                base_lot_size: 100,
+                maker_fee: -100,
+                taker_fee: 200,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                auction_end_ts,
+                ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputAuction.into())
+    );
+
+    send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            auction_end_ts,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let market = solana.get_account::<Market>(market.pubkey()).await;
+    assert!(market.is_in_auction());
+    assert_eq!(market.auction_end_ts, auction_end_ts);
+
+    Ok(())
+}
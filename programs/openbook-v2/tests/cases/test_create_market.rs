@@ -145,3 +145,190 @@ async fn test_with_wrong_oracle_types() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_with_market_oracle() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let source_market = TestKeypair::new();
+    let market = TestKeypair::new();
+
+    send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market: source_market,
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    // The source market's fills can be moved by trading on it, so a twap guard is required
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market,
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                ..CreateMarketInstruction::with_new_book_and_heap(
+                    solana,
+                    Some(source_market.pubkey()),
+                    None
+                )
+                .await
+            },
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidInputMarketOracle.into())
+    );
+
+    send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market,
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            max_twap_deviation: Some(0.1),
+            ..CreateMarketInstruction::with_new_book_and_heap(
+                solana,
+                Some(source_market.pubkey()),
+                None,
+            )
+            .await
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_min_size_and_tick_size() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let market = TestKeypair::new();
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market,
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                tick_size_lots: -1,
+                ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+            },
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidInputMarketLimits.into())
+    );
+
+    send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market,
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            min_base_lots: 2,
+            min_quote_lots: 1_000,
+            tick_size_lots: 10,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let market = solana.get_account::<Market>(market.pubkey()).await;
+    assert_eq!(market.min_base_lots, 2);
+    assert_eq!(market.min_quote_lots, 1_000);
+    assert_eq!(market.tick_size_lots, 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_market_reclaims_rent() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        close_market_admin,
+        market,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        close_market_admin_bool: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    let market_account = solana.get_account::<Market>(market).await;
+    let closed_accounts = [
+        market,
+        market_account.bids,
+        market_account.asks,
+        market_account.event_heap,
+    ];
+
+    let mut rent = 0;
+    for account in closed_accounts {
+        rent += solana.get_account_lamports(account).await.unwrap();
+    }
+
+    let sol_destination = TestKeypair::new().pubkey();
+    let close_ix = CloseMarketInstruction {
+        close_market_admin,
+        market,
+        sol_destination,
+    };
+
+    // Only an expired market can be closed
+    assert!(send_tx(solana, close_ix.clone()).await.is_err());
+
+    send_tx(
+        solana,
+        SetMarketExpiredInstruction {
+            close_market_admin,
+            market,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(solana, close_ix).await.unwrap();
+
+    assert_eq!(
+        solana.get_account_lamports(sol_destination).await,
+        Some(rent)
+    );
+    for account in closed_accounts {
+        assert_eq!(solana.get_account_lamports(account).await, None);
+    }
+
+    Ok(())
+}
@@ -126,8 +126,8 @@ async fn test_fees_accrued() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -148,6 +148,7 @@ async fn test_fees_accrued() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -342,8 +343,8 @@ async fn test_maker_fees() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -365,8 +366,8 @@ async fn test_maker_fees() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: Some(owner_token_1),
         },
     )
@@ -387,6 +388,7 @@ async fn test_maker_fees() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -477,14 +479,15 @@ async fn test_market_maker_fees() -> Result<(), TransportError> {
         PlaceTakeOrderInstruction {
             market,
             signer: jup_user,
-            user_base_account: jup_user_token_0,
-            user_quote_account: jup_user_token_1,
+            user_base_account: Some(jup_user_token_0),
+            user_quote_account: Some(jup_user_token_1),
             market_base_vault,
             market_quote_vault,
             side: Side::Ask,
             price_lots,
             max_base_lots: 1,
             max_quote_lots_including_fees: 10040,
+            order_type: PlaceOrderType::ImmediateOrCancel,
             open_orders_admin: None,
         },
     )
@@ -522,8 +525,8 @@ async fn test_market_maker_fees() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: Some(owner_token_1),
         },
     )
@@ -682,8 +685,8 @@ async fn test_no_maker_fees_ask() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -705,8 +708,8 @@ async fn test_no_maker_fees_ask() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: Some(owner_token_1),
         },
     )
@@ -727,6 +730,7 @@ async fn test_no_maker_fees_ask() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -875,8 +879,8 @@ async fn test_maker_fees_ask() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -898,8 +902,8 @@ async fn test_maker_fees_ask() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: Some(owner_token_1),
         },
     )
@@ -920,6 +924,7 @@ async fn test_maker_fees_ask() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -1049,8 +1054,8 @@ async fn test_fees_half() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -1065,8 +1070,8 @@ async fn test_fees_half() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -1085,6 +1090,7 @@ async fn test_fees_half() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -1211,8 +1217,8 @@ async fn test_fees_half() -> Result<(), TransportError> {
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -1227,8 +1233,8 @@ async fn test_fees_half() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -1247,6 +1253,7 @@ async fn test_fees_half() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
         },
     )
     .await
@@ -1324,8 +1331,8 @@ async fn test_locked_maker_fees() -> Result<(), TransportError> {
         open_orders_account: maker,
         market_base_vault,
         market_quote_vault,
-        user_base_account: owner_base_ata,
-        user_quote_account: owner_quote_ata,
+        user_base_account: Some(owner_base_ata),
+        user_quote_account: Some(owner_quote_ata),
         referrer_account: None,
     };
 
@@ -1394,3 +1401,437 @@ async fn test_locked_maker_fees() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fees_in_base_ask_taker() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: -100,
+        taker_fee: 200,
+        fees_in_base: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 100,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Bids don't lock quote for fees
+    assert_eq!(
+        solana.token_account_balance(market_quote_vault).await,
+        10_000_000
+    );
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+
+    // The taker fee is paid on top of the base sold, so 101 lots only sell 100
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 101,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.base_free_native, 0);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 10_000_000);
+        assert_eq!(
+            solana.token_account_balance(owner_token_0).await,
+            balance_base - 10_002
+        );
+
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_fees_available, 1);
+        assert_eq!(market.fees_available, 0);
+        assert_eq!(market.fees_accrued, 0);
+        assert_eq!(market.referrer_rebates_accrued, 0);
+    }
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 10_001);
+        assert_eq!(open_orders_account_1.position.quote_free_native, 0);
+        assert_eq!(open_orders_account_1.position.locked_maker_fees, 0);
+    }
+
+    let admin_token_0 = solana
+        .create_associated_token_account(&collect_fee_admin.pubkey(), mints[0].pubkey)
+        .await;
+    let admin_token_1 = solana
+        .create_associated_token_account(&collect_fee_admin.pubkey(), mints[1].pubkey)
+        .await;
+
+    send_tx(
+        solana,
+        SweepFeesInstruction {
+            collect_fee_admin,
+            market,
+            market_quote_vault,
+            token_receiver_account: admin_token_1,
+            base_token_receiver_account: Some(admin_token_0),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(solana.token_account_balance(admin_token_0).await, 1);
+    assert_eq!(solana.token_account_balance(admin_token_1).await, 0);
+
+    for account in [account_1, account_2] {
+        send_tx(
+            solana,
+            SettleFundsInstruction {
+                owner,
+                market,
+                open_orders_account: account,
+                market_base_vault,
+                market_quote_vault,
+                user_base_account: Some(owner_token_0),
+                user_quote_account: Some(owner_token_1),
+                referrer_account: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // Everything paid in is accounted for
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_fees_available, 0);
+        assert_eq!(market.base_deposit_total, 0);
+        assert_eq!(market.quote_deposit_total, 0);
+        assert_eq!(solana.token_account_balance(market_base_vault).await, 0);
+        assert_eq!(solana.token_account_balance(market_quote_vault).await, 0);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fees_in_base_reduce_only_ask() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: -100,
+        taker_fee: 200,
+        fees_in_base: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 100,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_2,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
+            base_amount: 10_100,
+            quote_amount: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let wallet_base = solana.token_account_balance(owner_token_0).await;
+
+    // The taker fee in base comes out of the free base as well, the wallet is not touched
+    send_tx(
+        solana,
+        CancelAllAndPlaceOrdersInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            orders_type: PlaceOrderType::ImmediateOrCancel,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            bids: vec![],
+            asks: vec![openbook_v2::PlaceMultipleOrdersArgs {
+                price_lots,
+                max_quote_lots_including_fees: 1_000_000,
+                expiry_timestamp: 0,
+                reduce_only: true,
+            }],
+        },
+    )
+    .await
+    .unwrap();
+
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_2)
+        .await
+        .position;
+    assert_eq!(position.base_free_native, 10_100 - 10_002);
+    assert_eq!(position.quote_free_native, 10_000_000);
+    assert_eq!(
+        solana.token_account_balance(owner_token_0).await,
+        wallet_base
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fees_in_base_bid_taker() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: -100,
+        taker_fee: 200,
+        fees_in_base: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 100,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    // The whole quote goes to the maker, the fee comes out of the base received
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 100,
+            max_quote_lots_including_fees: 1_000_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.base_free_native, 9_998);
+        assert_eq!(open_orders_account_1.position.quote_free_native, 0);
+        assert_eq!(
+            solana.token_account_balance(owner_token_1).await,
+            balance_quote - 10_000_000
+        );
+
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_fees_available, 1);
+    }
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+        assert_eq!(open_orders_account_2.position.base_free_native, 1);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 10_000_000);
+    }
+
+    // The base vault holds the taker's base, the maker rebate and the market fees
+    assert_eq!(
+        solana.token_account_balance(market_base_vault).await,
+        9_998 + 1 + 1
+    );
+
+    let admin_token_0 = solana
+        .create_associated_token_account(&collect_fee_admin.pubkey(), mints[0].pubkey)
+        .await;
+    let admin_token_1 = solana
+        .create_associated_token_account(&collect_fee_admin.pubkey(), mints[1].pubkey)
+        .await;
+
+    // Without the base accounts only the quote fees are swept
+    send_tx(
+        solana,
+        SweepFeesInstruction {
+            collect_fee_admin,
+            market,
+            market_quote_vault,
+            token_receiver_account: admin_token_1,
+            base_token_receiver_account: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_fees_available, 1);
+        assert_eq!(market.base_deposit_total, 10_000);
+    }
+
+    send_tx(
+        solana,
+        SweepFeesInstruction {
+            collect_fee_admin,
+            market,
+            market_quote_vault,
+            token_receiver_account: admin_token_1,
+            base_token_receiver_account: Some(admin_token_0),
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_fees_available, 0);
+        assert_eq!(market.base_deposit_total, 9_999);
+        assert_eq!(solana.token_account_balance(admin_token_0).await, 1);
+    }
+
+    Ok(())
+}
@@ -181,8 +181,8 @@ async fn consume_and_settle(
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -197,8 +197,8 @@ async fn consume_and_settle(
             open_orders_account: account_2,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
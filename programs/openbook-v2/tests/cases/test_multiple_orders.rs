@@ -55,8 +55,8 @@ async fn insufficient_funds() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             base_amount: 1_200,
             quote_amount: 0,
         },
@@ -70,6 +70,7 @@ async fn insufficient_funds() -> Result<(), TransportError> {
         price_lots: 1,
         max_quote_lots_including_fees,
         expiry_timestamp: 0,
+        reduce_only: false,
     };
 
     let bids = vec![order];
@@ -109,3 +110,89 @@ async fn insufficient_funds() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_reduce_only() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        account_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_1,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
+            base_amount: 1_200,
+            quote_amount: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let wallet_base = solana.token_account_balance(owner_token_0).await;
+    let wallet_quote = solana.token_account_balance(owner_token_1).await;
+
+    // the first ask uses up the free base, nothing is left for the second one and
+    // the bid has no free quote at all
+    let order = openbook_v2::PlaceMultipleOrdersArgs {
+        price_lots: 1_000,
+        max_quote_lots_including_fees: 100_000,
+        expiry_timestamp: 0,
+        reduce_only: true,
+    };
+
+    send_tx(
+        solana,
+        CancelAllAndPlaceOrdersInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            orders_type: PlaceOrderType::Limit,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            bids: vec![openbook_v2::PlaceMultipleOrdersArgs {
+                price_lots: 500,
+                ..order
+            }],
+            asks: vec![order; 2],
+        },
+    )
+    .await
+    .unwrap();
+
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .position;
+
+    assert_eq!(position.asks_base_lots, 12);
+    assert_eq!(position.bids_base_lots, 0);
+    assert_eq!(position.base_free_native, 0);
+
+    assert_eq!(
+        solana.token_account_balance(owner_token_0).await,
+        wallet_base
+    );
+    assert_eq!(
+        solana.token_account_balance(owner_token_1).await,
+        wallet_quote
+    );
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use super::*;
+
+#[tokio::test]
+async fn test_native_sol_wrap_and_unwrap() -> Result<(), TransportError> {
+    let mut test_builder = TestContextBuilder::new();
+    test_builder.test().add_packable_account(
+        spl_token::native_mint::id(),
+        u32::MAX as u64,
+        &spl_token::state::Mint {
+            is_initialized: true,
+            decimals: 9,
+            ..spl_token::state::Mint::default()
+        },
+        &spl_token::id(),
+    );
+    let context = test_builder.start_default().await;
+    let solana = &context.solana.clone();
+
+    let maker = context.users[0].key;
+    let maker_token_0 = context.users[0].token_accounts[0];
+    let taker = context.users[1].key;
+    let taker_token_0 = context.users[1].token_accounts[0];
+
+    let openbook_v2::accounts::CreateMarket {
+        market,
+        market_base_vault,
+        market_quote_vault,
+        ..
+    } = send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer: taker,
+            market: TestKeypair::new(),
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            base_mint: context.mints[0].pubkey,
+            quote_mint: spl_token::native_mint::id(),
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let _indexer = create_open_orders_indexer(solana, &context.users[1], maker, market).await;
+    let account_1 =
+        create_open_orders_account(solana, maker, market, 1, &context.users[1], None).await;
+
+    // Deposit lamports directly as quote
+    let maker_lamports = solana.get_account_lamports(maker.pubkey()).await.unwrap();
+    send_tx(
+        solana,
+        DepositInstruction {
+            open_orders_account: account_1,
+            market,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(maker_token_0),
+            user_quote_account: None,
+            owner: maker,
+            base_amount: 0,
+            quote_amount: 1_000_000,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(maker.pubkey()).await.unwrap(),
+        maker_lamports - 1_000_000
+    );
+    assert_eq!(
+        solana.token_account_balance(market_quote_vault).await,
+        1_000_000
+    );
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: maker,
+            user_token_account: maker_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: 1000,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 10_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let take_order_ix = PlaceTakeOrderInstruction {
+        open_orders_admin: None,
+        market,
+        signer: taker,
+        market_base_vault,
+        market_quote_vault,
+        user_base_account: Some(taker_token_0),
+        user_quote_account: None,
+        side: Side::Bid,
+        price_lots: 1000,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 10_000,
+        order_type: PlaceOrderType::ImmediateOrCancel,
+    };
+
+    // The base mint is not native, so its token account can't be left out
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            PlaceTakeOrderInstruction {
+                user_base_account: None,
+                ..take_order_ix.clone()
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputNativeTransfer.into())
+    );
+
+    // Pay for the base with lamports
+    let taker_lamports = solana.get_account_lamports(taker.pubkey()).await.unwrap();
+    let taker_base = solana.token_account_balance(taker_token_0).await;
+    send_tx(solana, take_order_ix).await.unwrap();
+    assert_eq!(
+        solana.get_account_lamports(taker.pubkey()).await.unwrap(),
+        taker_lamports - 20_000 - PENALTY_EVENT_HEAP
+    );
+    assert_eq!(
+        solana.token_account_balance(taker_token_0).await,
+        taker_base + 200
+    );
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Settle the deposit and the proceeds straight back as lamports
+    let maker_lamports = solana.get_account_lamports(maker.pubkey()).await.unwrap();
+    send_tx(
+        solana,
+        SettleFundsInstruction {
+            owner: maker,
+            open_orders_account: account_1,
+            market,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(maker_token_0),
+            user_quote_account: None,
+            referrer_account: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(maker.pubkey()).await.unwrap(),
+        maker_lamports + 1_020_000
+    );
+    assert_eq!(solana.token_account_balance(market_quote_vault).await, 0);
+    assert!(solana
+        .get_account_data(get_unwrap_address(market))
+        .await
+        .is_none());
+
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .position;
+    assert_eq!(position.quote_free_native, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_native_sol_place_order() -> Result<(), TransportError> {
+    let mut test_builder = TestContextBuilder::new();
+    test_builder.test().add_packable_account(
+        spl_token::native_mint::id(),
+        u32::MAX as u64,
+        &spl_token::state::Mint {
+            is_initialized: true,
+            decimals: 9,
+            ..spl_token::state::Mint::default()
+        },
+        &spl_token::id(),
+    );
+    let context = test_builder.start_default().await;
+    let solana = &context.solana.clone();
+
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+
+    let openbook_v2::accounts::CreateMarket {
+        market,
+        market_base_vault,
+        market_quote_vault,
+        ..
+    } = send_tx(
+        solana,
+        CreateMarketInstruction {
+            payer,
+            market: TestKeypair::new(),
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            base_mint: context.mints[0].pubkey,
+            quote_mint: spl_token::native_mint::id(),
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let _indexer = create_open_orders_indexer(solana, &context.users[1], owner, market).await;
+    let account_1 =
+        create_open_orders_account(solana, owner, market, 1, &context.users[1], None).await;
+
+    let place_order = |side: Side, market_vault: Pubkey| PlaceOrderNativeInstruction {
+        order: PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            // Left out of the instruction
+            user_token_account: Pubkey::default(),
+            market_vault,
+            side,
+            price_lots: 1000,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 10_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    };
+
+    // The base mint is not native, an ask needs its token account
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_order(Side::Ask, market_base_vault)).await,
+        Some(OpenBookError::InvalidInputNativeTransfer.into())
+    );
+
+    // The bid locks its quote out of the owner's lamports
+    let owner_lamports = solana.get_account_lamports(owner.pubkey()).await.unwrap();
+    send_tx(solana, place_order(Side::Bid, market_quote_vault))
+        .await
+        .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(owner.pubkey()).await.unwrap(),
+        owner_lamports - 20_000
+    );
+    assert_eq!(
+        solana.token_account_balance(market_quote_vault).await,
+        20_000
+    );
+
+    let position = solana
+        .get_account::<OpenOrdersAccount>(account_1)
+        .await
+        .position;
+    assert_eq!(position.bids_base_lots, 2);
+    assert_eq!(position.bids_quote_lots, 2_000);
+    assert_eq!(position.quote_free_native, 0);
+
+    Ok(())
+}
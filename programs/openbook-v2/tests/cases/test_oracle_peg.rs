@@ -27,6 +27,7 @@ async fn test_oracle_peg_enabled() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -1,
+            price_offset_bps: None,
             peg_limit: 100,
             max_base_lots: 1,
             max_quote_lots_including_fees: 100_000,
@@ -64,6 +65,7 @@ async fn test_oracle_peg_invalid_oracle() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -1,
+            price_offset_bps: None,
             peg_limit: 100,
             max_base_lots: 1,
             max_quote_lots_including_fees: 100_000,
@@ -76,6 +78,85 @@ async fn test_oracle_peg_invalid_oracle() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_oracle_peg_bps() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        account_1,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        quote_lot_size: 10,
+        base_lot_size: 10000,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    let oracle_price_lots = {
+        let market = solana.get_account::<Market>(market).await;
+        market.native_price_to_lot(I80F48::ONE).unwrap()
+    };
+    assert_eq!(oracle_price_lots, 1000);
+
+    let place_pegged_ix = PlaceOrderPeggedInstruction {
+        open_orders_account: account_1,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_offset: 0,
+        price_offset_bps: Some(-100),
+        peg_limit: 10_000,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+    };
+
+    // offsets can't be given in lots and bps at the same time
+    assert!(send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            price_offset: -1,
+            ..place_pegged_ix.clone()
+        },
+    )
+    .await
+    .is_err());
+
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            price_offset: -5,
+            price_offset_bps: None,
+            client_order_id: 1,
+            ..place_pegged_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    // both orders live in the oracle pegged tree and are ranked by their current price
+    let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+    assert_eq!(bids_data.roots[1].leaf_count, 2);
+    let prices = |oracle_price_lots: i64| -> Vec<(i64, u64)> {
+        bids_data
+            .iter_valid(0, Some(oracle_price_lots))
+            .map(|it| (it.price_lots, it.node.client_order_id))
+            .collect()
+    };
+    assert_eq!(prices(1000), vec![(995, 1), (990, 0)]);
+    assert_eq!(prices(100), vec![(99, 0), (95, 1)]);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_oracle_peg() -> Result<(), TransportError> {
     let market_base_lot_size = 10000;
@@ -119,6 +200,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
         market_vault: market_quote_vault,
         side: Side::Bid,
         price_offset: -1,
+        price_offset_bps: None,
         peg_limit: 1,
         max_base_lots: 1,
         max_quote_lots_including_fees: 100_000,
@@ -179,6 +261,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: 0,
+            price_offset_bps: None,
             peg_limit: price_lots,
             max_base_lots: 2,
             max_quote_lots_including_fees,
@@ -232,6 +315,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
             market_vault: market_base_vault,
             side: Side::Ask,
             price_offset: 0,
+            price_offset_bps: None,
             peg_limit: price_lots,
             max_base_lots: 1,
             max_quote_lots_including_fees: 100_000,
@@ -341,6 +425,7 @@ async fn test_oracle_peg() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -1,
+            price_offset_bps: None,
             peg_limit: price_lots + 2,
             max_base_lots: 2,
             max_quote_lots_including_fees: 100_000,
@@ -461,6 +546,7 @@ async fn test_take_peg_invalid_oracle() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -1,
+            price_offset_bps: None,
             peg_limit: 100,
             max_base_lots: 1,
             max_quote_lots_including_fees: 100_000,
@@ -516,6 +602,106 @@ async fn test_take_peg_invalid_oracle() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_oracle_peg_twap_deviation() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        account_1,
+        tokens,
+        collect_fee_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        max_twap_deviation: Some(0.1),
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    let place_pegged_ix = PlaceOrderPeggedInstruction {
+        open_orders_account: account_1,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_offset: -1,
+        price_offset_bps: None,
+        peg_limit: 10_000,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+    };
+
+    // the first accepted price starts the twap
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 1.05).await;
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+
+    // a single print far from the twap invalidates the oracle
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 2.0).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_pegged_ix.clone()).await,
+        Some(OpenBookError::OraclePegInvalidOracleState.into())
+    );
+
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 1.0).await;
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+
+    {
+        let oo = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(oo.position.bids_base_lots, 3);
+    }
+    let twap_price = solana.get_account::<Market>(market).await.oracle_twap_price;
+
+    // after a quiet period an outlier seen by a successful fixed order can't
+    // replace the twap
+    solana
+        .advance_clock(openbook_v2::state::ORACLE_TWAP_SECONDS as i64)
+        .await;
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 2.0).await;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots: 1,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 1,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_pegged_ix.clone()).await,
+        Some(OpenBookError::OraclePegInvalidOracleState.into())
+    );
+
+    {
+        let market = solana.get_account::<Market>(market).await;
+        assert!((market.oracle_twap_price / twap_price - 1.1).abs() < 1e-9);
+    }
+
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 1.0).await;
+    send_tx(solana, place_pegged_ix).await.unwrap();
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_oracle_peg_limit() -> Result<(), TransportError> {
     let market_base_lot_size = 10000;
@@ -560,6 +746,7 @@ async fn test_oracle_peg_limit() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -100,
+            price_offset_bps: None,
             peg_limit: price_lots + 100_000,
             max_base_lots: 2,
             max_quote_lots_including_fees,
@@ -583,6 +770,7 @@ async fn test_oracle_peg_limit() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: -100,
+            price_offset_bps: None,
             peg_limit: price_lots + 100_000,
             max_base_lots: 2,
             max_quote_lots_including_fees,
@@ -645,6 +833,7 @@ async fn test_locked_amounts() -> Result<(), TransportError> {
         market_vault: market_quote_vault,
         side: Side::Bid,
         price_offset: 0,
+        price_offset_bps: None,
         peg_limit: 30,
         max_base_lots: 1_000,
         max_quote_lots_including_fees: 100_000_000,
@@ -666,8 +855,8 @@ async fn test_locked_amounts() -> Result<(), TransportError> {
         open_orders_account: account_1,
         market_base_vault,
         market_quote_vault,
-        user_base_account: owner_base_ata,
-        user_quote_account: owner_quote_ata,
+        user_base_account: Some(owner_base_ata),
+        user_quote_account: Some(owner_quote_ata),
         referrer_account: None,
     };
 
@@ -810,6 +999,7 @@ async fn test_bids_quote_lots() -> Result<(), TransportError> {
             market_vault: market_quote_vault,
             side: Side::Bid,
             price_offset: 0,
+            price_offset_bps: None,
             peg_limit: 20,
             max_base_lots: 100,
             max_quote_lots_including_fees: 100_000_000,
@@ -830,6 +1020,7 @@ async fn test_bids_quote_lots() -> Result<(), TransportError> {
             market_vault: market_base_vault,
             side: Side::Ask,
             price_offset: 0,
+            price_offset_bps: None,
             peg_limit: 20,
             max_base_lots: 30,
             max_quote_lots_including_fees: 100_000_000,
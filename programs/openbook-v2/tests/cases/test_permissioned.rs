@@ -464,8 +464,8 @@ async fn test_close_market_admin() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -490,6 +490,7 @@ async fn test_close_market_admin() -> Result<(), TransportError> {
             market,
             market_quote_vault,
             token_receiver_account: fee_admin_ata,
+            base_token_receiver_account: None,
         },
     )
     .await
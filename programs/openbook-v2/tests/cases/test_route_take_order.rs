@@ -0,0 +1,139 @@
+use super::*;
+
+#[tokio::test]
+async fn test_route_take_order() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        payer,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        maker_fee: 0,
+        taker_fee: 0,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let owner_token_2 = context.users[0].token_accounts[2];
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    // A second market trading token 2 against the same quote token
+    let openbook_v2::accounts::CreateMarket {
+        market: second_market,
+        market_base_vault: second_market_base_vault,
+        ..
+    } = send_tx(
+        solana,
+        CreateMarketInstruction {
+            collect_fee_admin: collect_fee_admin.pubkey(),
+            payer,
+            market: TestKeypair::new(),
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            base_mint: mints[2].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+    let account_3 =
+        create_open_orders_account(solana, owner, second_market, 3, &context.users[1], None).await;
+
+    let place_order = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: price_lots,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_order.clone()).await.unwrap();
+
+    // Token 2 is 10% cheaper than token 0
+    let second_price_lots = price_lots * 9 / 10;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_3,
+            market: second_market,
+            user_token_account: owner_token_2,
+            market_vault: second_market_base_vault,
+            side: Side::Ask,
+            price_lots: second_price_lots,
+            max_quote_lots_including_fees: second_price_lots,
+            ..place_order
+        },
+    )
+    .await
+    .unwrap();
+
+    let route = RouteTakeOrderInstruction {
+        signer: owner,
+        first_market: market,
+        second_market,
+        user_input_account: owner_token_0,
+        user_intermediate_account: owner_token_1,
+        user_output_account: owner_token_2,
+        amount_in: 100,
+        min_amount_out: 101,
+    };
+
+    let result = send_tx(solana, route.clone()).await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::RouteBelowMinimumOutput.error_code(),
+        "Should not reach the minimum output".into(),
+    );
+
+    let balance_0 = solana.token_account_balance(owner_token_0).await;
+    let balance_1 = solana.token_account_balance(owner_token_1).await;
+    let balance_2 = solana.token_account_balance(owner_token_2).await;
+
+    send_tx(
+        solana,
+        RouteTakeOrderInstruction {
+            min_amount_out: 100,
+            ..route
+        },
+    )
+    .await
+    .unwrap();
+
+    // 1 base lot of token 0 sold for 1000 * 100 of token 1, which buys 1 base lot
+    // of token 2 at 900 * 100 and leaves the rest for the user
+    assert_eq!(
+        balance_0 - 100,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_1 + 10_000,
+        solana.token_account_balance(owner_token_1).await
+    );
+    assert_eq!(
+        balance_2 + 100,
+        solana.token_account_balance(owner_token_2).await
+    );
+
+    Ok(())
+}
@@ -114,8 +114,8 @@ async fn test_self_trade_decrement_take() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -504,8 +504,8 @@ async fn test_self_trade_no_fees() -> Result<(), TransportError> {
         open_orders_account,
         market_base_vault,
         market_quote_vault,
-        user_base_account: owner_base_ata,
-        user_quote_account: owner_quote_ata,
+        user_base_account: Some(owner_base_ata),
+        user_quote_account: Some(owner_quote_ata),
         referrer_account: None,
     };
 
@@ -55,14 +55,15 @@ async fn test_take_ask_order() -> Result<(), TransportError> {
         PlaceTakeOrderInstruction {
             market,
             signer: owner,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             market_base_vault,
             market_quote_vault,
             side: Side::Ask,
             price_lots,
             max_base_lots: 1,
             max_quote_lots_including_fees: 10000,
+            order_type: PlaceOrderType::ImmediateOrCancel,
             open_orders_admin: None,
         },
     )
@@ -124,8 +125,8 @@ async fn test_take_ask_order() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -199,14 +200,15 @@ async fn test_take_bid_order() -> Result<(), TransportError> {
         PlaceTakeOrderInstruction {
             market,
             signer: owner,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             market_base_vault,
             market_quote_vault,
             side: Side::Bid,
             price_lots,
             max_base_lots: 1,
             max_quote_lots_including_fees: 10040,
+            order_type: PlaceOrderType::ImmediateOrCancel,
             open_orders_admin: None,
         },
     )
@@ -268,8 +270,8 @@ async fn test_take_bid_order() -> Result<(), TransportError> {
             open_orders_account: account_1,
             market_base_vault,
             market_quote_vault,
-            user_base_account: owner_token_0,
-            user_quote_account: owner_token_1,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
             referrer_account: None,
         },
     )
@@ -367,3 +369,249 @@ async fn test_negative_spread_ask() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_take_exact_out_order() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_maker_order = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 20000,
+
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_maker_order.clone()).await.unwrap();
+
+    let take_exact_out = PlaceTakeOrderInstruction {
+        market,
+        signer: owner,
+        user_base_account: Some(owner_token_0),
+        user_quote_account: Some(owner_token_1),
+        market_base_vault,
+        market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 3,
+        max_quote_lots_including_fees: 40000,
+        order_type: PlaceOrderType::ExactOut,
+        open_orders_admin: None,
+    };
+
+    // Not enough liquidity on the book for 3 base lots
+    let result = send_tx(solana, take_exact_out.clone()).await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::WouldNotFillExactOut.error_code(),
+        "Should not fill exact out".into(),
+    );
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            max_base_lots: 2,
+            ..take_exact_out.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance_base + 200,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - 200040,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+            ..place_maker_order
+        },
+    )
+    .await
+    .unwrap();
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    // Receive 15000 quote lots after fees, which doesn't divide by the bid price
+    let take_exact_out = PlaceTakeOrderInstruction {
+        side: Side::Ask,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 15000,
+        ..take_exact_out
+    };
+
+    // Selling a single base lot falls short of it
+    let result = send_tx(solana, take_exact_out.clone()).await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::WouldNotFillExactOut.error_code(),
+        "Should not fill exact out".into(),
+    );
+
+    // The second base lot overshoots the output instead of failing
+    let take_exact_out = PlaceTakeOrderInstruction {
+        max_base_lots: 5,
+        ..take_exact_out
+    };
+    send_tx(solana, take_exact_out.clone()).await.unwrap();
+
+    assert_eq!(
+        balance_base - 200,
+        solana.token_account_balance(owner_token_0).await
+    );
+    // 200000 native quote minus 80 of taker fees
+    assert_eq!(
+        balance_quote + 199920,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    // The book is empty now
+    let result = send_tx(solana, take_exact_out).await;
+    assert_openbook_error(
+        &result,
+        OpenBookError::WouldNotFillExactOut.error_code(),
+        "Should not fill exact out".into(),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_take_order_market_stats() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        payer,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(solana, CreateMarketStatsInstruction { payer, market })
+        .await
+        .unwrap();
+    assert!(solana
+        .get_account::<Market>(market)
+        .await
+        .has_market_stats());
+    // There is only one per market
+    assert!(
+        send_tx(solana, CreateMarketStatsInstruction { payer, market })
+            .await
+            .is_err()
+    );
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            market,
+            signer: owner,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
+            market_base_vault,
+            market_quote_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    let market_stats = solana
+        .get_account::<MarketStats>(get_market_stats_address(market))
+        .await;
+    assert_eq!(market_stats.market, market);
+    assert_eq!(
+        market_stats.last_24h(now_ts),
+        TradeStats {
+            high_price_lots: price_lots,
+            low_price_lots: price_lots,
+            volume_base_lots: 2,
+        }
+    );
+    assert_eq!(
+        market_stats.last_24h(now_ts + 24 * 60 * 60),
+        TradeStats::default()
+    );
+
+    Ok(())
+}
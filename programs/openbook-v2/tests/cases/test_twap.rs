@@ -0,0 +1,295 @@
+use super::*;
+
+#[tokio::test]
+async fn test_twap_order() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        payer,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let keeper = payer;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 10,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Slices are paid with the free balance of the open orders account
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_2,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
+            base_amount: 1_000,
+            quote_amount: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateTwapOrderInstruction {
+                open_orders_account: account_2,
+                market,
+                signer: owner,
+                payer,
+                args: openbook_v2::CreateTwapOrderArgs {
+                    side: Side::Ask,
+                    limit_price_lots: price_lots,
+                    total_base_lots: 5,
+                    slice_base_lots: 0,
+                    interval_seconds: 60,
+                    start_ts: 0,
+                    keeper_fee_lamports: 1_000,
+                },
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputTwap.into())
+    );
+
+    send_tx(
+        solana,
+        CreateTwapOrderInstruction {
+            open_orders_account: account_2,
+            market,
+            signer: owner,
+            payer,
+            args: openbook_v2::CreateTwapOrderArgs {
+                side: Side::Ask,
+                limit_price_lots: price_lots,
+                total_base_lots: 5,
+                slice_base_lots: 2,
+                interval_seconds: 60,
+                start_ts: 0,
+                keeper_fee_lamports: 1_000,
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    let twap_order = twap_order_address(&account_2);
+    let execute_slice_ix = || ExecuteTwapSliceInstruction {
+        keeper,
+        open_orders_account: account_2,
+        market,
+        remainings: vec![],
+    };
+
+    let keeper_lamports = solana.get_account_lamports(keeper.pubkey()).await.unwrap();
+    send_tx(solana, execute_slice_ix()).await.unwrap();
+
+    {
+        let twap = solana.get_account::<TwapOrder>(twap_order).await;
+        assert_eq!(twap.filled_base_lots, 2);
+        assert_eq!(twap.remaining_base_lots, 3);
+        assert_eq!(twap.next_slice, 1);
+
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.base_free_native, 800);
+        assert_eq!(
+            solana.get_account_lamports(keeper.pubkey()).await.unwrap(),
+            keeper_lamports + 1_000
+        );
+    }
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, execute_slice_ix()).await,
+        Some(OpenBookError::TwapSliceNotDue.into())
+    );
+
+    // The second slice misses its window, the third one only has a lot left
+    solana.advance_clock(120).await;
+    send_tx(solana, execute_slice_ix()).await.unwrap();
+
+    {
+        let twap = solana.get_account::<TwapOrder>(twap_order).await;
+        assert_eq!(twap.filled_base_lots, 3);
+        assert_eq!(twap.remaining_base_lots, 0);
+        assert_eq!(twap.next_slice, 3);
+
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.base_free_native, 700);
+    }
+
+    solana.advance_clock(60).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, execute_slice_ix()).await,
+        Some(OpenBookError::TwapOrderCompleted.into())
+    );
+
+    // The fee of the skipped slice is returned with the rent
+    let twap_lamports = solana.get_account_lamports(twap_order).await.unwrap();
+    let owner_lamports = solana.get_account_lamports(owner.pubkey()).await.unwrap();
+    send_tx(
+        solana,
+        CancelTwapOrderInstruction {
+            open_orders_account: account_2,
+            signer: owner,
+            sol_destination: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        solana.get_account_lamports(owner.pubkey()).await.unwrap(),
+        owner_lamports + twap_lamports
+    );
+    assert_eq!(solana.get_account_lamports(twap_order).await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_twap_order_min_size() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        payer,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        min_base_lots: 2,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let keeper = payer;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 10,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_2,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: Some(owner_token_0),
+            user_quote_account: Some(owner_token_1),
+            base_amount: 1_000,
+            quote_amount: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let create_ix = |slice_base_lots| CreateTwapOrderInstruction {
+        open_orders_account: account_2,
+        market,
+        signer: owner,
+        payer,
+        args: openbook_v2::CreateTwapOrderArgs {
+            side: Side::Ask,
+            limit_price_lots: price_lots,
+            total_base_lots: 5,
+            slice_base_lots,
+            interval_seconds: 60,
+            start_ts: 0,
+            keeper_fee_lamports: 1_000,
+        },
+    };
+
+    // Slices below the market minimum could never be placed
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, create_ix(1)).await,
+        Some(OpenBookError::OrderBelowMinimumSize.into())
+    );
+    send_tx(solana, create_ix(2)).await.unwrap();
+
+    let twap_order = twap_order_address(&account_2);
+    let execute_slice_ix = || ExecuteTwapSliceInstruction {
+        keeper,
+        open_orders_account: account_2,
+        market,
+        remainings: vec![],
+    };
+
+    send_tx(solana, execute_slice_ix()).await.unwrap();
+    {
+        let twap = solana.get_account::<TwapOrder>(twap_order).await;
+        assert_eq!(twap.filled_base_lots, 2);
+        assert_eq!(twap.remaining_base_lots, 3);
+    }
+
+    // The last lot is below the minimum, it's executed with the second slice
+    solana.advance_clock(60).await;
+    send_tx(solana, execute_slice_ix()).await.unwrap();
+    {
+        let twap = solana.get_account::<TwapOrder>(twap_order).await;
+        assert_eq!(twap.filled_base_lots, 5);
+        assert_eq!(twap.remaining_base_lots, 0);
+        assert_eq!(twap.next_slice, 2);
+
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.base_free_native, 500);
+    }
+
+    Ok(())
+}
@@ -11,7 +11,8 @@ use std::sync::Arc;
 use super::solana::SolanaCookie;
 use super::utils::TestKeypair;
 use openbook_v2::{
-    state::*, PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs, PlaceTakeOrderArgs,
+    state::*, CreateTwapOrderArgs, PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs,
+    PlaceTakeOrderArgs, RouteTakeOrderArgs,
 };
 
 #[async_trait::async_trait(?Send)]
@@ -126,6 +127,40 @@ fn make_instruction(
     }
 }
 
+pub fn get_unwrap_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"Unwrap".as_ref(), market.as_ref()], &openbook_v2::id()).0
+}
+
+pub fn get_market_stats_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"MarketStats".as_ref(), market.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+/// The market stats account, needed once the market has one
+fn market_stats_account(market: Pubkey, market_data: &Market) -> Option<Pubkey> {
+    market_data
+        .has_market_stats()
+        .then(|| get_market_stats_address(market))
+}
+
+/// The native mint and unwrap accounts, needed when a user token account is left out
+fn native_unwrap_accounts(
+    market: Pubkey,
+    user_accounts: &[Option<Pubkey>],
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    if user_accounts.iter().any(Option::is_none) {
+        (
+            Some(spl_token::native_mint::id()),
+            Some(get_unwrap_address(market)),
+        )
+    } else {
+        (None, None)
+    }
+}
+
 pub fn get_market_address(market: TestKeypair) -> Pubkey {
     Pubkey::find_program_address(
         &[b"Market".as_ref(), market.pubkey().to_bytes().as_ref()],
@@ -318,6 +353,12 @@ pub struct CreateMarketInstruction {
     pub settle_fee_flat: f32,
     pub settle_fee_amount_threshold: f32,
     pub time_expiry: i64,
+    pub min_base_lots: i64,
+    pub min_quote_lots: i64,
+    pub tick_size_lots: i64,
+    pub auction_end_ts: i64,
+    pub max_twap_deviation: Option<f32>,
+    pub fees_in_base: bool,
 }
 impl CreateMarketInstruction {
     pub async fn with_new_book_and_heap(
@@ -356,12 +397,19 @@ impl ClientInstruction for CreateMarketInstruction {
             oracle_config: OracleConfigParams {
                 conf_filter: 0.1,
                 max_staleness_slots: Some(100),
+                max_staleness_seconds: None,
+                max_twap_deviation: self.max_twap_deviation,
             },
             quote_lot_size: self.quote_lot_size,
             base_lot_size: self.base_lot_size,
             maker_fee: self.maker_fee,
             taker_fee: self.taker_fee,
             time_expiry: self.time_expiry,
+            min_base_lots: self.min_base_lots,
+            min_quote_lots: self.min_quote_lots,
+            tick_size_lots: self.tick_size_lots,
+            auction_end_ts: self.auction_end_ts,
+            fees_in_base: self.fees_in_base,
         };
 
         let event_authority =
@@ -454,6 +502,7 @@ impl ClientInstruction for PlaceOrderInstruction {
                 expiry_timestamp: self.expiry_timestamp,
                 self_trade_behavior: self.self_trade_behavior,
                 limit: 10,
+                reduce_only: false,
             },
         };
 
@@ -469,9 +518,12 @@ impl ClientInstruction for PlaceOrderInstruction {
             oracle_a: market.oracle_a.into(),
             oracle_b: market.oracle_b.into(),
             signer: self.signer.pubkey(),
-            user_token_account: self.user_token_account,
+            user_token_account: Some(self.user_token_account),
             market_vault: self.market_vault,
             token_program: Token::id(),
+            native_payer: None,
+            system_program: None,
+            market_stats: market_stats_account(self.market, &market),
         };
         let mut instruction = make_instruction(program_id, &accounts, instruction);
         let mut vec_remainings: Vec<AccountMeta> = Vec::new();
@@ -496,6 +548,36 @@ impl ClientInstruction for PlaceOrderInstruction {
     }
 }
 
+/// Places `order` paying lamports instead of from its `user_token_account`, which
+/// is left out, on markets with a native mint vault
+pub struct PlaceOrderNativeInstruction {
+    pub order: PlaceOrderInstruction,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PlaceOrderNativeInstruction {
+    type Accounts = openbook_v2::accounts::PlaceOrder;
+    type Instruction = openbook_v2::instruction::PlaceOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let (mut accounts, mut instruction) = self.order.to_instruction(account_loader).await;
+        let accounts_len = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None).len();
+        let mut remainings = instruction.accounts.split_off(accounts_len);
+
+        accounts.user_token_account = None;
+        accounts.native_payer = Some(self.order.signer.pubkey());
+        accounts.system_program = Some(System::id());
+        instruction.accounts = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+        instruction.accounts.append(&mut remainings);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        self.order.signers()
+    }
+}
+
 #[derive(Clone)]
 pub struct PlaceOrderPeggedInstruction {
     pub open_orders_account: Pubkey,
@@ -505,6 +587,7 @@ pub struct PlaceOrderPeggedInstruction {
     pub market_vault: Pubkey,
     pub side: Side,
     pub price_offset: i64,
+    pub price_offset_bps: Option<i32>,
     pub max_base_lots: i64,
     pub max_quote_lots_including_fees: i64,
     pub client_order_id: u64,
@@ -523,6 +606,7 @@ impl ClientInstruction for PlaceOrderPeggedInstruction {
             args: PlaceOrderPeggedArgs {
                 side: self.side,
                 price_offset_lots: self.price_offset,
+                price_offset_bps: self.price_offset_bps,
                 peg_limit: self.peg_limit,
                 max_base_lots: self.max_base_lots,
                 max_quote_lots_including_fees: self.max_quote_lots_including_fees,
@@ -546,9 +630,12 @@ impl ClientInstruction for PlaceOrderPeggedInstruction {
             oracle_a: market.oracle_a.into(),
             oracle_b: market.oracle_b.into(),
             signer: self.signer.pubkey(),
-            user_token_account: self.user_token_account,
+            user_token_account: Some(self.user_token_account),
             market_vault: self.market_vault,
             token_program: Token::id(),
+            native_payer: None,
+            system_program: None,
+            market_stats: market_stats_account(self.market, &market),
         };
         let instruction = make_instruction(program_id, &accounts, instruction);
 
@@ -560,18 +647,20 @@ impl ClientInstruction for PlaceOrderPeggedInstruction {
     }
 }
 
+#[derive(Clone)]
 pub struct PlaceTakeOrderInstruction {
     pub open_orders_admin: Option<TestKeypair>,
     pub market: Pubkey,
     pub signer: TestKeypair,
     pub market_base_vault: Pubkey,
     pub market_quote_vault: Pubkey,
-    pub user_base_account: Pubkey,
-    pub user_quote_account: Pubkey,
+    pub user_base_account: Option<Pubkey>,
+    pub user_quote_account: Option<Pubkey>,
     pub side: Side,
     pub price_lots: i64,
     pub max_base_lots: i64,
     pub max_quote_lots_including_fees: i64,
+    pub order_type: PlaceOrderType,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for PlaceTakeOrderInstruction {
@@ -588,12 +677,16 @@ impl ClientInstruction for PlaceTakeOrderInstruction {
                 price_lots: self.price_lots,
                 max_base_lots: self.max_base_lots,
                 max_quote_lots_including_fees: self.max_quote_lots_including_fees,
-                order_type: PlaceOrderType::ImmediateOrCancel,
+                order_type: self.order_type,
                 limit: 10,
             },
         };
 
         let market: Market = account_loader.load(&self.market).await.unwrap();
+        let (native_mint, unwrap_account) = native_unwrap_accounts(
+            self.market,
+            &[self.user_base_account, self.user_quote_account],
+        );
 
         let accounts = Self::Accounts {
             open_orders_admin: self.open_orders_admin.map(|kp| kp.pubkey()),
@@ -612,6 +705,9 @@ impl ClientInstruction for PlaceTakeOrderInstruction {
             market_quote_vault: self.market_quote_vault,
             token_program: Token::id(),
             system_program: System::id(),
+            native_mint,
+            unwrap_account,
+            market_stats: market_stats_account(self.market, &market),
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -628,6 +724,76 @@ impl ClientInstruction for PlaceTakeOrderInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct RouteTakeOrderInstruction {
+    pub signer: TestKeypair,
+    pub first_market: Pubkey,
+    pub second_market: Pubkey,
+    pub user_input_account: Pubkey,
+    pub user_intermediate_account: Pubkey,
+    pub user_output_account: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for RouteTakeOrderInstruction {
+    type Accounts = openbook_v2::accounts::RouteTakeOrder;
+    type Instruction = openbook_v2::instruction::RouteTakeOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            args: RouteTakeOrderArgs {
+                amount_in: self.amount_in,
+                min_amount_out: self.min_amount_out,
+                limit: 10,
+            },
+        };
+
+        let first_market: Market = account_loader.load(&self.first_market).await.unwrap();
+        let second_market: Market = account_loader.load(&self.second_market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            penalty_payer: self.signer.pubkey(),
+            first_market: self.first_market,
+            first_market_authority: first_market.market_authority,
+            first_bids: first_market.bids,
+            first_asks: first_market.asks,
+            first_market_base_vault: first_market.market_base_vault,
+            first_market_quote_vault: first_market.market_quote_vault,
+            first_event_heap: first_market.event_heap,
+            first_oracle_a: first_market.oracle_a.into(),
+            first_oracle_b: first_market.oracle_b.into(),
+            second_market: self.second_market,
+            second_market_authority: second_market.market_authority,
+            second_bids: second_market.bids,
+            second_asks: second_market.asks,
+            second_market_base_vault: second_market.market_base_vault,
+            second_market_quote_vault: second_market.market_quote_vault,
+            second_event_heap: second_market.event_heap,
+            second_oracle_a: second_market.oracle_a.into(),
+            second_oracle_b: second_market.oracle_b.into(),
+            user_input_account: self.user_input_account,
+            user_intermediate_account: self.user_intermediate_account,
+            user_output_account: self.user_output_account,
+            token_program: Token::id(),
+            system_program: System::id(),
+            first_market_stats: market_stats_account(self.first_market, &first_market),
+            second_market_stats: market_stats_account(self.second_market, &second_market),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
 pub struct CancelOrderInstruction {
     pub open_orders_account: Pubkey,
     pub market: Pubkey,
@@ -832,8 +998,8 @@ pub struct SettleFundsInstruction {
     pub market: Pubkey,
     pub market_base_vault: Pubkey,
     pub market_quote_vault: Pubkey,
-    pub user_base_account: Pubkey,
-    pub user_quote_account: Pubkey,
+    pub user_base_account: Option<Pubkey>,
+    pub user_quote_account: Option<Pubkey>,
     pub referrer_account: Option<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
@@ -847,6 +1013,10 @@ impl ClientInstruction for SettleFundsInstruction {
         let program_id = openbook_v2::id();
         let instruction = Self::Instruction {};
         let market: Market = account_loader.load(&self.market).await.unwrap();
+        let (native_mint, unwrap_account) = native_unwrap_accounts(
+            self.market,
+            &[self.user_base_account, self.user_quote_account],
+        );
         let accounts = Self::Accounts {
             owner: self.owner.pubkey(),
             penalty_payer: self.owner.pubkey(),
@@ -860,6 +1030,8 @@ impl ClientInstruction for SettleFundsInstruction {
             referrer_account: self.referrer_account,
             token_program: Token::id(),
             system_program: System::id(),
+            native_mint,
+            unwrap_account,
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -924,6 +1096,7 @@ pub struct SweepFeesInstruction {
     pub market: Pubkey,
     pub market_quote_vault: Pubkey,
     pub token_receiver_account: Pubkey,
+    pub base_token_receiver_account: Option<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for SweepFeesInstruction {
@@ -944,6 +1117,10 @@ impl ClientInstruction for SweepFeesInstruction {
             market_quote_vault: self.market_quote_vault,
             token_receiver_account: self.token_receiver_account,
             token_program: Token::id(),
+            market_base_vault: self
+                .base_token_receiver_account
+                .map(|_| market.market_base_vault),
+            base_token_receiver_account: self.base_token_receiver_account,
         };
         let instruction = make_instruction(program_id, &accounts, instruction);
 
@@ -960,8 +1137,8 @@ pub struct DepositInstruction {
     pub market: Pubkey,
     pub market_base_vault: Pubkey,
     pub market_quote_vault: Pubkey,
-    pub user_base_account: Pubkey,
-    pub user_quote_account: Pubkey,
+    pub user_base_account: Option<Pubkey>,
+    pub user_quote_account: Option<Pubkey>,
     pub owner: TestKeypair,
     pub base_amount: u64,
     pub quote_amount: u64,
@@ -980,6 +1157,7 @@ impl ClientInstruction for DepositInstruction {
             quote_amount: self.quote_amount,
         };
 
+        let native = self.user_base_account.is_none() || self.user_quote_account.is_none();
         let accounts = Self::Accounts {
             owner: self.owner.pubkey(),
             open_orders_account: self.open_orders_account,
@@ -989,6 +1167,8 @@ impl ClientInstruction for DepositInstruction {
             user_base_account: self.user_base_account,
             user_quote_account: self.user_quote_account,
             token_program: Token::id(),
+            native_payer: native.then(|| self.owner.pubkey()),
+            system_program: native.then(System::id),
         };
         let instruction = make_instruction(program_id, &accounts, instruction);
 
@@ -1154,6 +1334,7 @@ impl ClientInstruction for CloseMarketInstruction {
             event_heap: market.event_heap,
             token_program: Token::id(),
             sol_destination: self.sol_destination,
+            market_stats: market_stats_account(self.market, &market),
         };
 
         let instruction = make_instruction(program_id, &accounts, instruction);
@@ -1165,6 +1346,37 @@ impl ClientInstruction for CloseMarketInstruction {
     }
 }
 
+pub struct CreateMarketStatsInstruction {
+    pub payer: TestKeypair,
+    pub market: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CreateMarketStatsInstruction {
+    type Accounts = openbook_v2::accounts::CreateMarketStats;
+    type Instruction = openbook_v2::instruction::CreateMarketStats;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            market: self.market,
+            market_stats: get_market_stats_address(self.market),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer]
+    }
+}
+
 pub struct SetMarketExpiredInstruction {
     pub close_market_admin: TestKeypair,
     pub market: Pubkey,
@@ -1194,6 +1406,74 @@ impl ClientInstruction for SetMarketExpiredInstruction {
     }
 }
 
+pub struct StartAuctionInstruction {
+    pub close_market_admin: TestKeypair,
+    pub market: Pubkey,
+    pub auction_end_ts: i64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for StartAuctionInstruction {
+    type Accounts = openbook_v2::accounts::StartAuction;
+    type Instruction = openbook_v2::instruction::StartAuction;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            auction_end_ts: self.auction_end_ts,
+        };
+
+        let accounts = Self::Accounts {
+            close_market_admin: self.close_market_admin.pubkey(),
+            market: self.market,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.close_market_admin]
+    }
+}
+
+#[derive(Clone)]
+pub struct UncrossAuctionInstruction {
+    pub market: Pubkey,
+    pub limit: u8,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for UncrossAuctionInstruction {
+    type Accounts = openbook_v2::accounts::UncrossAuction;
+    type Instruction = openbook_v2::instruction::UncrossAuction;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: self.limit };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+            market_stats: market_stats_account(self.market, &market),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
 pub struct PruneOrdersInstruction {
     pub close_market_admin: TestKeypair,
     pub market: Pubkey,
@@ -1301,6 +1581,7 @@ impl ClientInstruction for EditOrderInstruction {
                 expiry_timestamp: self.expiry_timestamp,
                 self_trade_behavior: self.self_trade_behavior,
                 limit: 10,
+                reduce_only: false,
             },
         };
 
@@ -1316,9 +1597,12 @@ impl ClientInstruction for EditOrderInstruction {
             oracle_a: market.oracle_a.into(),
             oracle_b: market.oracle_b.into(),
             signer: self.signer.pubkey(),
-            user_token_account: self.user_token_account,
+            user_token_account: Some(self.user_token_account),
             market_vault: self.market_vault,
             token_program: Token::id(),
+            native_payer: None,
+            system_program: None,
+            market_stats: market_stats_account(self.market, &market),
         };
         let mut instruction = make_instruction(program_id, &accounts, instruction);
         let mut vec_remainings: Vec<AccountMeta> = Vec::new();
@@ -1389,6 +1673,7 @@ impl ClientInstruction for CancelAllAndPlaceOrdersInstruction {
             market_base_vault: market.market_base_vault,
             market_quote_vault: market.market_quote_vault,
             token_program: Token::id(),
+            market_stats: market_stats_account(self.market, &market),
         };
         let instruction = make_instruction(program_id, &accounts, instruction);
         (accounts, instruction)
@@ -1403,3 +1688,130 @@ impl ClientInstruction for CancelAllAndPlaceOrdersInstruction {
         signers
     }
 }
+
+pub fn twap_order_address(open_orders_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"TwapOrder".as_ref(), open_orders_account.as_ref()],
+        &openbook_v2::id(),
+    )
+    .0
+}
+
+pub struct CreateTwapOrderInstruction {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub signer: TestKeypair,
+    pub payer: TestKeypair,
+    pub args: CreateTwapOrderArgs,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CreateTwapOrderInstruction {
+    type Accounts = openbook_v2::accounts::CreateTwapOrder;
+    type Instruction = openbook_v2::instruction::CreateTwapOrder;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { args: self.args };
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            signer: self.signer.pubkey(),
+            open_orders_account: self.open_orders_account,
+            twap_order: twap_order_address(&self.open_orders_account),
+            market: self.market,
+            system_program: System::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.signer]
+    }
+}
+
+pub struct ExecuteTwapSliceInstruction {
+    pub keeper: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub remainings: Vec<Pubkey>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ExecuteTwapSliceInstruction {
+    type Accounts = openbook_v2::accounts::ExecuteTwapSlice;
+    type Instruction = openbook_v2::instruction::ExecuteTwapSlice;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 10 };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            keeper: self.keeper.pubkey(),
+            twap_order: twap_order_address(&self.open_orders_account),
+            open_orders_account: self.open_orders_account,
+            open_orders_admin: None,
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+            market_stats: market_stats_account(self.market, &market),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        let mut vec_remainings: Vec<AccountMeta> = Vec::new();
+        for remaining in &self.remainings {
+            vec_remainings.push(AccountMeta {
+                pubkey: *remaining,
+                is_signer: false,
+                is_writable: true,
+            })
+        }
+        instruction.accounts.append(&mut vec_remainings);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.keeper]
+    }
+}
+
+pub struct CancelTwapOrderInstruction {
+    pub open_orders_account: Pubkey,
+    pub signer: TestKeypair,
+    pub sol_destination: Pubkey,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CancelTwapOrderInstruction {
+    type Accounts = openbook_v2::accounts::CancelTwapOrder;
+    type Instruction = openbook_v2::instruction::CancelTwapOrder;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            open_orders_account: self.open_orders_account,
+            twap_order: twap_order_address(&self.open_orders_account),
+            sol_destination: self.sol_destination,
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
@@ -290,6 +290,9 @@ pub struct TestNewMarketInitialize {
     pub time_expiry: i64,
     pub with_oracle: bool,
     pub payer_as_delegate: bool,
+    pub max_twap_deviation: Option<f32>,
+    pub fees_in_base: bool,
+    pub min_base_lots: i64,
 }
 
 impl Default for TestNewMarketInitialize {
@@ -306,6 +309,9 @@ impl Default for TestNewMarketInitialize {
             time_expiry: 0,
             with_oracle: true,
             payer_as_delegate: false,
+            max_twap_deviation: None,
+            fees_in_base: false,
+            min_base_lots: 0,
         }
     }
 }
@@ -382,6 +388,9 @@ impl TestContext {
                 quote_mint: mints[1].pubkey,
                 fee_penalty: args.fee_penalty,
                 time_expiry: args.time_expiry,
+                max_twap_deviation: args.max_twap_deviation,
+                fees_in_base: args.fees_in_base,
+                min_base_lots: args.min_base_lots,
                 ..CreateMarketInstruction::with_new_book_and_heap(solana, oracle, None).await
             },
         )
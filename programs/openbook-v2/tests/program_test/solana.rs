@@ -242,6 +242,16 @@ impl SolanaCookie {
         )
     }
 
+    pub async fn get_account_lamports(&self, address: Pubkey) -> Option<u64> {
+        self.context
+            .borrow_mut()
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .map(|account| account.lamports)
+    }
+
     pub async fn get_account_opt<T: AccountDeserialize>(&self, address: Pubkey) -> Option<T> {
         let data = self.get_account_data(address).await?;
         let mut data_slice: &[u8] = &data;
@@ -37,6 +37,7 @@ export interface OrderToPlace {
   expiryTimestamp?: number;
   selfTradeBehavior?: SelfTradeBehaviorType;
   matchLoopLimit?: number;
+  reduceOnly?: boolean;
 }
 
 export class OpenOrders {
@@ -437,6 +438,7 @@ export class OpenOrders {
     const selfTradeBehavior =
       order.selfTradeBehavior ?? SelfTradeBehaviorUtils.DecrementTake;
     const limit = order.matchLoopLimit ?? 16;
+    const reduceOnly = order.reduceOnly ?? false;
 
     const args = {
       side: order.side,
@@ -448,6 +450,7 @@ export class OpenOrders {
       expiryTimestamp,
       selfTradeBehavior,
       limit,
+      reduceOnly,
     };
 
     return await this.market.client.placeOrderIx(
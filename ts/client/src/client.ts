@@ -226,9 +226,16 @@ export class OpenBookV2Client {
     oracleConfigParams: OracleConfigParams = {
       confFilter: 0.1,
       maxStalenessSlots: 100,
+      maxStalenessSeconds: null,
+      maxTwapDeviation: null,
     },
     market = Keypair.generate(),
     collectFeeAdmin?: PublicKey,
+    minBaseLots = new BN(0),
+    minQuoteLots = new BN(0),
+    tickSizeLots = new BN(0),
+    auctionEndTs = new BN(0),
+    feesInBase = false,
   ): Promise<[TransactionInstruction[], Signer[]]> {
     const [bidIx, bidsKeypair] = await this.createProgramAccountIx(
       payer,
@@ -274,6 +281,11 @@ export class OpenBookV2Client {
         makerFee,
         takerFee,
         timeExpiry,
+        minBaseLots,
+        minQuoteLots,
+        tickSizeLots,
+        auctionEndTs,
+        feesInBase,
       )
       .accounts({
         market: market.publicKey,
@@ -6,6 +6,17 @@ export type OpenbookV2 = {
       name: 'createMarket';
       docs: [
         'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'A non-zero `auction_end_ts` opens the market with a call auction, see',
+        '`uncross_auction`.',
+        '',
+        'With `fees_in_base` taker fees and maker rebates are paid in base: asks pay',
+        'their taker fee on top of the base they sell and bids receive that much less',
+        "base. The maker fee can't be positive then.",
+        '',
+        'Book side oracles need their market, and Raydium CPMM vault oracles their',
+        'pool state, as the first remaining account. Pool oracles and OpenBook market',
+        'and book side oracles need a `max_twap_deviation`.',
       ];
       accounts: [
         {
@@ -154,6 +165,26 @@ export type OpenbookV2 = {
           name: 'timeExpiry';
           type: 'i64';
         },
+        {
+          name: 'minBaseLots';
+          type: 'i64';
+        },
+        {
+          name: 'minQuoteLots';
+          type: 'i64';
+        },
+        {
+          name: 'tickSizeLots';
+          type: 'i64';
+        },
+        {
+          name: 'auctionEndTs';
+          type: 'i64';
+        },
+        {
+          name: 'feesInBase';
+          type: 'bool';
+        },
       ];
     },
     {
@@ -161,6 +192,10 @@ export type OpenbookV2 = {
       docs: [
         'Close a [`Market`](crate::state::Market) (only',
         '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        '',
+        'The market must be expired and its book and event heap empty. The market,',
+        'bids, asks, event heap and market stats accounts are closed and their rent',
+        'goes to `sol_destination`.',
       ];
       accounts: [
         {
@@ -198,6 +233,46 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Closed with the market once it has market stats'];
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'createMarketStats';
+      docs: [
+        'Create the [`MarketStats`](crate::state::MarketStats) account of a market,',
+        'keeping the high, low and volume of its fills of the last 24 hours.',
+        '',
+        'Anyone can create it. From then on the instructions matching orders of the',
+        'market need it.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
       ];
       args: [];
     },
@@ -384,6 +459,7 @@ export type OpenbookV2 = {
           name: 'userTokenAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'market';
@@ -427,6 +503,28 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'nativePayer';
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ];
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -464,6 +562,7 @@ export type OpenbookV2 = {
           name: 'userTokenAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'market';
@@ -507,6 +606,28 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'nativePayer';
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ];
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -552,6 +673,7 @@ export type OpenbookV2 = {
           name: 'userTokenAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'market';
@@ -595,6 +717,28 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'nativePayer';
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ];
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -693,6 +837,13 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -805,6 +956,13 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -864,6 +1022,7 @@ export type OpenbookV2 = {
           name: 'userTokenAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'market';
@@ -907,6 +1066,28 @@ export type OpenbookV2 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'nativePayer';
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ];
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -927,6 +1108,15 @@ export type OpenbookV2 = {
         'add a new order off the book.',
         '',
         'This type of order allows for instant token settlement for the taker.',
+        '',
+        'With `PlaceOrderType::ExactOut` a bid receives `max_base_lots` paying at',
+        'most `max_quote_lots_including_fees`, and an ask receives at least',
+        '`max_quote_lots_including_fees` selling at most `max_base_lots`, both net',
+        "of taker fees. Asks may receive up to a base lot's worth more, as fills",
+        "are in whole base lots. It fails if the output can't be fully delivered.",
+        '',
+        'On a native mint side the user token account can be left out to pay',
+        'with and receive lamports of the signer directly.',
       ];
       accounts: [
         {
@@ -978,11 +1168,13 @@ export type OpenbookV2 = {
           name: 'userBaseAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'userQuoteAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: 'oracleA';
@@ -1012,6 +1204,25 @@ export type OpenbookV2 = {
           isSigner: true;
           isOptional: true;
         },
+        {
+          name: 'nativeMint';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'unwrapAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
       ];
       args: [
         {
@@ -1023,686 +1234,1184 @@ export type OpenbookV2 = {
       ];
     },
     {
-      name: 'consumeEvents';
+      name: 'routeTakeOrder';
       docs: [
-        'Process up to `limit` [events](crate::state::AnyEvent).',
-        '',
-        "When a user places a 'take' order, they do not know beforehand which",
-        "market maker will have placed the 'make' order that they get executed",
-        "against. This prevents them from passing in a market maker's",
-        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
-        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
-        "uses a 'crank' system, where `place_order` only emits events, and",
-        '`consume_events` handles token settlement.',
+        'Swap through two markets that share a mint, e.g. SOL -> USDC -> BONK,',
+        'taking liquidity off both books.',
         '',
-        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
-        'and [`OutEvent`](crate::state::OutEvent)s.',
+        'Up to `amount_in` of the input token is sold on the first market at any',
+        'price and its whole output is sold on the second market. The instruction',
+        'fails if less than `min_amount_out` of the output token is received.',
         '',
-        'A `FillEvent` is emitted when an order is filled, and it is handled by',
-        'debiting whatever the taker is selling from the taker and crediting',
-        'it to the maker, and debiting whatever the taker is buying from the',
-        'maker and crediting it to the taker. Note that *no tokens are moved*,',
-        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        'The intermediate token moves directly between the market vaults, only',
+        'the part of it the second market could not take (lot size rounding or',
+        'missing liquidity) is sent to `user_intermediate_account`.',
         '',
-        'An `OutEvent` is emitted when a limit order needs to be removed from',
-        'the book during a `place_order` invocation, and it is handled by',
-        'crediting whatever the maker would have sold (quote token in a bid,',
-        'base token in an ask) back to the maker.',
+        '`limit` caps the number of orders to fill on each of the markets.',
       ];
       accounts: [
         {
-          name: 'consumeEventsAdmin';
-          isMut: false;
+          name: 'signer';
+          isMut: true;
           isSigner: true;
-          isOptional: true;
         },
         {
-          name: 'market';
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'firstMarket';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'eventHeap';
+          name: 'firstMarketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'firstBids';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'limit';
-          type: 'u64';
+          name: 'firstAsks';
+          isMut: true;
+          isSigner: false;
         },
-      ];
-    },
-    {
-      name: 'consumeGivenEvents';
-      docs: [
-        'Process the [events](crate::state::AnyEvent) at the given positions.',
-      ];
-      accounts: [
         {
-          name: 'consumeEventsAdmin';
-          isMut: false;
-          isSigner: true;
-          isOptional: true;
+          name: 'firstMarketBaseVault';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'market';
+          name: 'firstMarketQuoteVault';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'eventHeap';
+          name: 'firstEventHeap';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'slots';
-          type: {
-            vec: 'u64';
-          };
+          name: 'firstOracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         },
-      ];
-    },
-    {
-      name: 'cancelOrder';
-      docs: [
-        'Cancel an order by its `order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ];
-      accounts: [
         {
-          name: 'signer';
+          name: 'firstOracleB';
           isMut: false;
-          isSigner: true;
+          isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'secondMarket';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'secondMarketAuthority';
           isMut: false;
           isSigner: false;
         },
         {
-          name: 'bids';
+          name: 'secondBids';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'asks';
+          name: 'secondAsks';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'orderId';
-          type: 'u128';
+          name: 'secondMarketBaseVault';
+          isMut: true;
+          isSigner: false;
         },
-      ];
-    },
-    {
-      name: 'cancelOrderByClientOrderId';
-      docs: [
-        'Cancel an order by its `client_order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ];
-      accounts: [
         {
-          name: 'signer';
-          isMut: false;
-          isSigner: true;
+          name: 'secondMarketQuoteVault';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'secondEventHeap';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'secondOracleA';
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'bids';
-          isMut: true;
+          name: 'secondOracleB';
+          isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'asks';
+          name: 'userInputAccount';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'clientOrderId';
-          type: 'u64';
+          name: 'userIntermediateAccount';
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "Receives the part of the first leg's output that the second leg could not use",
+          ];
         },
-      ];
-      returns: 'i64';
-    },
-    {
-      name: 'cancelAllOrders';
-      docs: ['Cancel up to `limit` orders, optionally filtering by side'];
-      accounts: [
         {
-          name: 'signer';
-          isMut: false;
-          isSigner: true;
+          name: 'userOutputAccount';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
-          isMut: true;
+          name: 'tokenProgram';
+          isMut: false;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'systemProgram';
           isMut: false;
           isSigner: false;
         },
         {
-          name: 'bids';
+          name: 'firstMarketStats';
           isMut: true;
           isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the first market has market stats'];
         },
         {
-          name: 'asks';
+          name: 'secondMarketStats';
           isMut: true;
           isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the second market has market stats'];
         },
       ];
       args: [
         {
-          name: 'sideOption';
+          name: 'args';
           type: {
-            option: {
-              defined: 'Side';
-            };
+            defined: 'RouteTakeOrderArgs';
           };
         },
-        {
-          name: 'limit';
-          type: 'u8';
-        },
       ];
     },
     {
-      name: 'deposit';
+      name: 'createTwapOrder';
       docs: [
-        "Deposit a certain amount of `base` and `quote` lamports into one's",
-        '[`Position`](crate::state::Position).',
+        'Create a TWAP order for an open orders account, selling or buying',
+        '`total_base_lots` in slices of `slice_base_lots` every `interval_seconds`.',
         '',
-        'Makers might wish to `deposit`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'Slices are ImmediateOrCancel orders at `limit_price_lots` placed by keepers',
+        'with `execute_twap_slice`, using the free balances of the open orders account.',
+        'The payer funds `keeper_fee_lamports` for each slice up front.',
+        '',
+        "Slices need to meet the market's minimum order size at the limit price, a",
+        'remainder below it is executed with the slice before it.',
       ];
       accounts: [
         {
-          name: 'owner';
-          isMut: false;
-          isSigner: true;
-        },
-        {
-          name: 'userBaseAccount';
+          name: 'payer';
           isMut: true;
-          isSigner: false;
+          isSigner: true;
         },
         {
-          name: 'userQuoteAccount';
-          isMut: true;
-          isSigner: false;
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
         },
         {
           name: 'openOrdersAccount';
-          isMut: true;
-          isSigner: false;
-        },
-        {
-          name: 'market';
-          isMut: true;
+          isMut: false;
           isSigner: false;
         },
         {
-          name: 'marketBaseVault';
+          name: 'twapOrder';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'marketQuoteVault';
-          isMut: true;
+          name: 'market';
+          isMut: false;
           isSigner: false;
         },
         {
-          name: 'tokenProgram';
+          name: 'systemProgram';
           isMut: false;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: 'baseAmount';
-          type: 'u64';
-        },
-        {
-          name: 'quoteAmount';
-          type: 'u64';
+          name: 'args';
+          type: {
+            defined: 'CreateTwapOrderArgs';
+          };
         },
       ];
     },
     {
-      name: 'refill';
+      name: 'executeTwapSlice';
       docs: [
-        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
-        'total lamports that the [`Position`](crate::state::Position) will have.',
+        'Execute the current slice of a TWAP order, permissionless.',
         '',
-        'Makers might wish to `refill`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'Slices whose window passed without being executed are skipped, as are slices',
+        "the free balance can't cover at the market's minimum order size. The keeper",
+        'receives the keeper fee of the slice, even if nothing could be filled.',
       ];
       accounts: [
         {
-          name: 'owner';
-          isMut: false;
+          name: 'keeper';
+          isMut: true;
           isSigner: true;
+          docs: ['Receives the keeper fee of the slice'];
         },
         {
-          name: 'userBaseAccount';
+          name: 'twapOrder';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'userQuoteAccount';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'openOrdersAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'bids';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'marketBaseVault';
+          name: 'asks';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'marketQuoteVault';
+          name: 'eventHeap';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'tokenProgram';
+          name: 'oracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'oracleB';
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
-      ];
-      args: [
         {
-          name: 'baseAmount';
-          type: 'u64';
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
         },
+      ];
+      args: [
         {
-          name: 'quoteAmount';
-          type: 'u64';
+          name: 'limit';
+          type: 'u8';
         },
       ];
     },
     {
-      name: 'settleFunds';
-      docs: ['Withdraw any available tokens.'];
+      name: 'cancelTwapOrder';
+      docs: [
+        'Close a TWAP order, returning its rent and the unused keeper fees.',
+      ];
       accounts: [
         {
-          name: 'owner';
-          isMut: true;
+          name: 'signer';
+          isMut: false;
           isSigner: true;
         },
         {
-          name: 'penaltyPayer';
-          isMut: true;
-          isSigner: true;
+          name: 'openOrdersAccount';
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'twapOrder';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'solDestination';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [];
+    },
+    {
+      name: 'consumeEvents';
+      docs: [
+        'Process up to `limit` [events](crate::state::AnyEvent).',
+        '',
+        "When a user places a 'take' order, they do not know beforehand which",
+        "market maker will have placed the 'make' order that they get executed",
+        "against. This prevents them from passing in a market maker's",
+        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
+        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
+        "uses a 'crank' system, where `place_order` only emits events, and",
+        '`consume_events` handles token settlement.',
+        '',
+        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
+        'and [`OutEvent`](crate::state::OutEvent)s.',
+        '',
+        'A `FillEvent` is emitted when an order is filled, and it is handled by',
+        'debiting whatever the taker is selling from the taker and crediting',
+        'it to the maker, and debiting whatever the taker is buying from the',
+        'maker and crediting it to the taker. Note that *no tokens are moved*,',
+        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        '',
+        'An `OutEvent` is emitted when a limit order needs to be removed from',
+        'the book during a `place_order` invocation, and it is handled by',
+        'crediting whatever the maker would have sold (quote token in a bid,',
+        'base token in an ask) back to the maker.',
+      ];
+      accounts: [
         {
-          name: 'marketAuthority';
+          name: 'consumeEventsAdmin';
           isMut: false;
-          isSigner: false;
+          isSigner: true;
+          isOptional: true;
         },
         {
-          name: 'marketBaseVault';
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'marketQuoteVault';
+          name: 'eventHeap';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'userBaseAccount';
-          isMut: true;
-          isSigner: false;
+          name: 'limit';
+          type: 'u64';
         },
+      ];
+    },
+    {
+      name: 'consumeGivenEvents';
+      docs: [
+        'Process the [events](crate::state::AnyEvent) at the given positions.',
+      ];
+      accounts: [
         {
-          name: 'userQuoteAccount';
-          isMut: true;
-          isSigner: false;
+          name: 'consumeEventsAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
         },
         {
-          name: 'referrerAccount';
+          name: 'market';
           isMut: true;
           isSigner: false;
-          isOptional: true;
         },
         {
-          name: 'tokenProgram';
-          isMut: false;
+          name: 'eventHeap';
+          isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'systemProgram';
-          isMut: false;
-          isSigner: false;
+          name: 'slots';
+          type: {
+            vec: 'u64';
+          };
         },
       ];
-      args: [];
     },
     {
-      name: 'settleFundsExpired';
+      name: 'cancelOrder';
       docs: [
-        'Withdraw any available tokens when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Cancel an order by its `order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
       ];
       accounts: [
         {
-          name: 'closeMarketAdmin';
+          name: 'signer';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'owner';
+          name: 'openOrdersAccount';
           isMut: true;
-          isSigner: true;
+          isSigner: false;
         },
         {
-          name: 'penaltyPayer';
-          isMut: true;
-          isSigner: true;
+          name: 'market';
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'bids';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'asks';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'marketAuthority';
-          isMut: false;
-          isSigner: false;
+          name: 'orderId';
+          type: 'u128';
         },
+      ];
+    },
+    {
+      name: 'cancelOrderByClientOrderId';
+      docs: [
+        'Cancel an order by its `client_order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ];
+      accounts: [
         {
-          name: 'marketBaseVault';
-          isMut: true;
-          isSigner: false;
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: 'marketQuoteVault';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'userBaseAccount';
-          isMut: true;
+          name: 'market';
+          isMut: false;
           isSigner: false;
         },
         {
-          name: 'userQuoteAccount';
+          name: 'bids';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'referrerAccount';
+          name: 'asks';
           isMut: true;
           isSigner: false;
-          isOptional: true;
-        },
-        {
-          name: 'tokenProgram';
-          isMut: false;
-          isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'systemProgram';
-          isMut: false;
-          isSigner: false;
+          name: 'clientOrderId';
+          type: 'u64';
         },
       ];
-      args: [];
+      returns: 'i64';
     },
     {
-      name: 'sweepFees';
-      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."];
+      name: 'cancelAllOrders';
+      docs: ['Cancel up to `limit` orders, optionally filtering by side'];
       accounts: [
         {
-          name: 'collectFeeAdmin';
+          name: 'signer';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'market';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'marketAuthority';
+          name: 'market';
           isMut: false;
           isSigner: false;
         },
         {
-          name: 'marketQuoteVault';
+          name: 'bids';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'tokenReceiverAccount';
+          name: 'asks';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'tokenProgram';
-          isMut: false;
-          isSigner: false;
+          name: 'sideOption';
+          type: {
+            option: {
+              defined: 'Side';
+            };
+          };
+        },
+        {
+          name: 'limit';
+          type: 'u8';
         },
       ];
-      args: [];
     },
     {
-      name: 'setDelegate';
+      name: 'deposit';
       docs: [
-        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        "Deposit a certain amount of `base` and `quote` lamports into one's",
+        '[`Position`](crate::state::Position).',
+        '',
+        'Makers might wish to `deposit`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
+        '',
+        'Without a user token account on a native mint side, lamports of the',
+        '`native_payer` are wrapped instead. The same goes for `place_order`.',
       ];
       accounts: [
         {
           name: 'owner';
-          isMut: true;
+          isMut: false;
           isSigner: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'userBaseAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'delegateAccount';
-          isMut: false;
+          name: 'userQuoteAccount';
+          isMut: true;
           isSigner: false;
           isOptional: true;
         },
-      ];
-      args: [];
-    },
-    {
-      name: 'setMarketExpired';
-      docs: [
-        'Set market to expired before pruning orders and closing the market (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ];
-      accounts: [
         {
-          name: 'closeMarketAdmin';
-          isMut: false;
-          isSigner: true;
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
         },
         {
           name: 'market';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [];
-    },
-    {
-      name: 'pruneOrders';
-      docs: [
-        'Remove orders from the book when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ];
-      accounts: [
         {
-          name: 'closeMarketAdmin';
-          isMut: false;
-          isSigner: true;
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'marketQuoteVault';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'tokenProgram';
           isMut: false;
           isSigner: false;
         },
         {
-          name: 'bids';
+          name: 'nativePayer';
           isMut: true;
-          isSigner: false;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint side without user token account',
+          ];
         },
         {
-          name: 'asks';
-          isMut: true;
+          name: 'systemProgram';
+          isMut: false;
           isSigner: false;
+          isOptional: true;
         },
       ];
       args: [
         {
-          name: 'limit';
-          type: 'u8';
+          name: 'baseAmount';
+          type: 'u64';
+        },
+        {
+          name: 'quoteAmount';
+          type: 'u64';
         },
       ];
     },
     {
-      name: 'stubOracleCreate';
+      name: 'refill';
+      docs: [
+        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
+        'total lamports that the [`Position`](crate::state::Position) will have.',
+        '',
+        'Makers might wish to `refill`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
+      ];
       accounts: [
-        {
-          name: 'payer';
-          isMut: true;
-          isSigner: true;
-        },
         {
           name: 'owner';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'userBaseAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'mint';
+          name: 'userQuoteAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
           isMut: false;
           isSigner: false;
         },
+        {
+          name: 'nativePayer';
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+          docs: [
+            'Wraps its lamports for a native mint side without user token account',
+          ];
+        },
         {
           name: 'systemProgram';
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
       ];
       args: [
         {
-          name: 'price';
-          type: 'f64';
+          name: 'baseAmount';
+          type: 'u64';
+        },
+        {
+          name: 'quoteAmount';
+          type: 'u64';
         },
       ];
     },
     {
-      name: 'stubOracleClose';
+      name: 'settleFunds';
+      docs: [
+        'Withdraw any available tokens.',
+        '',
+        'Leaving out the user token account of a native mint side unwraps it to',
+        'the owner, who has to be the `penalty_payer` then.',
+      ];
       accounts: [
         {
           name: 'owner';
-          isMut: false;
+          isMut: true;
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'solDestination';
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'tokenProgram';
+          name: 'marketAuthority';
           isMut: false;
           isSigner: false;
         },
-      ];
-      args: [];
-    },
-    {
-      name: 'stubOracleSet';
-      accounts: [
         {
-          name: 'owner';
-          isMut: false;
-          isSigner: true;
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'oracle';
+          name: 'marketQuoteVault';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
+        {
+          name: 'userBaseAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'userQuoteAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'referrerAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'nativeMint';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'unwrapAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'settleFundsExpired';
+      docs: [
+        'Withdraw any available tokens when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'userBaseAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'userQuoteAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrerAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'sweepFees';
+      docs: [
+        "Sweep fees, as a [`Market`](crate::state::Market)'s admin.",
+        '',
+        'Base fees of markets with fees in base are swept when the base vault and a',
+        'base token receiver account are passed.',
+      ];
+      accounts: [
+        {
+          name: 'collectFeeAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenReceiverAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: [
+            'Only needed to sweep the base fees of markets with fees in base',
+          ];
+        },
+        {
+          name: 'baseTokenReceiverAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setDelegate';
+      docs: [
+        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'delegateAccount';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setMarketExpired';
+      docs: [
+        'Set market to expired before pruning orders and closing the market (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'pruneOrders';
+      docs: [
+        'Remove orders from the book when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'startAuction';
+      docs: [
+        'Put the market in a call auction until `auction_end_ts`, e.g. before trading',
+        'resumes (only [`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'auctionEndTs';
+          type: 'i64';
+        },
+      ];
+    },
+    {
+      name: 'uncrossAuction';
+      docs: [
+        'End the call auction of a market.',
+        '',
+        'While in auction, orders are posted to the book without matching. Once',
+        '`auction_end_ts` has passed, anyone can call this to match the crossing orders',
+        'at the single price that maximizes the matched volume. Both sides of each match',
+        'are settled as makers through [`FillEvent`](crate::state::FillEvent)s, without',
+        "taker fees. Each match logs a single `FillLog`, from the bid's event.",
+        '',
+        'Matches up to `limit` pairs of orders per call. Continuous trading starts once',
+        'no more orders cross at the clearing price.',
+      ];
+      accounts: [
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'oracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'oracleB';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'marketStats';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+          docs: ['Needed once the market has market stats'];
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleCreate';
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'mint';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleClose';
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'stubOracleSet';
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
         {
           name: 'price';
           type: 'f64';
@@ -1734,10 +2443,26 @@ export type OpenbookV2 = {
             name: 'quoteDecimals';
             type: 'u8';
           },
+          {
+            name: 'feesInBase';
+            docs: [
+              '1 if taker fees and maker rebates are paid in base instead of quote, see',
+              '`base_fees_available`',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'hasMarketStats';
+            docs: [
+              '1 once the market has a `MarketStats` account, which the instructions matching',
+              'orders need then',
+            ];
+            type: 'u8';
+          },
           {
             name: 'padding1';
             type: {
-              array: ['u8', 5];
+              array: ['u8', 3];
             };
           },
           {
@@ -1821,6 +2546,18 @@ export type OpenbookV2 = {
               defined: 'OracleConfig';
             };
           },
+          {
+            name: 'oracleTwapPrice';
+            docs: [
+              'Time weighted average of the accepted native oracle prices, zero until the first',
+            ];
+            type: 'f64';
+          },
+          {
+            name: 'oracleTwapLastUpdateTs';
+            docs: ['Timestamp in seconds of the last oracle TWAP update'];
+            type: 'u64';
+          },
           {
             name: 'quoteLotSize';
             docs: [
@@ -1849,88 +2586,211 @@ export type OpenbookV2 = {
             type: 'u64';
           },
           {
-            name: 'registrationTime';
-            docs: ['Timestamp in seconds that the market was registered at.'];
+            name: 'registrationTime';
+            docs: ['Timestamp in seconds that the market was registered at.'];
+            type: 'i64';
+          },
+          {
+            name: 'makerFee';
+            docs: [
+              'Fees',
+              '',
+              'Fee (in 10^-6) when matching maker orders.',
+              'maker_fee < 0 it means some of the taker_fees goes to the maker',
+              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'takerFee';
+            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'];
+            type: 'i64';
+          },
+          {
+            name: 'feesAccrued';
+            docs: ['Total fees accrued in native quote'];
+            type: 'u128';
+          },
+          {
+            name: 'feesToReferrers';
+            docs: ['Total fees settled in native quote'];
+            type: 'u128';
+          },
+          {
+            name: 'referrerRebatesAccrued';
+            docs: ['Referrer rebates to be distributed'];
+            type: 'u64';
+          },
+          {
+            name: 'feesAvailable';
+            docs: ['Fees generated and available to withdraw via sweep_fees'];
+            type: 'u64';
+          },
+          {
+            name: 'makerVolume';
+            docs: [
+              'Cumulative maker volume (same as taker volume) in quote native units',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'takerVolumeWoOo';
+            docs: [
+              'Cumulative taker volume in quote native units due to place take orders',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'baseMint';
+            type: 'publicKey';
+          },
+          {
+            name: 'quoteMint';
+            type: 'publicKey';
+          },
+          {
+            name: 'marketBaseVault';
+            type: 'publicKey';
+          },
+          {
+            name: 'baseDepositTotal';
+            type: 'u64';
+          },
+          {
+            name: 'marketQuoteVault';
+            type: 'publicKey';
+          },
+          {
+            name: 'quoteDepositTotal';
+            type: 'u64';
+          },
+          {
+            name: 'minBaseLots';
+            docs: [
+              'Minimum number of base lots an order needs to have to be accepted.',
+              '',
+              'Remainders of partially matched orders below it are not posted.',
+            ];
             type: 'i64';
           },
           {
-            name: 'makerFee';
+            name: 'minQuoteLots';
             docs: [
-              'Fees',
+              'Minimum notional value in quote lots an order needs to have to be accepted.',
               '',
-              'Fee (in 10^-6) when matching maker orders.',
-              'maker_fee < 0 it means some of the taker_fees goes to the maker',
-              'maker_fee > 0, it means no taker_fee to the maker, and maker fee goes to the referral',
+              'Remainders of partially matched orders below it are not posted.',
             ];
             type: 'i64';
           },
           {
-            name: 'takerFee';
-            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'];
+            name: 'tickSizeLots';
+            docs: [
+              'Prices of orders posted at a fixed price need to be a multiple of this, in',
+              'price lots. Values of 0 and 1 mean any price lot is fine.',
+            ];
             type: 'i64';
           },
           {
-            name: 'feesAccrued';
-            docs: ['Total fees accrued in native quote'];
-            type: 'u128';
+            name: 'auctionEndTs';
+            docs: [
+              'While non-zero the market is in a call auction: orders are posted without',
+              'matching until `uncross_auction` is called after this timestamp.',
+            ];
+            type: 'i64';
           },
           {
-            name: 'feesToReferrers';
-            docs: ['Total fees settled in native quote'];
-            type: 'u128';
+            name: 'auctionPriceLots';
+            docs: [
+              'Clearing price of the auction in price lots, set once the uncross started.',
+              'Zero while orders are still being collected.',
+            ];
+            type: 'i64';
           },
           {
-            name: 'referrerRebatesAccrued';
-            docs: ['Referrer rebates to be distributed'];
-            type: 'u64';
+            name: 'lastPriceLots';
+            docs: [
+              'Price in lots of the last fill. Zero if nothing traded yet.',
+            ];
+            type: 'i64';
           },
           {
-            name: 'feesAvailable';
-            docs: ['Fees generated and available to withdraw via sweep_fees'];
+            name: 'lastTradeTs';
+            docs: ['Timestamp in seconds of the last fill'];
             type: 'u64';
           },
           {
-            name: 'makerVolume';
+            name: 'fillTwapPriceLots';
             docs: [
-              'Cumulative maker volume (same as taker volume) in quote native units',
+              'Time weighted average of the fill prices in lots over `ORACLE_TWAP_SECONDS`, as',
+              'of `last_trade_ts`. Read by markets using this one as their oracle.',
             ];
-            type: 'u128';
+            type: 'f64';
           },
           {
-            name: 'takerVolumeWoOo';
+            name: 'baseFeesAvailable';
             docs: [
-              'Cumulative taker volume in quote native units due to place take orders',
+              'Base fees generated on markets with fees in base, available to withdraw via',
+              "sweep_fees. Referrers don't get a share of them.",
             ];
-            type: 'u128';
+            type: 'u64';
           },
           {
-            name: 'baseMint';
-            type: 'publicKey';
+            name: 'reserved';
+            type: {
+              array: ['u8', 56];
+            };
           },
+        ];
+      };
+    },
+    {
+      name: 'marketStats';
+      docs: [
+        'Rolling 24 hour trade statistics of a market, in hourly buckets',
+        '',
+        'Created for a market with `create_market_stats`. From then on all the instructions',
+        'matching orders of the market need it.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
           {
-            name: 'quoteMint';
+            name: 'market';
             type: 'publicKey';
           },
           {
-            name: 'marketBaseVault';
-            type: 'publicKey';
+            name: 'bump';
+            type: 'u8';
           },
           {
-            name: 'baseDepositTotal';
-            type: 'u64';
+            name: 'padding';
+            type: {
+              array: ['u8', 7];
+            };
           },
           {
-            name: 'marketQuoteVault';
-            type: 'publicKey';
+            name: 'lastTradeTs';
+            docs: ['Timestamp in seconds of the last recorded fill'];
+            type: 'u64';
           },
           {
-            name: 'quoteDepositTotal';
-            type: 'u64';
+            name: 'buckets';
+            docs: [
+              'Fills by hour, hour `h` since the epoch being kept in `buckets[h % 24]`',
+            ];
+            type: {
+              array: [
+                {
+                  defined: 'TradeStats';
+                },
+                24,
+              ];
+            };
           },
           {
             name: 'reserved';
             type: {
-              array: ['u8', 128];
+              array: ['u8', 64];
             };
           },
         ];
@@ -2139,6 +2999,95 @@ export type OpenbookV2 = {
         ];
       };
     },
+    {
+      name: 'twapOrder';
+      docs: [
+        'Sells or buys `total_base_lots` over time, in slices of `slice_base_lots`',
+        '',
+        'Each slice is an ImmediateOrCancel order at `limit_price_lots`, placed by any',
+        'keeper through `execute_twap_slice` and paid from the free balances of the',
+        'open orders account. Slice `i` can be executed between `start_ts + i * interval_seconds`',
+        'and the start of the next slice, slices missing their window are skipped.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'openOrdersAccount';
+            type: 'publicKey';
+          },
+          {
+            name: 'market';
+            type: 'publicKey';
+          },
+          {
+            name: 'bump';
+            type: 'u8';
+          },
+          {
+            name: 'side';
+            docs: ['Side, as a `Side` value'];
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 6];
+            };
+          },
+          {
+            name: 'limitPriceLots';
+            type: 'i64';
+          },
+          {
+            name: 'totalBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'sliceBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'remainingBaseLots';
+            docs: ['Base lots of the slices not executed or skipped yet'];
+            type: 'i64';
+          },
+          {
+            name: 'filledBaseLots';
+            docs: ['Base lots actually filled by the executed slices'];
+            type: 'i64';
+          },
+          {
+            name: 'startTs';
+            type: 'u64';
+          },
+          {
+            name: 'intervalSeconds';
+            type: 'u64';
+          },
+          {
+            name: 'nextSlice';
+            docs: [
+              "Index of the first slice that wasn't executed or skipped yet",
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'keeperFeeLamports';
+            docs: [
+              'Lamports paid to the keeper of each executed slice, out of the account balance',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 64];
+            };
+          },
+        ];
+      };
+    },
   ];
   types: [
     {
@@ -2156,6 +3105,33 @@ export type OpenbookV2 = {
         ];
       };
     },
+    {
+      name: 'TradeStats';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'highPriceLots';
+            docs: [
+              'Highest fill price in lots, only meaningful with a non-zero volume',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'lowPriceLots';
+            docs: [
+              'Lowest fill price in lots, only meaningful with a non-zero volume',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'volumeBaseLots';
+            docs: ['Filled base lots'];
+            type: 'i64';
+          },
+        ];
+      };
+    },
     {
       name: 'Position';
       type: {
@@ -2271,10 +3247,26 @@ export type OpenbookV2 = {
             name: 'maxStalenessSlots';
             type: 'i64';
           },
+          {
+            name: 'maxStalenessSeconds';
+            docs: [
+              'Disabled if not positive, so that markets created before it was carved out',
+              'of `reserved` keep it off. Checked against oracles that report their update time.',
+            ];
+            type: 'i64';
+          },
+          {
+            name: 'maxTwapDeviation';
+            docs: [
+              'Largest accepted deviation of the oracle price from `Market::oracle_twap_price`,',
+              'relative to it. Disabled if zero.',
+            ];
+            type: 'f64';
+          },
           {
             name: 'reserved';
             type: {
-              array: ['u8', 72];
+              array: ['u8', 40];
             };
           },
         ];
@@ -2295,6 +3287,18 @@ export type OpenbookV2 = {
               option: 'u32';
             };
           },
+          {
+            name: 'maxStalenessSeconds';
+            type: {
+              option: 'u32';
+            };
+          },
+          {
+            name: 'maxTwapDeviation';
+            type: {
+              option: 'f32';
+            };
+          },
         ];
       };
     },
@@ -2393,10 +3397,18 @@ export type OpenbookV2 = {
             name: 'makerSlot';
             type: 'u8';
           },
+          {
+            name: 'auction';
+            type: 'u8';
+          },
+          {
+            name: 'auctionMirror';
+            type: 'u8';
+          },
           {
             name: 'padding';
             type: {
-              array: ['u8', 4];
+              array: ['u8', 2];
             };
           },
           {
@@ -2404,7 +3416,7 @@ export type OpenbookV2 = {
             type: 'u64';
           },
           {
-            name: 'seqNum';
+            name: 'marketSeqNum';
             type: 'u64';
           },
           {
@@ -2776,6 +3788,10 @@ export type OpenbookV2 = {
             name: 'limit';
             type: 'u8';
           },
+          {
+            name: 'reduceOnly';
+            type: 'bool';
+          },
         ];
       };
     },
@@ -2796,6 +3812,10 @@ export type OpenbookV2 = {
             name: 'expiryTimestamp';
             type: 'u64';
           },
+          {
+            name: 'reduceOnly';
+            type: 'bool';
+          },
         ];
       };
     },
@@ -2837,24 +3857,95 @@ export type OpenbookV2 = {
             };
           },
           {
-            name: 'expiryTimestamp';
+            name: 'expiryTimestamp';
+            type: 'u64';
+          },
+          {
+            name: 'selfTradeBehavior';
+            type: {
+              defined: 'SelfTradeBehavior';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+          {
+            name: 'priceOffsetBps';
+            type: {
+              option: 'i32';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceTakeOrderArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'side';
+            type: {
+              defined: 'Side';
+            };
+          },
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxBaseLots';
+            type: 'i64';
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees';
+            type: 'i64';
+          },
+          {
+            name: 'orderType';
+            type: {
+              defined: 'PlaceOrderType';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'RouteTakeOrderArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'amountIn';
+            docs: [
+              'Native amount of the input token to sell on the first market.',
+            ];
             type: 'u64';
           },
           {
-            name: 'selfTradeBehavior';
-            type: {
-              defined: 'SelfTradeBehavior';
-            };
+            name: 'minAmountOut';
+            docs: [
+              'Minimum native amount of the output token to receive from the second market.',
+            ];
+            type: 'u64';
           },
           {
             name: 'limit';
+            docs: [
+              'Maximum number of orders to fill on each of the two markets.',
+            ];
             type: 'u8';
           },
         ];
       };
     },
     {
-      name: 'PlaceTakeOrderArgs';
+      name: 'CreateTwapOrderArgs';
       type: {
         kind: 'struct';
         fields: [
@@ -2865,26 +3956,33 @@ export type OpenbookV2 = {
             };
           },
           {
-            name: 'priceLots';
+            name: 'limitPriceLots';
+            docs: ['Worst price the slices are executed at.'];
             type: 'i64';
           },
           {
-            name: 'maxBaseLots';
+            name: 'totalBaseLots';
             type: 'i64';
           },
           {
-            name: 'maxQuoteLotsIncludingFees';
+            name: 'sliceBaseLots';
             type: 'i64';
           },
           {
-            name: 'orderType';
-            type: {
-              defined: 'PlaceOrderType';
-            };
+            name: 'intervalSeconds';
+            type: 'u64';
           },
           {
-            name: 'limit';
-            type: 'u8';
+            name: 'startTs';
+            docs: [
+              'Timestamp of the first slice, it starts right away if in the past.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'keeperFeeLamports';
+            docs: ['Lamports paid to the keeper executing a slice.'];
+            type: 'u64';
           },
         ];
       };
@@ -2909,6 +4007,21 @@ export type OpenbookV2 = {
           {
             name: 'RaydiumCLMM';
           },
+          {
+            name: 'OpenBookMarket';
+          },
+          {
+            name: 'OrcaWhirlpool';
+          },
+          {
+            name: 'RaydiumCPMM';
+          },
+          {
+            name: 'SwitchboardOnDemand';
+          },
+          {
+            name: 'OpenBookBookSide';
+          },
         ];
       };
     },
@@ -3003,6 +4116,12 @@ export type OpenbookV2 = {
           {
             name: 'FillOrKill';
           },
+          {
+            name: 'ExactOut';
+          },
+          {
+            name: 'PostOnlyCancelReplace';
+          },
         ];
       };
     },
@@ -3020,6 +4139,9 @@ export type OpenbookV2 = {
           {
             name: 'PostOnlySlide';
           },
+          {
+            name: 'PostOnlyCancelReplace';
+          },
         ];
       };
     },
@@ -3133,6 +4255,25 @@ export type OpenbookV2 = {
               },
             ];
           },
+          {
+            name: 'OraclePeggedBps';
+            fields: [
+              {
+                name: 'price_offset_bps';
+                type: 'i32';
+              },
+              {
+                name: 'order_type';
+                type: {
+                  defined: 'PostOrderType';
+                };
+              },
+              {
+                name: 'peg_limit';
+                type: 'i64';
+              },
+            ];
+          },
           {
             name: 'FillOrKill';
             fields: [
@@ -3142,6 +4283,15 @@ export type OpenbookV2 = {
               },
             ];
           },
+          {
+            name: 'ExactOut';
+            fields: [
+              {
+                name: 'price_lots';
+                type: 'i64';
+              },
+            ];
+          },
         ];
       };
     },
@@ -3350,127 +4500,297 @@ export type OpenbookV2 = {
           index: false;
         },
         {
-          name: 'delegate';
-          type: {
-            option: 'publicKey';
-          };
+          name: 'delegate';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SettleFundsLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrerRebate';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrer';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SweepFeesLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'amount';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'receiver';
+          type: 'publicKey';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'OpenOrdersPositionLog';
+      fields: [
+        {
+          name: 'owner';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'openOrdersAccountNum';
+          type: 'u32';
+          index: false;
+        },
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'bidsBaseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'bidsQuoteLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'asksBaseLots';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'baseFreeNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteFreeNative';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'lockedMakerFees';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'referrerRebatesAvailable';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerVolume';
+          type: 'u128';
+          index: false;
+        },
+        {
+          name: 'takerVolume';
+          type: 'u128';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'OrderPlacedLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'owner';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'orderId';
+          type: 'u128';
+          index: false;
+        },
+        {
+          name: 'clientOrderId';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'side';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'orderTree';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'priceData';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'pegLimit';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'price';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quantity';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'timeInForce';
+          type: 'u16';
+          index: false;
+        },
+        {
+          name: 'timestamp';
+          type: 'u64';
           index: false;
         },
       ];
     },
     {
-      name: 'SettleFundsLog';
+      name: 'OrderCancelledLog';
       fields: [
         {
-          name: 'openOrdersAccount';
+          name: 'market';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'baseNative';
-          type: 'u64';
+          name: 'owner';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'quoteNative';
-          type: 'u64';
+          name: 'orderId';
+          type: 'u128';
           index: false;
         },
         {
-          name: 'referrerRebate';
+          name: 'clientOrderId';
           type: 'u64';
           index: false;
         },
         {
-          name: 'referrer';
-          type: {
-            option: 'publicKey';
-          };
+          name: 'side';
+          type: 'u8';
           index: false;
         },
-      ];
-    },
-    {
-      name: 'SweepFeesLog';
-      fields: [
         {
-          name: 'market';
-          type: 'publicKey';
+          name: 'orderTree';
+          type: 'u8';
           index: false;
         },
         {
-          name: 'amount';
+          name: 'priceData';
           type: 'u64';
           index: false;
         },
         {
-          name: 'receiver';
-          type: 'publicKey';
+          name: 'pegLimit';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quantity';
+          type: 'i64';
           index: false;
         },
       ];
     },
     {
-      name: 'OpenOrdersPositionLog';
+      name: 'OrderExpiredLog';
       fields: [
-        {
-          name: 'owner';
-          type: 'publicKey';
-          index: false;
-        },
-        {
-          name: 'openOrdersAccountNum';
-          type: 'u32';
-          index: false;
-        },
         {
           name: 'market';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'bidsBaseLots';
-          type: 'i64';
+          name: 'owner';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'bidsQuoteLots';
-          type: 'i64';
+          name: 'orderId';
+          type: 'u128';
           index: false;
         },
         {
-          name: 'asksBaseLots';
-          type: 'i64';
+          name: 'clientOrderId';
+          type: 'u64';
           index: false;
         },
         {
-          name: 'baseFreeNative';
-          type: 'u64';
+          name: 'side';
+          type: 'u8';
           index: false;
         },
         {
-          name: 'quoteFreeNative';
-          type: 'u64';
+          name: 'orderTree';
+          type: 'u8';
           index: false;
         },
         {
-          name: 'lockedMakerFees';
+          name: 'priceData';
           type: 'u64';
           index: false;
         },
         {
-          name: 'referrerRebatesAvailable';
-          type: 'u64';
+          name: 'pegLimit';
+          type: 'i64';
           index: false;
         },
         {
-          name: 'makerVolume';
-          type: 'u128';
+          name: 'quantity';
+          type: 'i64';
           index: false;
         },
         {
-          name: 'takerVolume';
-          type: 'u128';
+          name: 'expiry';
+          type: 'u64';
           index: false;
         },
       ];
@@ -3673,110 +4993,624 @@ export type OpenbookV2 = {
       msg: 'Could not find order in user account';
     },
     {
-      code: 6039;
-      name: 'InvalidPostAmount';
-      msg: 'Amount to post above book limits';
+      code: 6039;
+      name: 'InvalidPostAmount';
+      msg: 'Amount to post above book limits';
+    },
+    {
+      code: 6040;
+      name: 'DisabledOraclePeg';
+      msg: 'Oracle peg orders are not enabled for this market';
+    },
+    {
+      code: 6041;
+      name: 'NonEmptyMarket';
+      msg: 'Cannot close a non-empty market';
+    },
+    {
+      code: 6042;
+      name: 'NonEmptyOpenOrdersPosition';
+      msg: 'Cannot close a non-empty open orders account';
+    },
+    {
+      code: 6043;
+      name: 'WouldExecutePartially';
+      msg: 'Fill-Or-Kill order would generate a partial execution';
+    },
+    {
+      code: 6044;
+      name: 'InvalidInputPegOffset';
+      msg: 'Peg offset should be given either in lots or in bps';
+    },
+    {
+      code: 6045;
+      name: 'InvalidInputMarketLimits';
+      msg: 'Market minimum order size and tick size cannot be negative';
+    },
+    {
+      code: 6046;
+      name: 'OrderBelowMinimumSize';
+      msg: 'Order size is below the market minimum';
+    },
+    {
+      code: 6047;
+      name: 'InvalidInputTickSize';
+      msg: 'Order price is not a multiple of the market tick size';
+    },
+    {
+      code: 6048;
+      name: 'WouldNotFillExactOut';
+      msg: 'Exact-out order would not deliver the full output amount';
+    },
+    {
+      code: 6049;
+      name: 'InvalidInputRouteMints';
+      msg: 'Route input, intermediate and output mints do not match the markets';
+    },
+    {
+      code: 6050;
+      name: 'RouteBelowMinimumOutput';
+      msg: 'Route output is below the requested minimum';
+    },
+    {
+      code: 6051;
+      name: 'InvalidInputAuction';
+      msg: "Auction end must be in the future and maker fees can't be negative";
+    },
+    {
+      code: 6052;
+      name: 'MarketInAuction';
+      msg: "Orders can't be matched while the market is in auction";
+    },
+    {
+      code: 6053;
+      name: 'MarketNotInAuction';
+      msg: 'Market is not in auction';
+    },
+    {
+      code: 6054;
+      name: 'AuctionNotEnded';
+      msg: 'Auction has not ended yet';
+    },
+    {
+      code: 6055;
+      name: 'InvalidInputNativeTransfer';
+      msg: 'Native SOL transfers without a token account need a native mint vault and the native accounts';
+    },
+    {
+      code: 6056;
+      name: 'InvalidInputVaultOracle';
+      msg: 'Pool vault oracles need the base vault as oracle a and the quote vault as oracle b, of the pool passed as the first remaining account';
+    },
+    {
+      code: 6057;
+      name: 'InvalidInputBookOracle';
+      msg: 'Book oracles need the bids and asks of a market with the same mints and lot sizes, passed as the first remaining account';
+    },
+    {
+      code: 6058;
+      name: 'InvalidInputTwap';
+      msg: 'TWAP total size, slice size and interval should be greater than zero';
+    },
+    {
+      code: 6059;
+      name: 'TwapSliceNotDue';
+      msg: 'The next slice of the TWAP order is not due yet';
+    },
+    {
+      code: 6060;
+      name: 'TwapOrderCompleted';
+      msg: 'All slices of the TWAP order were executed or skipped';
+    },
+    {
+      code: 6061;
+      name: 'InvalidInputPoolOracle';
+      msg: 'Pool oracles need to price the base mint in the quote mint and a max twap deviation';
+    },
+    {
+      code: 6062;
+      name: 'MissingMarketStats';
+      msg: 'The market has market stats, which need to be passed';
+    },
+    {
+      code: 6063;
+      name: 'InvalidInputMarketOracle';
+      msg: 'OpenBook market and book side oracles need a max twap deviation';
+    },
+    {
+      code: 6064;
+      name: 'ReduceOnlyExceedsFreeBalance';
+      msg: 'Reduce-only orders can only use the free balance, including taker fees in base';
+    },
+  ];
+};
+
+export const IDL: OpenbookV2 = {
+  version: '0.1.0',
+  name: 'openbook_v2',
+  instructions: [
+    {
+      name: 'createMarket',
+      docs: [
+        'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'A non-zero `auction_end_ts` opens the market with a call auction, see',
+        '`uncross_auction`.',
+        '',
+        'With `fees_in_base` taker fees and maker rebates are paid in base: asks pay',
+        'their taker fee on top of the base they sell and bids receive that much less',
+        "base. The maker fee can't be positive then.",
+        '',
+        'Book side oracles need their market, and Raydium CPMM vault oracles their',
+        'pool state, as the first remaining account. Pool oracles and OpenBook market',
+        'and book side oracles need a `max_twap_deviation`.',
+      ],
+      accounts: [
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+          docs: [
+            'Accounts are initialized by client,',
+            'anchor discriminator is set first when ix exits,',
+          ],
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'baseMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'quoteMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'associatedTokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'collectFeeAdmin',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'eventAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'program',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+        {
+          name: 'oracleConfig',
+          type: {
+            defined: 'OracleConfigParams',
+          },
+        },
+        {
+          name: 'quoteLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'baseLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'makerFee',
+          type: 'i64',
+        },
+        {
+          name: 'takerFee',
+          type: 'i64',
+        },
+        {
+          name: 'timeExpiry',
+          type: 'i64',
+        },
+        {
+          name: 'minBaseLots',
+          type: 'i64',
+        },
+        {
+          name: 'minQuoteLots',
+          type: 'i64',
+        },
+        {
+          name: 'tickSizeLots',
+          type: 'i64',
+        },
+        {
+          name: 'auctionEndTs',
+          type: 'i64',
+        },
+        {
+          name: 'feesInBase',
+          type: 'bool',
+        },
+      ],
+    },
+    {
+      name: 'closeMarket',
+      docs: [
+        'Close a [`Market`](crate::state::Market) (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        '',
+        'The market must be expired and its book and event heap empty. The market,',
+        'bids, asks, event heap and market stats accounts are closed and their rent',
+        'goes to `sol_destination`.',
+      ],
+      accounts: [
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Closed with the market once it has market stats'],
+        },
+      ],
+      args: [],
     },
     {
-      code: 6040;
-      name: 'DisabledOraclePeg';
-      msg: 'Oracle peg orders are not enabled for this market';
+      name: 'createMarketStats',
+      docs: [
+        'Create the [`MarketStats`](crate::state::MarketStats) account of a market,',
+        'keeping the high, low and volume of its fills of the last 24 hours.',
+        '',
+        'Anyone can create it. From then on the instructions matching orders of the',
+        'market need it.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6041;
-      name: 'NonEmptyMarket';
-      msg: 'Cannot close a non-empty market';
+      name: 'createOpenOrdersIndexer',
+      docs: [
+        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6042;
-      name: 'NonEmptyOpenOrdersPosition';
-      msg: 'Cannot close a non-empty open orders account';
+      name: 'closeOpenOrdersIndexer',
+      docs: [
+        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6043;
-      name: 'WouldExecutePartially';
-      msg: 'Fill-Or-Kill order would generate a partial execution';
+      name: 'createOpenOrdersAccount',
+      docs: [
+        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'delegateAccount',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+      ],
     },
-  ];
-};
-
-export const IDL: OpenbookV2 = {
-  version: '0.1.0',
-  name: 'openbook_v2',
-  instructions: [
     {
-      name: 'createMarket',
+      name: 'closeOpenOrdersAccount',
       docs: [
-        'Create a [`Market`](crate::state::Market) for a given token pair.',
+        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
       ],
       accounts: [
         {
-          name: 'market',
-          isMut: true,
+          name: 'owner',
+          isMut: false,
           isSigner: true,
         },
         {
-          name: 'marketAuthority',
-          isMut: false,
+          name: 'openOrdersIndexer',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
-          docs: [
-            'Accounts are initialized by client,',
-            'anchor discriminator is set first when ix exits,',
-          ],
         },
         {
-          name: 'asks',
+          name: 'solDestination',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
-          isMut: true,
+          name: 'systemProgram',
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'placeOrder',
+      docs: [
+        'Place an order.',
+        '',
+        'Different types of orders have different effects on the order book,',
+        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
+        '',
+        '`price_lots` refers to the price in lots: the number of quote lots',
+        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
+        '',
+        '`expiry_timestamp` is a unix timestamp for when this order should',
+        'expire. If 0 is passed in, the order will never expire. If the time',
+        'is in the past, the instruction is skipped. Timestamps in the future',
+        'are reduced to now + 65,535s.',
+        '',
+        '`limit` determines the maximum number of orders from the book to fill,',
+        'and can be used to limit CU spent. When the limit is reached, processing',
+        'stops and the instruction succeeds.',
+      ],
+      accounts: [
         {
-          name: 'payer',
-          isMut: true,
+          name: 'signer',
+          isMut: false,
           isSigner: true,
         },
         {
-          name: 'marketBaseVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'baseMint',
-          isMut: false,
+          name: 'market',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'quoteMint',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'eventHeap',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'associatedTokenProgram',
-          isMut: false,
+          name: 'marketVault',
+          isMut: true,
           isSigner: false,
         },
         {
@@ -3792,83 +5626,70 @@ export const IDL: OpenbookV2 = {
           isOptional: true,
         },
         {
-          name: 'collectFeeAdmin',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: false,
+          name: 'nativePayer',
+          isMut: true,
+          isSigner: true,
           isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ],
         },
         {
-          name: 'consumeEventsAdmin',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'closeMarketAdmin',
-          isMut: false,
+          name: 'marketStats',
+          isMut: true,
           isSigner: false,
           isOptional: true,
-        },
-        {
-          name: 'eventAuthority',
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: 'program',
-          isMut: false,
-          isSigner: false,
+          docs: ['Needed once the market has market stats'],
         },
       ],
       args: [
         {
-          name: 'name',
-          type: 'string',
-        },
-        {
-          name: 'oracleConfig',
+          name: 'args',
           type: {
-            defined: 'OracleConfigParams',
+            defined: 'PlaceOrderArgs',
           },
         },
-        {
-          name: 'quoteLotSize',
-          type: 'i64',
-        },
-        {
-          name: 'baseLotSize',
-          type: 'i64',
-        },
-        {
-          name: 'makerFee',
-          type: 'i64',
-        },
-        {
-          name: 'takerFee',
-          type: 'i64',
-        },
-        {
-          name: 'timeExpiry',
-          type: 'i64',
-        },
       ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      name: 'closeMarket',
-      docs: [
-        'Close a [`Market`](crate::state::Market) (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ],
+      name: 'editOrder',
+      docs: ['Edit an order.'],
       accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
           isMut: false,
           isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
         },
         {
           name: 'market',
@@ -3891,180 +5712,184 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'marketVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'oracleA',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'createOpenOrdersIndexer',
-      docs: [
-        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
-      ],
-      accounts: [
         {
-          name: 'payer',
-          isMut: true,
-          isSigner: true,
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'owner',
+          name: 'tokenProgram',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'nativePayer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
+          isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ],
         },
         {
           name: 'systemProgram',
           isMut: false,
           isSigner: false,
-        },
-      ],
-      args: [],
-    },
-    {
-      name: 'closeOpenOrdersIndexer',
-      docs: [
-        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
-      ],
-      accounts: [
-        {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'marketStats',
           isMut: true,
           isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
         },
+      ],
+      args: [
         {
-          name: 'solDestination',
-          isMut: true,
-          isSigner: false,
+          name: 'clientOrderId',
+          type: 'u64',
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
+          name: 'expectedCancelSize',
+          type: 'i64',
+        },
+        {
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderArgs',
+          },
         },
       ],
-      args: [],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      name: 'createOpenOrdersAccount',
-      docs: [
-        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
+      name: 'editOrderPegged',
+      docs: ['Edit an order pegged.'],
       accounts: [
         {
-          name: 'payer',
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'owner',
+          name: 'openOrdersAdmin',
           isMut: false,
           isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'delegateAccount',
-          isMut: false,
+          name: 'userTokenAccount',
+          isMut: true,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'eventHeap',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'name',
-          type: 'string',
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
         },
-      ],
-    },
-    {
-      name: 'closeOpenOrdersAccount',
-      docs: [
-        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
-      accounts: [
         {
-          name: 'owner',
+          name: 'oracleA',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'openOrdersIndexer',
-          isMut: true,
+          name: 'oracleB',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'nativePayer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
+          isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ],
         },
         {
           name: 'systemProgram',
           isMut: false,
           isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
         },
       ],
-      args: [],
+      args: [
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+        },
+        {
+          name: 'expectedCancelSize',
+          type: 'i64',
+        },
+        {
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderPeggedArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      name: 'placeOrder',
-      docs: [
-        'Place an order.',
-        '',
-        'Different types of orders have different effects on the order book,',
-        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
-        '',
-        '`price_lots` refers to the price in lots: the number of quote lots',
-        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
-        '',
-        '`expiry_timestamp` is a unix timestamp for when this order should',
-        'expire. If 0 is passed in, the order will never expire. If the time',
-        'is in the past, the instruction is skipped. Timestamps in the future',
-        'are reduced to now + 65,535s.',
-        '',
-        '`limit` determines the maximum number of orders from the book to fill,',
-        'and can be used to limit CU spent. When the limit is reached, processing',
-        'stops and the instruction succeeds.',
-      ],
+      name: 'placeOrders',
+      docs: ['Place multiple orders'],
       accounts: [
         {
           name: 'signer',
@@ -4083,7 +5908,12 @@ export const IDL: OpenbookV2 = {
           isOptional: true,
         },
         {
-          name: 'userTokenAccount',
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
@@ -4108,7 +5938,12 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
@@ -4129,22 +5964,51 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
+        },
       ],
       args: [
         {
-          name: 'args',
+          name: 'ordersType',
           type: {
-            defined: 'PlaceOrderArgs',
+            defined: 'PlaceOrderType',
+          },
+        },
+        {
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
+        },
+        {
+          name: 'asks',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
           },
         },
+        {
+          name: 'limit',
+          type: 'u8',
+        },
       ],
       returns: {
-        option: 'u128',
+        vec: {
+          option: 'u128',
+        },
       },
     },
     {
-      name: 'editOrder',
-      docs: ['Edit an order.'],
+      name: 'cancelAllAndPlaceOrders',
+      docs: ['Cancel orders and place multiple orders.'],
       accounts: [
         {
           name: 'signer',
@@ -4163,7 +6027,12 @@ export const IDL: OpenbookV2 = {
           isOptional: true,
         },
         {
-          name: 'userTokenAccount',
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
@@ -4188,7 +6057,12 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
@@ -4209,30 +6083,51 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
+        },
       ],
       args: [
         {
-          name: 'clientOrderId',
-          type: 'u64',
+          name: 'ordersType',
+          type: {
+            defined: 'PlaceOrderType',
+          },
         },
         {
-          name: 'expectedCancelSize',
-          type: 'i64',
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
         },
         {
-          name: 'placeOrder',
+          name: 'asks',
           type: {
-            defined: 'PlaceOrderArgs',
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
           },
         },
+        {
+          name: 'limit',
+          type: 'u8',
+        },
       ],
       returns: {
-        option: 'u128',
+        vec: {
+          option: 'u128',
+        },
       },
     },
     {
-      name: 'editOrderPegged',
-      docs: ['Edit an order pegged.'],
+      name: 'placeOrderPegged',
+      docs: ['Place an oracle-peg order.'],
       accounts: [
         {
           name: 'signer',
@@ -4254,6 +6149,7 @@ export const IDL: OpenbookV2 = {
           name: 'userTokenAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'market',
@@ -4297,18 +6193,32 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'clientOrderId',
-          type: 'u64',
+          name: 'nativePayer',
+          isMut: true,
+          isSigner: true,
+          isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint vault without user token account',
+          ],
         },
         {
-          name: 'expectedCancelSize',
-          type: 'i64',
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'placeOrder',
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
+        },
+      ],
+      args: [
+        {
+          name: 'args',
           type: {
             defined: 'PlaceOrderPeggedArgs',
           },
@@ -4319,47 +6229,60 @@ export const IDL: OpenbookV2 = {
       },
     },
     {
-      name: 'placeOrders',
-      docs: ['Place multiple orders'],
+      name: 'placeTakeOrder',
+      docs: [
+        'Place an order that shall take existing liquidity off of the book, not',
+        'add a new order off the book.',
+        '',
+        'This type of order allows for instant token settlement for the taker.',
+        '',
+        'With `PlaceOrderType::ExactOut` a bid receives `max_base_lots` paying at',
+        'most `max_quote_lots_including_fees`, and an ask receives at least',
+        '`max_quote_lots_including_fees` selling at most `max_base_lots`, both net',
+        "of taker fees. Asks may receive up to a base lot's worth more, as fills",
+        "are in whole base lots. It fails if the output can't be fully delivered.",
+        '',
+        'On a native mint side the user token account can be left out to pay',
+        'with and receive lamports of the signer directly.',
+      ],
       accounts: [
         {
           name: 'signer',
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'penaltyPayer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
+          name: 'marketAuthority',
           isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userQuoteAccount',
-          isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
@@ -4369,14 +6292,16 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'marketBaseVault',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'oracleA',
@@ -4395,294 +6320,322 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'asks',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'nativeMint',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'limit',
-          type: 'u8',
+          name: 'unwrapAccount',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
         },
       ],
-      returns: {
-        vec: {
-          option: 'u128',
+      args: [
+        {
+          name: 'args',
+          type: {
+            defined: 'PlaceTakeOrderArgs',
+          },
         },
-      },
+      ],
     },
     {
-      name: 'cancelAllAndPlaceOrders',
-      docs: ['Cancel orders and place multiple orders.'],
+      name: 'routeTakeOrder',
+      docs: [
+        'Swap through two markets that share a mint, e.g. SOL -> USDC -> BONK,',
+        'taking liquidity off both books.',
+        '',
+        'Up to `amount_in` of the input token is sold on the first market at any',
+        'price and its whole output is sold on the second market. The instruction',
+        'fails if less than `min_amount_out` of the output token is received.',
+        '',
+        'The intermediate token moves directly between the market vaults, only',
+        'the part of it the second market could not take (lot size rounding or',
+        'missing liquidity) is sent to `user_intermediate_account`.',
+        '',
+        '`limit` caps the number of orders to fill on each of the markets.',
+      ],
       accounts: [
         {
           name: 'signer',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'openOrdersAccount',
           isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'openOrdersAdmin',
-          isMut: false,
           isSigner: true,
-          isOptional: true,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'penaltyPayer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'userBaseAccount',
+          name: 'firstMarket',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
-          isMut: true,
+          name: 'firstMarketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'firstBids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'firstAsks',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'firstMarketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'firstMarketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'firstEventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'firstOracleA',
           isMut: false,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'oracleB',
+          name: 'firstOracleB',
           isMut: false,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'secondMarket',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
+          name: 'secondMarketAuthority',
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'secondBids',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'asks',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'secondAsks',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'limit',
-          type: 'u8',
-        },
-      ],
-      returns: {
-        vec: {
-          option: 'u128',
+          name: 'secondMarketBaseVault',
+          isMut: true,
+          isSigner: false,
         },
-      },
-    },
-    {
-      name: 'placeOrderPegged',
-      docs: ['Place an oracle-peg order.'],
-      accounts: [
         {
-          name: 'signer',
-          isMut: false,
-          isSigner: true,
+          name: 'secondMarketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'secondEventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
+          name: 'secondOracleA',
           isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userTokenAccount',
-          isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'market',
-          isMut: true,
+          name: 'secondOracleB',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'bids',
+          name: 'userInputAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'userIntermediateAccount',
           isMut: true,
           isSigner: false,
+          docs: [
+            "Receives the part of the first leg's output that the second leg could not use",
+          ],
         },
         {
-          name: 'eventHeap',
+          name: 'userOutputAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketVault',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'firstMarketStats',
+          isMut: true,
           isSigner: false,
           isOptional: true,
+          docs: ['Needed once the first market has market stats'],
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'secondMarketStats',
+          isMut: true,
           isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the second market has market stats'],
         },
       ],
       args: [
         {
           name: 'args',
           type: {
-            defined: 'PlaceOrderPeggedArgs',
+            defined: 'RouteTakeOrderArgs',
           },
         },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'placeTakeOrder',
+      name: 'createTwapOrder',
       docs: [
-        'Place an order that shall take existing liquidity off of the book, not',
-        'add a new order off the book.',
+        'Create a TWAP order for an open orders account, selling or buying',
+        '`total_base_lots` in slices of `slice_base_lots` every `interval_seconds`.',
         '',
-        'This type of order allows for instant token settlement for the taker.',
+        'Slices are ImmediateOrCancel orders at `limit_price_lots` placed by keepers',
+        'with `execute_twap_slice`, using the free balances of the open orders account.',
+        'The payer funds `keeper_fee_lamports` for each slice up front.',
+        '',
+        "Slices need to meet the market's minimum order size at the limit price, a",
+        'remainder below it is executed with the slice before it.',
       ],
       accounts: [
         {
-          name: 'signer',
+          name: 'payer',
           isMut: true,
           isSigner: true,
         },
         {
-          name: 'penaltyPayer',
-          isMut: true,
+          name: 'signer',
+          isMut: false,
           isSigner: true,
         },
         {
-          name: 'market',
+          name: 'openOrdersAccount',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'twapOrder',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketAuthority',
+          name: 'market',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'systemProgram',
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'asks',
+          name: 'args',
+          type: {
+            defined: 'CreateTwapOrderArgs',
+          },
+        },
+      ],
+    },
+    {
+      name: 'executeTwapSlice',
+      docs: [
+        'Execute the current slice of a TWAP order, permissionless.',
+        '',
+        'Slices whose window passed without being executed are skipped, as are slices',
+        "the free balance can't cover at the market's minimum order size. The keeper",
+        'receives the keeper fee of the slice, even if nothing could be filled.',
+      ],
+      accounts: [
+        {
+          name: 'keeper',
+          isMut: true,
+          isSigner: true,
+          docs: ['Receives the keeper fee of the slice'],
+        },
+        {
+          name: 'twapOrder',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'eventHeap',
           isMut: true,
           isSigner: false,
         },
@@ -4699,30 +6652,48 @@ export const IDL: OpenbookV2 = {
           isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
+        },
+      ],
+      args: [
+        {
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+    },
+    {
+      name: 'cancelTwapOrder',
+      docs: [
+        'Close a TWAP order, returning its rent and the unused keeper fees.',
+      ],
+      accounts: [
+        {
+          name: 'signer',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'systemProgram',
+          name: 'openOrdersAccount',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
+          name: 'twapOrder',
+          isMut: true,
+          isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'args',
-          type: {
-            defined: 'PlaceTakeOrderArgs',
-          },
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
       name: 'consumeEvents',
@@ -4946,6 +6917,9 @@ export const IDL: OpenbookV2 = {
         '',
         'Makers might wish to `deposit`, rather than have actual tokens moved for',
         'each trade, in order to reduce CUs.',
+        '',
+        'Without a user token account on a native mint side, lamports of the',
+        '`native_payer` are wrapped instead. The same goes for `place_order`.',
       ],
       accounts: [
         {
@@ -4957,11 +6931,13 @@ export const IDL: OpenbookV2 = {
           name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'openOrdersAccount',
@@ -4988,6 +6964,21 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'nativePayer',
+          isMut: true,
+          isSigner: true,
+          isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint side without user token account',
+          ],
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
       ],
       args: [
         {
@@ -5019,11 +7010,13 @@ export const IDL: OpenbookV2 = {
           name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'openOrdersAccount',
@@ -5050,6 +7043,21 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'nativePayer',
+          isMut: true,
+          isSigner: true,
+          isOptional: true,
+          docs: [
+            'Wraps its lamports for a native mint side without user token account',
+          ],
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
       ],
       args: [
         {
@@ -5064,7 +7072,12 @@ export const IDL: OpenbookV2 = {
     },
     {
       name: 'settleFunds',
-      docs: ['Withdraw any available tokens.'],
+      docs: [
+        'Withdraw any available tokens.',
+        '',
+        'Leaving out the user token account of a native mint side unwraps it to',
+        'the owner, who has to be the `penalty_payer` then.',
+      ],
       accounts: [
         {
           name: 'owner',
@@ -5105,11 +7118,13 @@ export const IDL: OpenbookV2 = {
           name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
           name: 'referrerAccount',
@@ -5127,6 +7142,18 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'nativeMint',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'unwrapAccount',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
       ],
       args: [],
     },
@@ -5208,7 +7235,12 @@ export const IDL: OpenbookV2 = {
     },
     {
       name: 'sweepFees',
-      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."],
+      docs: [
+        "Sweep fees, as a [`Market`](crate::state::Market)'s admin.",
+        '',
+        'Base fees of markets with fees in base are swept when the base vault and a',
+        'base token receiver account are passed.',
+      ],
       accounts: [
         {
           name: 'collectFeeAdmin',
@@ -5240,6 +7272,21 @@ export const IDL: OpenbookV2 = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            'Only needed to sweep the base fees of markets with fees in base',
+          ],
+        },
+        {
+          name: 'baseTokenReceiverAccount',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
       ],
       args: [],
     },
@@ -5328,6 +7375,93 @@ export const IDL: OpenbookV2 = {
         },
       ],
     },
+    {
+      name: 'startAuction',
+      docs: [
+        'Put the market in a call auction until `auction_end_ts`, e.g. before trading',
+        'resumes (only [`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ],
+      accounts: [
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'auctionEndTs',
+          type: 'i64',
+        },
+      ],
+    },
+    {
+      name: 'uncrossAuction',
+      docs: [
+        'End the call auction of a market.',
+        '',
+        'While in auction, orders are posted to the book without matching. Once',
+        '`auction_end_ts` has passed, anyone can call this to match the crossing orders',
+        'at the single price that maximizes the matched volume. Both sides of each match',
+        'are settled as makers through [`FillEvent`](crate::state::FillEvent)s, without',
+        "taker fees. Each match logs a single `FillLog`, from the bid's event.",
+        '',
+        'Matches up to `limit` pairs of orders per call. Continuous trading starts once',
+        'no more orders cross at the clearing price.',
+      ],
+      accounts: [
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'marketStats',
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: ['Needed once the market has market stats'],
+        },
+      ],
+      args: [
+        {
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+    },
     {
       name: 'stubOracleCreate',
       accounts: [
@@ -5436,10 +7570,26 @@ export const IDL: OpenbookV2 = {
             name: 'quoteDecimals',
             type: 'u8',
           },
+          {
+            name: 'feesInBase',
+            docs: [
+              '1 if taker fees and maker rebates are paid in base instead of quote, see',
+              '`base_fees_available`',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'hasMarketStats',
+            docs: [
+              '1 once the market has a `MarketStats` account, which the instructions matching',
+              'orders need then',
+            ],
+            type: 'u8',
+          },
           {
             name: 'padding1',
             type: {
-              array: ['u8', 5],
+              array: ['u8', 3],
             },
           },
           {
@@ -5523,6 +7673,18 @@ export const IDL: OpenbookV2 = {
               defined: 'OracleConfig',
             },
           },
+          {
+            name: 'oracleTwapPrice',
+            docs: [
+              'Time weighted average of the accepted native oracle prices, zero until the first',
+            ],
+            type: 'f64',
+          },
+          {
+            name: 'oracleTwapLastUpdateTs',
+            docs: ['Timestamp in seconds of the last oracle TWAP update'],
+            type: 'u64',
+          },
           {
             name: 'quoteLotSize',
             docs: [
@@ -5567,72 +7729,195 @@ export const IDL: OpenbookV2 = {
             type: 'i64',
           },
           {
-            name: 'takerFee',
-            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'],
+            name: 'takerFee',
+            docs: ['Fee (in 10^-6) for taker orders, always >= 0.'],
+            type: 'i64',
+          },
+          {
+            name: 'feesAccrued',
+            docs: ['Total fees accrued in native quote'],
+            type: 'u128',
+          },
+          {
+            name: 'feesToReferrers',
+            docs: ['Total fees settled in native quote'],
+            type: 'u128',
+          },
+          {
+            name: 'referrerRebatesAccrued',
+            docs: ['Referrer rebates to be distributed'],
+            type: 'u64',
+          },
+          {
+            name: 'feesAvailable',
+            docs: ['Fees generated and available to withdraw via sweep_fees'],
+            type: 'u64',
+          },
+          {
+            name: 'makerVolume',
+            docs: [
+              'Cumulative maker volume (same as taker volume) in quote native units',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'takerVolumeWoOo',
+            docs: [
+              'Cumulative taker volume in quote native units due to place take orders',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'baseMint',
+            type: 'publicKey',
+          },
+          {
+            name: 'quoteMint',
+            type: 'publicKey',
+          },
+          {
+            name: 'marketBaseVault',
+            type: 'publicKey',
+          },
+          {
+            name: 'baseDepositTotal',
+            type: 'u64',
+          },
+          {
+            name: 'marketQuoteVault',
+            type: 'publicKey',
+          },
+          {
+            name: 'quoteDepositTotal',
+            type: 'u64',
+          },
+          {
+            name: 'minBaseLots',
+            docs: [
+              'Minimum number of base lots an order needs to have to be accepted.',
+              '',
+              'Remainders of partially matched orders below it are not posted.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'minQuoteLots',
+            docs: [
+              'Minimum notional value in quote lots an order needs to have to be accepted.',
+              '',
+              'Remainders of partially matched orders below it are not posted.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'tickSizeLots',
+            docs: [
+              'Prices of orders posted at a fixed price need to be a multiple of this, in',
+              'price lots. Values of 0 and 1 mean any price lot is fine.',
+            ],
             type: 'i64',
           },
           {
-            name: 'feesAccrued',
-            docs: ['Total fees accrued in native quote'],
-            type: 'u128',
+            name: 'auctionEndTs',
+            docs: [
+              'While non-zero the market is in a call auction: orders are posted without',
+              'matching until `uncross_auction` is called after this timestamp.',
+            ],
+            type: 'i64',
           },
           {
-            name: 'feesToReferrers',
-            docs: ['Total fees settled in native quote'],
-            type: 'u128',
+            name: 'auctionPriceLots',
+            docs: [
+              'Clearing price of the auction in price lots, set once the uncross started.',
+              'Zero while orders are still being collected.',
+            ],
+            type: 'i64',
           },
           {
-            name: 'referrerRebatesAccrued',
-            docs: ['Referrer rebates to be distributed'],
-            type: 'u64',
+            name: 'lastPriceLots',
+            docs: [
+              'Price in lots of the last fill. Zero if nothing traded yet.',
+            ],
+            type: 'i64',
           },
           {
-            name: 'feesAvailable',
-            docs: ['Fees generated and available to withdraw via sweep_fees'],
+            name: 'lastTradeTs',
+            docs: ['Timestamp in seconds of the last fill'],
             type: 'u64',
           },
           {
-            name: 'makerVolume',
+            name: 'fillTwapPriceLots',
             docs: [
-              'Cumulative maker volume (same as taker volume) in quote native units',
+              'Time weighted average of the fill prices in lots over `ORACLE_TWAP_SECONDS`, as',
+              'of `last_trade_ts`. Read by markets using this one as their oracle.',
             ],
-            type: 'u128',
+            type: 'f64',
           },
           {
-            name: 'takerVolumeWoOo',
+            name: 'baseFeesAvailable',
             docs: [
-              'Cumulative taker volume in quote native units due to place take orders',
+              'Base fees generated on markets with fees in base, available to withdraw via',
+              "sweep_fees. Referrers don't get a share of them.",
             ],
-            type: 'u128',
+            type: 'u64',
           },
           {
-            name: 'baseMint',
-            type: 'publicKey',
+            name: 'reserved',
+            type: {
+              array: ['u8', 56],
+            },
           },
+        ],
+      },
+    },
+    {
+      name: 'marketStats',
+      docs: [
+        'Rolling 24 hour trade statistics of a market, in hourly buckets',
+        '',
+        'Created for a market with `create_market_stats`. From then on all the instructions',
+        'matching orders of the market need it.',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'quoteMint',
+            name: 'market',
             type: 'publicKey',
           },
           {
-            name: 'marketBaseVault',
-            type: 'publicKey',
+            name: 'bump',
+            type: 'u8',
           },
           {
-            name: 'baseDepositTotal',
-            type: 'u64',
+            name: 'padding',
+            type: {
+              array: ['u8', 7],
+            },
           },
           {
-            name: 'marketQuoteVault',
-            type: 'publicKey',
+            name: 'lastTradeTs',
+            docs: ['Timestamp in seconds of the last recorded fill'],
+            type: 'u64',
           },
           {
-            name: 'quoteDepositTotal',
-            type: 'u64',
+            name: 'buckets',
+            docs: [
+              'Fills by hour, hour `h` since the epoch being kept in `buckets[h % 24]`',
+            ],
+            type: {
+              array: [
+                {
+                  defined: 'TradeStats',
+                },
+                24,
+              ],
+            },
           },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 128],
+              array: ['u8', 64],
             },
           },
         ],
@@ -5841,6 +8126,95 @@ export const IDL: OpenbookV2 = {
         ],
       },
     },
+    {
+      name: 'twapOrder',
+      docs: [
+        'Sells or buys `total_base_lots` over time, in slices of `slice_base_lots`',
+        '',
+        'Each slice is an ImmediateOrCancel order at `limit_price_lots`, placed by any',
+        'keeper through `execute_twap_slice` and paid from the free balances of the',
+        'open orders account. Slice `i` can be executed between `start_ts + i * interval_seconds`',
+        'and the start of the next slice, slices missing their window are skipped.',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'openOrdersAccount',
+            type: 'publicKey',
+          },
+          {
+            name: 'market',
+            type: 'publicKey',
+          },
+          {
+            name: 'bump',
+            type: 'u8',
+          },
+          {
+            name: 'side',
+            docs: ['Side, as a `Side` value'],
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 6],
+            },
+          },
+          {
+            name: 'limitPriceLots',
+            type: 'i64',
+          },
+          {
+            name: 'totalBaseLots',
+            type: 'i64',
+          },
+          {
+            name: 'sliceBaseLots',
+            type: 'i64',
+          },
+          {
+            name: 'remainingBaseLots',
+            docs: ['Base lots of the slices not executed or skipped yet'],
+            type: 'i64',
+          },
+          {
+            name: 'filledBaseLots',
+            docs: ['Base lots actually filled by the executed slices'],
+            type: 'i64',
+          },
+          {
+            name: 'startTs',
+            type: 'u64',
+          },
+          {
+            name: 'intervalSeconds',
+            type: 'u64',
+          },
+          {
+            name: 'nextSlice',
+            docs: [
+              "Index of the first slice that wasn't executed or skipped yet",
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'keeperFeeLamports',
+            docs: [
+              'Lamports paid to the keeper of each executed slice, out of the account balance',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 64],
+            },
+          },
+        ],
+      },
+    },
   ],
   types: [
     {
@@ -5858,6 +8232,33 @@ export const IDL: OpenbookV2 = {
         ],
       },
     },
+    {
+      name: 'TradeStats',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'highPriceLots',
+            docs: [
+              'Highest fill price in lots, only meaningful with a non-zero volume',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'lowPriceLots',
+            docs: [
+              'Lowest fill price in lots, only meaningful with a non-zero volume',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'volumeBaseLots',
+            docs: ['Filled base lots'],
+            type: 'i64',
+          },
+        ],
+      },
+    },
     {
       name: 'Position',
       type: {
@@ -5973,10 +8374,26 @@ export const IDL: OpenbookV2 = {
             name: 'maxStalenessSlots',
             type: 'i64',
           },
+          {
+            name: 'maxStalenessSeconds',
+            docs: [
+              'Disabled if not positive, so that markets created before it was carved out',
+              'of `reserved` keep it off. Checked against oracles that report their update time.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'maxTwapDeviation',
+            docs: [
+              'Largest accepted deviation of the oracle price from `Market::oracle_twap_price`,',
+              'relative to it. Disabled if zero.',
+            ],
+            type: 'f64',
+          },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 72],
+              array: ['u8', 40],
             },
           },
         ],
@@ -5997,6 +8414,18 @@ export const IDL: OpenbookV2 = {
               option: 'u32',
             },
           },
+          {
+            name: 'maxStalenessSeconds',
+            type: {
+              option: 'u32',
+            },
+          },
+          {
+            name: 'maxTwapDeviation',
+            type: {
+              option: 'f32',
+            },
+          },
         ],
       },
     },
@@ -6095,10 +8524,18 @@ export const IDL: OpenbookV2 = {
             name: 'makerSlot',
             type: 'u8',
           },
+          {
+            name: 'auction',
+            type: 'u8',
+          },
+          {
+            name: 'auctionMirror',
+            type: 'u8',
+          },
           {
             name: 'padding',
             type: {
-              array: ['u8', 4],
+              array: ['u8', 2],
             },
           },
           {
@@ -6106,7 +8543,7 @@ export const IDL: OpenbookV2 = {
             type: 'u64',
           },
           {
-            name: 'seqNum',
+            name: 'marketSeqNum',
             type: 'u64',
           },
           {
@@ -6478,6 +8915,10 @@ export const IDL: OpenbookV2 = {
             name: 'limit',
             type: 'u8',
           },
+          {
+            name: 'reduceOnly',
+            type: 'bool',
+          },
         ],
       },
     },
@@ -6498,6 +8939,10 @@ export const IDL: OpenbookV2 = {
             name: 'expiryTimestamp',
             type: 'u64',
           },
+          {
+            name: 'reduceOnly',
+            type: 'bool',
+          },
         ],
       },
     },
@@ -6552,11 +8997,82 @@ export const IDL: OpenbookV2 = {
             name: 'limit',
             type: 'u8',
           },
+          {
+            name: 'priceOffsetBps',
+            type: {
+              option: 'i32',
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: 'PlaceTakeOrderArgs',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'side',
+            type: {
+              defined: 'Side',
+            },
+          },
+          {
+            name: 'priceLots',
+            type: 'i64',
+          },
+          {
+            name: 'maxBaseLots',
+            type: 'i64',
+          },
+          {
+            name: 'maxQuoteLotsIncludingFees',
+            type: 'i64',
+          },
+          {
+            name: 'orderType',
+            type: {
+              defined: 'PlaceOrderType',
+            },
+          },
+          {
+            name: 'limit',
+            type: 'u8',
+          },
+        ],
+      },
+    },
+    {
+      name: 'RouteTakeOrderArgs',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'amountIn',
+            docs: [
+              'Native amount of the input token to sell on the first market.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'minAmountOut',
+            docs: [
+              'Minimum native amount of the output token to receive from the second market.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'limit',
+            docs: [
+              'Maximum number of orders to fill on each of the two markets.',
+            ],
+            type: 'u8',
+          },
         ],
       },
     },
     {
-      name: 'PlaceTakeOrderArgs',
+      name: 'CreateTwapOrderArgs',
       type: {
         kind: 'struct',
         fields: [
@@ -6567,26 +9083,33 @@ export const IDL: OpenbookV2 = {
             },
           },
           {
-            name: 'priceLots',
+            name: 'limitPriceLots',
+            docs: ['Worst price the slices are executed at.'],
             type: 'i64',
           },
           {
-            name: 'maxBaseLots',
+            name: 'totalBaseLots',
             type: 'i64',
           },
           {
-            name: 'maxQuoteLotsIncludingFees',
+            name: 'sliceBaseLots',
             type: 'i64',
           },
           {
-            name: 'orderType',
-            type: {
-              defined: 'PlaceOrderType',
-            },
+            name: 'intervalSeconds',
+            type: 'u64',
           },
           {
-            name: 'limit',
-            type: 'u8',
+            name: 'startTs',
+            docs: [
+              'Timestamp of the first slice, it starts right away if in the past.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'keeperFeeLamports',
+            docs: ['Lamports paid to the keeper executing a slice.'],
+            type: 'u64',
           },
         ],
       },
@@ -6611,6 +9134,21 @@ export const IDL: OpenbookV2 = {
           {
             name: 'RaydiumCLMM',
           },
+          {
+            name: 'OpenBookMarket',
+          },
+          {
+            name: 'OrcaWhirlpool',
+          },
+          {
+            name: 'RaydiumCPMM',
+          },
+          {
+            name: 'SwitchboardOnDemand',
+          },
+          {
+            name: 'OpenBookBookSide',
+          },
         ],
       },
     },
@@ -6705,6 +9243,12 @@ export const IDL: OpenbookV2 = {
           {
             name: 'FillOrKill',
           },
+          {
+            name: 'ExactOut',
+          },
+          {
+            name: 'PostOnlyCancelReplace',
+          },
         ],
       },
     },
@@ -6722,6 +9266,9 @@ export const IDL: OpenbookV2 = {
           {
             name: 'PostOnlySlide',
           },
+          {
+            name: 'PostOnlyCancelReplace',
+          },
         ],
       },
     },
@@ -6835,6 +9382,25 @@ export const IDL: OpenbookV2 = {
               },
             ],
           },
+          {
+            name: 'OraclePeggedBps',
+            fields: [
+              {
+                name: 'price_offset_bps',
+                type: 'i32',
+              },
+              {
+                name: 'order_type',
+                type: {
+                  defined: 'PostOrderType',
+                },
+              },
+              {
+                name: 'peg_limit',
+                type: 'i64',
+              },
+            ],
+          },
           {
             name: 'FillOrKill',
             fields: [
@@ -6844,6 +9410,15 @@ export const IDL: OpenbookV2 = {
               },
             ],
           },
+          {
+            name: 'ExactOut',
+            fields: [
+              {
+                name: 'price_lots',
+                type: 'i64',
+              },
+            ],
+          },
         ],
       },
     },
@@ -7177,6 +9752,176 @@ export const IDL: OpenbookV2 = {
         },
       ],
     },
+    {
+      name: 'OrderPlacedLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'owner',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'orderId',
+          type: 'u128',
+          index: false,
+        },
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'side',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'orderTree',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'priceData',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'pegLimit',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'price',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'quantity',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'timeInForce',
+          type: 'u16',
+          index: false,
+        },
+        {
+          name: 'timestamp',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'OrderCancelledLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'owner',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'orderId',
+          type: 'u128',
+          index: false,
+        },
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'side',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'orderTree',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'priceData',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'pegLimit',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'quantity',
+          type: 'i64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'OrderExpiredLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'owner',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'orderId',
+          type: 'u128',
+          index: false,
+        },
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'side',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'orderTree',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'priceData',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'pegLimit',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'quantity',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'expiry',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
   ],
   errors: [
     {
@@ -7399,5 +10144,110 @@ export const IDL: OpenbookV2 = {
       name: 'WouldExecutePartially',
       msg: 'Fill-Or-Kill order would generate a partial execution',
     },
+    {
+      code: 6044,
+      name: 'InvalidInputPegOffset',
+      msg: 'Peg offset should be given either in lots or in bps',
+    },
+    {
+      code: 6045,
+      name: 'InvalidInputMarketLimits',
+      msg: 'Market minimum order size and tick size cannot be negative',
+    },
+    {
+      code: 6046,
+      name: 'OrderBelowMinimumSize',
+      msg: 'Order size is below the market minimum',
+    },
+    {
+      code: 6047,
+      name: 'InvalidInputTickSize',
+      msg: 'Order price is not a multiple of the market tick size',
+    },
+    {
+      code: 6048,
+      name: 'WouldNotFillExactOut',
+      msg: 'Exact-out order would not deliver the full output amount',
+    },
+    {
+      code: 6049,
+      name: 'InvalidInputRouteMints',
+      msg: 'Route input, intermediate and output mints do not match the markets',
+    },
+    {
+      code: 6050,
+      name: 'RouteBelowMinimumOutput',
+      msg: 'Route output is below the requested minimum',
+    },
+    {
+      code: 6051,
+      name: 'InvalidInputAuction',
+      msg: "Auction end must be in the future and maker fees can't be negative",
+    },
+    {
+      code: 6052,
+      name: 'MarketInAuction',
+      msg: "Orders can't be matched while the market is in auction",
+    },
+    {
+      code: 6053,
+      name: 'MarketNotInAuction',
+      msg: 'Market is not in auction',
+    },
+    {
+      code: 6054,
+      name: 'AuctionNotEnded',
+      msg: 'Auction has not ended yet',
+    },
+    {
+      code: 6055,
+      name: 'InvalidInputNativeTransfer',
+      msg: 'Native SOL transfers without a token account need a native mint vault and the native accounts',
+    },
+    {
+      code: 6056,
+      name: 'InvalidInputVaultOracle',
+      msg: 'Pool vault oracles need the base vault as oracle a and the quote vault as oracle b, of the pool passed as the first remaining account',
+    },
+    {
+      code: 6057,
+      name: 'InvalidInputBookOracle',
+      msg: 'Book oracles need the bids and asks of a market with the same mints and lot sizes, passed as the first remaining account',
+    },
+    {
+      code: 6058,
+      name: 'InvalidInputTwap',
+      msg: 'TWAP total size, slice size and interval should be greater than zero',
+    },
+    {
+      code: 6059,
+      name: 'TwapSliceNotDue',
+      msg: 'The next slice of the TWAP order is not due yet',
+    },
+    {
+      code: 6060,
+      name: 'TwapOrderCompleted',
+      msg: 'All slices of the TWAP order were executed or skipped',
+    },
+    {
+      code: 6061,
+      name: 'InvalidInputPoolOracle',
+      msg: 'Pool oracles need to price the base mint in the quote mint and a max twap deviation',
+    },
+    {
+      code: 6062,
+      name: 'MissingMarketStats',
+      msg: 'The market has market stats, which need to be passed',
+    },
+    {
+      code: 6063,
+      name: 'InvalidInputMarketOracle',
+      msg: 'OpenBook market and book side oracles need a max twap deviation',
+    },
+    {
+      code: 6064,
+      name: 'ReduceOnlyExceedsFreeBalance',
+      msg: 'Reduce-only orders can only use the free balance, including taker fees in base',
+    },
   ],
 };
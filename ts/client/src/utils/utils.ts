@@ -21,6 +21,8 @@ export const PlaceOrderTypeUtils = {
   PostOnly: { postOnly: {} },
   Market: { market: {} },
   PostOnlySlide: { postOnlySlide: {} },
+  ExactOut: { exactOut: {} },
+  PostOnlyCancelReplace: { postOnlyCancelReplace: {} },
 };
 
 export const SelfTradeBehaviorUtils = {